@@ -44,31 +44,34 @@ pub fn noh_kt_run(c: &mut Criterion) {
 }
 
 pub fn euler1d_isot_conversions(c: &mut Criterion) {
-    const PHYSICS_CONFIG: PhysicsConfig = PhysicsConfig {
-        adiabatic_index: 1.4,
-        units_mode: UnitsMode::SI,
-    };
+    fn physics_config() -> PhysicsConfig {
+        PhysicsConfig {
+            adiabatic_index: 1.4,
+            units_mode: UnitsMode::SI,
+            ..Default::default()
+        }
+    }
     let mut group = c.benchmark_group("physics_conversions");
     group.sample_size(1000);
 
-    let mut u = State::<Euler1DIsot<S>, 2, S>::new(&PHYSICS_CONFIG);
+    let mut u = State::<Euler1DIsot<S>, 2, S>::new(&physics_config());
     u.cent.prim.row_mut(0).fill(2.0);
     u.cent.prim.row_mut(1).fill(3.0);
     group.bench_function("from prim to cons and back; euler 1d isot", |b| {
         b.iter(|| {
             u.update_cons();
-            u.update_prim();
+            u.update_prim().unwrap();
         })
     });
 
-    let mut u = State::<Euler1DAdiabatic<S>, 3, S>::new(&PHYSICS_CONFIG);
+    let mut u = State::<Euler1DAdiabatic<S>, 3, S>::new(&physics_config());
     u.cent.prim.row_mut(0).fill(2.0);
     u.cent.prim.row_mut(1).fill(3.0);
     u.cent.prim.row_mut(2).fill(4.0);
     group.bench_function("from prim to cons and back; euler 1d adiabatic", |b| {
         b.iter(|| {
             u.update_cons();
-            u.update_prim();
+            u.update_prim().unwrap();
         })
     });
 
@@ -22,10 +22,13 @@ fn main() -> Result<()> {
             mode: MeshMode::Cartesian,
             xi_in: 1.0,
             xi_out: 2.0,
+            n_gc: 2,
+            n_all: None,
         },
         physics_config: PhysicsConfig {
             adiabatic_index: 1.4,
             units_mode: UnitsMode::SI,
+            ..Default::default()
         },
         boundary_condition_west: BoundaryMode::Custom(vec![
             (0, CustomBoundaryMode::NoGradients),
@@ -52,6 +55,7 @@ fn main() -> Result<()> {
             dt_min: 1.0e-12,
             dt_max: f64::MAX,
             dt_cfl_param: 0.1,
+            mesh_motion: MeshMotion::Eulerian,
         },
         output_counter_max: 10,
         writer_config: vec![
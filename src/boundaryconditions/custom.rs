@@ -29,21 +29,21 @@ impl<const E: usize, const S: usize> BoundaryCondition<E, S> for CustomBoundaryC
                 CustomBoundaryMode::Extrapolate => extrapolate_west(*j, vars, mesh),
                 CustomBoundaryMode::ExtrapolateDensityKepler => extrapolate_density_kepler_west(*j, vars, mesh),
                 CustomBoundaryMode::ExtrapolateEtaVelocityKepler => extrapolate_etavel_kepler_west(*j, vars, mesh),
-                CustomBoundaryMode::NearZero => near_zero_west(*j, vars),
-                CustomBoundaryMode::NoGradients => no_gradients_west(*j, vars),
+                CustomBoundaryMode::NearZero => near_zero_west(*j, vars, mesh),
+                CustomBoundaryMode::NoGradients => no_gradients_west(*j, vars, mesh),
                 CustomBoundaryMode::OutFlowExtrapolate => outflow_extrapolate_west(*j, vars, mesh),
-                CustomBoundaryMode::OutflowNoGradients => outflow_no_gradients_west(*j, vars),
-                CustomBoundaryMode::Reflecting => reflecting_west(*j, vars),
+                CustomBoundaryMode::OutflowNoGradients => outflow_no_gradients_west(*j, vars, mesh),
+                CustomBoundaryMode::Reflecting => reflecting_west(*j, vars, mesh),
             }),
             Direction::East => self.modes.iter().for_each(|(j, mode)| match mode {
                 CustomBoundaryMode::Extrapolate => extrapolate_east(*j, vars, mesh),
                 CustomBoundaryMode::ExtrapolateDensityKepler => extrapolate_density_kepler_east(*j, vars, mesh),
                 CustomBoundaryMode::ExtrapolateEtaVelocityKepler => extrapolate_etavel_kepler_east(*j, vars, mesh),
-                CustomBoundaryMode::NearZero => near_zero_east(*j, vars),
-                CustomBoundaryMode::NoGradients => no_gradients_east(*j, vars),
+                CustomBoundaryMode::NearZero => near_zero_east(*j, vars, mesh),
+                CustomBoundaryMode::NoGradients => no_gradients_east(*j, vars, mesh),
                 CustomBoundaryMode::OutFlowExtrapolate => outflow_extrapolate_east(*j, vars, mesh),
-                CustomBoundaryMode::OutflowNoGradients => outflow_no_gradients_east(*j, vars),
-                CustomBoundaryMode::Reflecting => reflecting_east(*j, vars),
+                CustomBoundaryMode::OutflowNoGradients => outflow_no_gradients_east(*j, vars, mesh),
+                CustomBoundaryMode::Reflecting => reflecting_east(*j, vars, mesh),
             }),
             Direction::Cent => panic!("Cannot apply boundary condition for Direction::Cent!"),
         }
@@ -72,39 +72,41 @@ impl CustomBoundaryConditions {
 }
 
 fn extrapolate_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    let (i0, i1) = (mesh.ixi_in, mesh.ixi_in + 1);
     if mesh.is_logarithmic {
-        if !(1.0 / vars.prim[[j, 2]]).is_finite() || !(1.0 / vars.prim[[j, 3]]).is_finite() {
-            for i in 0..=1 {
+        if !(1.0 / vars.prim[[j, i0]]).is_finite() || !(1.0 / vars.prim[[j, i1]]).is_finite() {
+            for i in mesh.imin..i0 {
                 vars.prim[[j, i]] = 0.0;
             }
         } else {
-            for i in 1..=2 {
-                vars.prim[[j, 2 - i]] =
-                    vars.prim[[j, 2]] * (vars.prim[[j, 2]] / vars.prim[[j, 3]]).abs().powi(i as i32);
+            for k in 1..=mesh.n_gc {
+                vars.prim[[j, i0 - k]] =
+                    vars.prim[[j, i0]] * (vars.prim[[j, i0]] / vars.prim[[j, i1]]).abs().powi(k as i32);
             }
         }
     } else {
-        for i in 1..=2 {
-            vars.prim[[j, 2 - i]] = (i + 1) as f64 * vars.prim[[j, 2]] - i as f64 * vars.prim[[j, 3]];
+        for k in 1..=mesh.n_gc {
+            vars.prim[[j, i0 - k]] = (k + 1) as f64 * vars.prim[[j, i0]] - k as f64 * vars.prim[[j, i1]];
         }
     }
 }
 
 fn extrapolate_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    let (i0, i1) = (mesh.ixi_out, mesh.ixi_out - 1);
     if mesh.is_logarithmic {
-        if !(1.0 / vars.prim[[j, S - 3]]).is_finite() || !(1.0 / vars.prim[[j, S - 4]]).is_finite() {
-            for i in S - 2..=S - 1 {
+        if !(1.0 / vars.prim[[j, i0]]).is_finite() || !(1.0 / vars.prim[[j, i1]]).is_finite() {
+            for i in i0 + 1..=mesh.imax {
                 vars.prim[[j, i]] = 0.0;
             }
         } else {
-            for i in 1..=2 {
-                vars.prim[[j, S - 3 + i]] =
-                    vars.prim[[j, S - 3]] * (vars.prim[[j, S - 3]] / vars.prim[[j, S - 4]]).abs().powi(i as i32);
+            for k in 1..=mesh.n_gc {
+                vars.prim[[j, i0 + k]] =
+                    vars.prim[[j, i0]] * (vars.prim[[j, i0]] / vars.prim[[j, i1]]).abs().powi(k as i32);
             }
         }
     } else {
-        for i in 1..=2 {
-            vars.prim[[j, S - 3 + i]] = (i + 1) as f64 * vars.prim[[j, S - 3]] - i as f64 * vars.prim[[j, S - 4]];
+        for k in 1..=mesh.n_gc {
+            vars.prim[[j, i0 + k]] = (k + 1) as f64 * vars.prim[[j, i0]] - k as f64 * vars.prim[[j, i1]];
         }
     }
 }
@@ -114,7 +116,7 @@ fn extrapolate_density_kepler_west<const E: usize, const S: usize>(
     vars: &mut Variables<E, S>,
     mesh: &Mesh<S>,
 ) {
-    for i in (0..=1).rev() {
+    for i in (mesh.imin..mesh.ixi_in).rev() {
         vars.prim[[j, i]] = vars.prim[[j, i + 1]] / (mesh.xi_cent[i] * mesh.xi_cent_inv[i + 1]).sqrt();
     }
 }
@@ -124,7 +126,7 @@ fn extrapolate_density_kepler_east<const E: usize, const S: usize>(
     vars: &mut Variables<E, S>,
     mesh: &Mesh<S>,
 ) {
-    for i in S - 2..=S - 1 {
+    for i in mesh.ixi_out + 1..=mesh.imax {
         vars.prim[[j, i]] = vars.prim[[j, i - 1]] / (mesh.xi_cent[i] * mesh.xi_cent_inv[i - 1]).sqrt();
     }
 }
@@ -134,7 +136,7 @@ fn extrapolate_etavel_kepler_west<const E: usize, const S: usize>(
     vars: &mut Variables<E, S>,
     mesh: &Mesh<S>,
 ) {
-    for i in (0..=1).rev() {
+    for i in (mesh.imin..mesh.ixi_in).rev() {
         vars.prim[[j, i]] = vars.prim[[j, i + 1]] / (mesh.xi_cent[i] * mesh.xi_cent_inv[i + 1]).sqrt();
     }
 }
@@ -144,76 +146,76 @@ fn extrapolate_etavel_kepler_east<const E: usize, const S: usize>(
     vars: &mut Variables<E, S>,
     mesh: &Mesh<S>,
 ) {
-    for i in S - 2..=S - 1 {
+    for i in mesh.ixi_out + 1..=mesh.imax {
         vars.prim[[j, i]] = vars.prim[[j, i - 1]] / (mesh.xi_cent[i] * mesh.xi_cent_inv[i - 1]).sqrt();
     }
 }
 
-fn near_zero_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    for i in 0..=1 {
-        vars.prim[[j, i]] = 1.0e-10 * vars.prim[[j, 2]];
+fn near_zero_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    for i in mesh.imin..mesh.ixi_in {
+        vars.prim[[j, i]] = 1.0e-10 * vars.prim[[j, mesh.ixi_in]];
     }
 }
 
-fn near_zero_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    for i in S - 2..=S - 1 {
-        vars.prim[[j, i]] = 1.0e-10 * vars.prim[[j, S - 3]];
+fn near_zero_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    for i in mesh.ixi_out + 1..=mesh.imax {
+        vars.prim[[j, i]] = 1.0e-10 * vars.prim[[j, mesh.ixi_out]];
     }
 }
 
-fn no_gradients_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    for i in 0..=1 {
-        vars.prim[[j, i]] = vars.prim[[j, 2]];
+fn no_gradients_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    for i in mesh.imin..mesh.ixi_in {
+        vars.prim[[j, i]] = vars.prim[[j, mesh.ixi_in]];
     }
 }
 
-fn no_gradients_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    for i in S - 2..=S - 1 {
-        vars.prim[[j, i]] = vars.prim[[j, S - 3]];
+fn no_gradients_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    for i in mesh.ixi_out + 1..=mesh.imax {
+        vars.prim[[j, i]] = vars.prim[[j, mesh.ixi_out]];
     }
 }
 
-fn outflow_no_gradients_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    if vars.prim[[j, 2]].is_sign_positive() {
-        reflecting_west(j, vars);
+fn outflow_no_gradients_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    if vars.prim[[j, mesh.ixi_in]].is_sign_positive() {
+        reflecting_west(j, vars, mesh);
     } else {
-        no_gradients_west(j, vars);
+        no_gradients_west(j, vars, mesh);
     }
 }
 
-fn outflow_no_gradients_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    if vars.prim[[j, S - 3]].is_sign_negative() {
-        reflecting_east(j, vars);
+fn outflow_no_gradients_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    if vars.prim[[j, mesh.ixi_out]].is_sign_negative() {
+        reflecting_east(j, vars, mesh);
     } else {
-        no_gradients_east(j, vars);
+        no_gradients_east(j, vars, mesh);
     }
 }
 
 fn outflow_extrapolate_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
-    if vars.prim[[j, 2]].is_sign_positive() {
-        reflecting_west(j, vars);
+    if vars.prim[[j, mesh.ixi_in]].is_sign_positive() {
+        reflecting_west(j, vars, mesh);
     } else {
         extrapolate_west(j, vars, mesh);
     }
 }
 
 fn outflow_extrapolate_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
-    if vars.prim[[j, S - 3]].is_sign_negative() {
-        reflecting_east(j, vars);
+    if vars.prim[[j, mesh.ixi_out]].is_sign_negative() {
+        reflecting_east(j, vars, mesh);
     } else {
         extrapolate_east(j, vars, mesh);
     }
 }
 
-fn reflecting_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    for i in 1..=2 {
-        vars.prim[[j, 2 - i]] = -1.0 * vars.prim[[j, i + 1]];
+fn reflecting_west<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    for k in 1..=mesh.n_gc {
+        vars.prim[[j, mesh.ixi_in - k]] = -1.0 * vars.prim[[j, mesh.ixi_in + k - 1]];
     }
 }
 
-fn reflecting_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>) {
-    for i in 1..=2 {
-        vars.prim[[j, S - 3 + i]] = -1.0 * vars.prim[[j, S - 2 - i]];
+fn reflecting_east<const E: usize, const S: usize>(j: usize, vars: &mut Variables<E, S>, mesh: &Mesh<S>) {
+    for k in 1..=mesh.n_gc {
+        vars.prim[[j, mesh.ixi_out + k]] = -1.0 * vars.prim[[j, mesh.ixi_out + 1 - k]];
     }
 }
 
@@ -233,17 +235,22 @@ mod tests {
         mode: MeshMode::Cartesian,
         xi_in: 2.0,
         xi_out: 3.0,
+        n_gc: 2,
+        n_all: None,
     };
-    const PHYSICSCONFIG: PhysicsConfig = PhysicsConfig {
-        adiabatic_index: 1.4,
-        units_mode: crate::UnitsMode::SI,
-    };
+    fn physics_config() -> PhysicsConfig {
+        PhysicsConfig {
+            adiabatic_index: 1.4,
+            units_mode: crate::UnitsMode::SI,
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn extrapolation_test() {
         // Also test this on log meshes
         let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
-        let mut vars: Variables<E, S> = Variables::new(&PHYSICSCONFIG);
+        let mut vars: Variables<E, S> = Variables::new(&physics_config());
         vars.prim.assign(
             &Array2::from_shape_vec(
                 (E, S),
@@ -270,7 +277,8 @@ mod tests {
 
     #[test]
     fn near_zero_test() {
-        let mut vars: Variables<E, S> = Variables::new(&PHYSICSCONFIG);
+        let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
+        let mut vars: Variables<E, S> = Variables::new(&physics_config());
         vars.prim.assign(
             &Array2::from_shape_vec(
                 (E, S),
@@ -290,15 +298,16 @@ mod tests {
         )
         .unwrap();
         for j in 0..E {
-            near_zero_west(j, &mut vars);
-            near_zero_east(j, &mut vars);
+            near_zero_west(j, &mut vars, &mesh);
+            near_zero_east(j, &mut vars, &mesh);
         }
         assert_relative_eq!(vars.prim, prim_expect, max_relative = 1.0e-12);
     }
 
     #[test]
     fn no_gradients_test() {
-        let mut vars: Variables<E, S> = Variables::new(&PHYSICSCONFIG);
+        let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
+        let mut vars: Variables<E, S> = Variables::new(&physics_config());
         vars.prim.assign(
             &Array2::from_shape_vec(
                 (E, S),
@@ -319,8 +328,8 @@ mod tests {
         .unwrap();
 
         for j in 0..E {
-            no_gradients_west(j, &mut vars);
-            no_gradients_east(j, &mut vars);
+            no_gradients_west(j, &mut vars, &mesh);
+            no_gradients_east(j, &mut vars, &mesh);
         }
 
         assert_relative_eq!(vars.prim, prim_expect, max_relative = 1.0e-12);
@@ -328,7 +337,8 @@ mod tests {
 
     #[test]
     fn outflow_no_gradients_test() {
-        let mut vars: Variables<E, S> = Variables::new(&PHYSICSCONFIG);
+        let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
+        let mut vars: Variables<E, S> = Variables::new(&physics_config());
         vars.prim.assign(
             &Array2::from_shape_vec(
                 (E, S),
@@ -347,8 +357,57 @@ mod tests {
         )
         .unwrap();
         for j in 0..E {
-            outflow_no_gradients_west(j, &mut vars);
-            outflow_no_gradients_east(j, &mut vars);
+            outflow_no_gradients_west(j, &mut vars, &mesh);
+            outflow_no_gradients_east(j, &mut vars, &mesh);
+        }
+        assert_relative_eq!(vars.prim, prim_expect, max_relative = 1.0e-12);
+    }
+
+    #[test]
+    fn extrapolation_log_test() {
+        let mesh: Mesh<S> = Mesh::new(&MeshConfig {
+            mode: MeshMode::LogCylindrical,
+            ..MESHCONFIG
+        })
+        .unwrap();
+        let mut vars: Variables<E, S> = Variables::new(&physics_config());
+        vars.prim.assign(
+            &Array2::from_shape_vec(
+                (E, S),
+                vec![
+                    0.0, 0.0, 4.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0, 3.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+                ],
+            )
+            .unwrap()
+            .view(),
+        );
+        let prim_expect = Array2::from_shape_vec(
+            (E, S),
+            vec![
+                16.0,
+                8.0,
+                4.0,
+                2.0,
+                1.0,
+                0.5,
+                1.0 / 9.0,
+                1.0 / 3.0,
+                1.0,
+                3.0,
+                9.0,
+                27.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+            ],
+        )
+        .unwrap();
+        for j in 0..E {
+            extrapolate_west(j, &mut vars, &mesh);
+            extrapolate_east(j, &mut vars, &mesh);
         }
         assert_relative_eq!(vars.prim, prim_expect, max_relative = 1.0e-12);
     }
@@ -357,7 +416,7 @@ mod tests {
     fn outflow_extrapolate_test() {
         // Also test this on log meshes
         let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
-        let mut vars: Variables<E, S> = Variables::new(&PHYSICSCONFIG);
+        let mut vars: Variables<E, S> = Variables::new(&physics_config());
         vars.prim.assign(
             &Array2::from_shape_vec(
                 (E, S),
@@ -385,7 +444,8 @@ mod tests {
     #[test]
     fn reflecting_test() {
         // Also test this on log meshes
-        let mut vars: Variables<E, S> = Variables::new(&PHYSICSCONFIG);
+        let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
+        let mut vars: Variables<E, S> = Variables::new(&physics_config());
         vars.prim.assign(
             &Array2::from_shape_vec(
                 (E, S),
@@ -404,9 +464,42 @@ mod tests {
         )
         .unwrap();
         for j in 0..E {
-            reflecting_west(j, &mut vars);
-            reflecting_east(j, &mut vars);
+            reflecting_west(j, &mut vars, &mesh);
+            reflecting_east(j, &mut vars, &mesh);
         }
         assert_relative_eq!(vars.prim, prim_expect, max_relative = 1.0e-12);
     }
+
+    #[test]
+    fn three_ghost_cells_test() {
+        const S3: usize = 10;
+        let mesh: Mesh<S3> = Mesh::new(&MeshConfig { n_gc: 3, ..MESHCONFIG }).unwrap();
+        let mut vars: Variables<E, S3> = Variables::new(&physics_config());
+        vars.prim.assign(
+            &Array2::from_shape_vec(
+                (E, S3),
+                vec![
+                    0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.25, 1.0, 2.0, -0.5, 0.0, 0.0,
+                    0.0, 0.0, 0.0, 0.0, 3.0, 4.0, 5.0, 6.0, 0.0, 0.0, 0.0,
+                ],
+            )
+            .unwrap()
+            .view(),
+        );
+        let prim_expect = Array2::from_shape_vec(
+            (E, S3),
+            vec![
+                -2.0, -1.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 0.25, 0.25, 0.25, 0.25, 1.0, 2.0, -0.5, -0.5, -0.5,
+                -0.5, -5.0, -4.0, -3.0, 3.0, 4.0, 5.0, 6.0, -6.0, -5.0, -4.0,
+            ],
+        )
+        .unwrap();
+        extrapolate_west(0, &mut vars, &mesh);
+        extrapolate_east(0, &mut vars, &mesh);
+        no_gradients_west(1, &mut vars, &mesh);
+        no_gradients_east(1, &mut vars, &mesh);
+        reflecting_west(2, &mut vars, &mesh);
+        reflecting_east(2, &mut vars, &mesh);
+        assert_relative_eq!(vars.prim, prim_expect, max_relative = 1.0e-12);
+    }
 }
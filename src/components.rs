@@ -69,7 +69,7 @@ where
     fn run_corries(&mut self) -> Result<()> {
         // source: https://stackoverflow.com/questions/41207306/mutable-reference-to-a-tuple-as-input-parameter
         // This is where I learned what the ref keyword is...
-        let (ref mut u, ref mut solver, ref mesh, ref mut writer) = *self;
+        let (ref mut u, ref mut solver, ref mut mesh, ref mut writer) = *self;
         loop {
             if solver.timestep.t >= solver.timestep.t_next_output - solver.timestep.dt_min {
                 solver.timestep.t_next_output += solver.timestep.dt_output;
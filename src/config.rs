@@ -5,17 +5,22 @@
 //! Exports the [CorriesConfig] structs and its nested structs for configuring Corries simulations.
 
 use crate::{components::*, errorhandling::Validation, initfuncs::InitFn, prelude::*};
-use color_eyre::{eyre::Context, Result};
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
+};
 pub use meshconfig::*;
 pub use numericsconfig::*;
 pub use outputconfig::*;
 pub use physicsconfig::*;
+pub use refinementconfig::*;
 use serde::Serialize;
 
 pub mod meshconfig;
 pub mod numericsconfig;
 pub mod outputconfig;
 pub mod physicsconfig;
+pub mod refinementconfig;
 
 /// Enumerates the different boundary conditions.
 ///
@@ -210,9 +215,114 @@ impl CorriesConfig {
         T: TimeSolver<P, E, S>,
     {
         self.validate().context("Validating CorriesConfig")?;
+        P::validate_physics_config(&self.physics_config)
+            .context("Validating PhysicsConfig against the Physics type")?;
+        ensure!(
+            !P::IS_ADIABATIC || self.physics_config.c_sound_profile == SoundSpeedProfile::Global,
+            "Adiabatic systems need PhysicsConfig::c_sound_profile to be SoundSpeedProfile::Global! Got {:?}",
+            self.physics_config.c_sound_profile
+        );
+        ensure!(
+            P::HAS_BOTTOM || self.physics_config.systems.shallow_water.bathymetry == Bathymetry::Flat,
+            "Only systems with a bottom, like ShallowWater, use ShallowWaterConfig::bathymetry, so it needs to be \
+             Bathymetry::Flat for {}!",
+            P::name()
+        );
+        ensure!(
+            P::HAS_GEOMETRIC_SOURCE || self.mesh_config.mode.geometry() == MeshGeometry::Cartesian,
+            "{} does not provide the geometric source terms of curvilinear meshes, so it needs a cartesian mesh! \
+             Got {:?}",
+            P::name(),
+            self.mesh_config.mode
+        );
+        ensure!(
+            self.physics_config.systems.shallow_water.bathymetry == Bathymetry::Flat
+                || self.numerics_config.mesh_motion == MeshMotion::Eulerian,
+            "A bathymetry needs a mesh that does not move! Got mesh_motion = {:?}",
+            self.numerics_config.mesh_motion
+        );
+        ensure!(
+            self.physics_config.systems.drag.is_none() || P::HAS_DUST,
+            "Only systems with dust, like DustyGas, use SystemsConfig::drag, so it needs to be None for {}!",
+            P::name()
+        );
+        ensure!(
+            self.physics_config.systems.network.is_none() || P::HAS_REACTIONS,
+            "Only systems with reacting species, like ReactingFlow, use SystemsConfig::network, so it needs to be \
+             None for {}!",
+            P::name()
+        );
+        ensure!(
+            self.physics_config.systems.radiation.is_none() || P::HAS_RADIATION,
+            "Only systems with radiation, like RadiationHydro, use SystemsConfig::radiation, so it needs to be None \
+             for {}!",
+            P::name()
+        );
+        if let Some(transport) = &self.physics_config.transport {
+            ensure!(
+                P::IS_NEWTONIAN_GAS,
+                "PhysicsConfig::transport needs a newtonian gas with a mass density and a xi velocity! Got {}",
+                P::name()
+            );
+            ensure!(
+                transport.thermal_conductivity == 0.0 || P::IS_ADIABATIC,
+                "Heat conduction needs an energy equation, so PhysicsConfig::transport::thermal_conductivity needs \
+                 to be 0.0 for {}!",
+                P::name()
+            );
+            ensure!(
+                !P::IS_ADIABATIC
+                    || self.physics_config.eos == EosConfig::IdealGas
+                    || (transport.thermal_conductivity == 0.0 && matches!(transport.viscosity, Viscosity::Constant(_))),
+                "The temperature in PhysicsConfig::transport follows the ideal gas law, so {} may neither conduct \
+                 heat nor use a temperature dependent viscosity!",
+                P::name()
+            );
+            ensure!(
+                transport.viscosity.is_zero() || self.mesh_config.mode.geometry() == MeshGeometry::Cartesian,
+                "The viscosity in PhysicsConfig::transport needs a cartesian mesh! Got {:?}",
+                self.mesh_config.mode
+            );
+        }
+        if self.physics_config.alpha_viscosity.is_some() {
+            ensure!(
+                P::JRHO != usize::MAX && P::JETA != usize::MAX,
+                "PhysicsConfig::alpha_viscosity needs a rotating system with a mass density and an eta velocity! Got \
+                 {}",
+                P::name()
+            );
+            ensure!(
+                self.mesh_config.mode.geometry() == MeshGeometry::Cylindrical,
+                "PhysicsConfig::alpha_viscosity needs a cylindrical mesh! Got {:?}",
+                self.mesh_config.mode
+            );
+            ensure!(
+                matches!(self.physics_config.potential, Some(Potential::PointMass { .. })),
+                "PhysicsConfig::alpha_viscosity takes the Kepler frequency from the central mass, so \
+                 PhysicsConfig::potential needs to be a Potential::PointMass! Got {:?}",
+                self.physics_config.potential
+            );
+        }
+        ensure!(
+            self.physics_config.potential.is_none() || P::IS_NEWTONIAN_GAS,
+            "PhysicsConfig::potential needs a newtonian gas with a mass density and a xi velocity! Got {}",
+            P::name()
+        );
+        if let Some(Potential::PointMass { softening, .. }) = self.physics_config.potential {
+            ensure!(
+                softening > 0.0 || self.mesh_config.xi_in > 0.0 || self.mesh_config.xi_out < 0.0,
+                "A Potential::PointMass needs a positive softening if the mesh touches the origin! Got softening = \
+                 {} and xi in [{}, {}]",
+                softening,
+                self.mesh_config.xi_in,
+                self.mesh_config.xi_out
+            );
+        }
 
         let mesh = Mesh::<S>::new(&self.mesh_config).context("Constructing Mesh")?;
-        let mut u = State::<P, E, S>::new(&self.physics_config);
+        let mut u = State::<P, E, S>::with_size(&self.physics_config, mesh.n_all);
+        u.apply_bathymetry(&mesh)
+            .context("Applying the bathymetry in CorriesConfig::init_corries")?;
         let mut solver = Solver::<P, N, T, E, S>::new(self, &mesh).context("Constructing Solver")?;
         let mut writer = Writer::new::<S>(self, &mesh).context("Constructing Writer")?;
 
@@ -231,6 +341,8 @@ impl CorriesConfig {
             .context("Calling writer.write_metadata in run_corries")?;
 
         init_fn(&mut u, &mut solver, &mesh).context("Calling init_fn in CorriesConfig::init_corries")?;
+        u.apply_c_sound_profile(&mesh)
+            .context("Applying the speed of sound profile in CorriesConfig::init_corries")?;
         u.update_vars_from_prim(&mut solver.rhs.boundary_west, &mut solver.rhs.boundary_east, &mesh);
         u.init_west_east();
         Ok((u, solver, mesh, writer))
@@ -4,20 +4,123 @@
 
 //! Exports [MeshConfig] for configuring the [Mesh](crate::mesh::Mesh) of the simulation.
 
-use crate::errorhandling::Validation;
-use color_eyre::{eyre::ensure, Result};
-use serde::Serialize;
+use crate::{errorhandling::Validation, mesh::RUNTIME_SIZE};
+use color_eyre::{
+    eyre::{bail, ensure},
+    Result,
+};
+use serde::{Serialize, Serializer};
 
 /// Enum for the different kinds of Meshes available
-#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Clone, PartialEq)]
 pub enum MeshMode {
     /// Cartesian mesh
     Cartesian,
+
+    /// Cylindrical mesh, where xi = r is the radius, eta is the azimuthal angle, and Phi = z is the
+    /// height. The mesh assumes axisymmetry, i.e. the full annulus is covered by one cell along eta.
+    /// xi_in may be set to 0.0, in which case the western ghost cells mirror the cells at the axis.
+    Cylindrical,
+
+    /// Like [Cylindrical](MeshMode::Cylindrical), but the cells are spaced logarithmically along
+    /// the radius, i.e. `dxi` is the (constant) cell width in `ln(r)`.
+    LogCylindrical,
+
+    /// Spherical mesh, where xi = r is the radius, eta is the polar angle, and Phi is the azimuthal
+    /// angle. The mesh assumes spherical symmetry, i.e. every cell is a full spherical shell.
+    /// xi_in may be set to 0.0, in which case the western ghost cells mirror the cells at the
+    /// origin.
+    Spherical,
+
+    /// Like [Spherical](MeshMode::Spherical), but the cells are spaced logarithmically along the
+    /// radius, i.e. `dxi` is the (constant) cell width in `ln(r)`.
+    LogSpherical,
+
+    /// Non-uniform mesh with the given `geometry`, where the cell faces are derived from `mapping`.
+    /// `dxi` is the (constant) cell width in the uniform coordinate that `mapping` maps onto xi.
+    Mapped {
+        /// The geometry of the mesh
+        geometry: MeshGeometry,
+
+        /// Determines the cell faces of the computational area
+        mapping: MeshMapping,
+    },
 }
 
 unsafe impl Send for MeshMode {}
 unsafe impl Sync for MeshMode {}
 
+impl MeshMode {
+    /// Returns the [MeshGeometry] of this [MeshMode].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use corries::prelude::*;
+    ///
+    /// assert_eq!(MeshMode::LogCylindrical.geometry(), MeshGeometry::Cylindrical);
+    /// ```
+    pub fn geometry(&self) -> MeshGeometry {
+        match self {
+            MeshMode::Cartesian => MeshGeometry::Cartesian,
+            MeshMode::Cylindrical | MeshMode::LogCylindrical => MeshGeometry::Cylindrical,
+            MeshMode::Spherical | MeshMode::LogSpherical => MeshGeometry::Spherical,
+            MeshMode::Mapped { geometry, .. } => *geometry,
+        }
+    }
+}
+
+/// Enumerates the geometries a [Mesh](crate::mesh::Mesh) can have, independent of how its cells
+/// are spaced.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+pub enum MeshGeometry {
+    /// Cartesian geometry
+    Cartesian,
+
+    /// Cylindrical geometry, see [MeshMode::Cylindrical]
+    Cylindrical,
+
+    /// Spherical geometry, see [MeshMode::Spherical]
+    Spherical,
+}
+
+unsafe impl Send for MeshGeometry {}
+unsafe impl Sync for MeshGeometry {}
+
+/// Enumerates the ways the cell faces of a [MeshMode::Mapped] mesh can be defined.
+///
+/// In both cases, the ghost cells continue the mesh with the widths of the outermost cells of the
+/// computational area, unless the mesh is curvilinear and starts at the origin, in which case the
+/// western ghost cells mirror the cells at the origin.
+#[derive(Debug, Serialize, Clone)]
+pub enum MeshMapping {
+    /// Strictly increasing function `f` with `f(0.0) == 0.0` and `f(1.0) == 1.0`, such that the
+    /// cell faces are placed at `xi = xi_in + (xi_out - xi_in) * f(s)` for a uniformly spaced `s`
+    /// in `[0.0, 1.0]`.
+    Function(#[serde(serialize_with = "serialize_mapping_fn")] fn(f64) -> f64),
+
+    /// Explicit cell faces of the computational area, i.e. `S - 2 * n_gc + 1` strictly increasing
+    /// values, starting with `xi_in` and ending with `xi_out`.
+    Faces(Vec<f64>),
+}
+
+unsafe impl Send for MeshMapping {}
+unsafe impl Sync for MeshMapping {}
+
+impl PartialEq for MeshMapping {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Function(f), Self::Function(g)) => std::ptr::fn_addr_eq(*f, *g),
+            (Self::Faces(a), Self::Faces(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn serialize_mapping_fn<T: Serializer>(_: &fn(f64) -> f64, serializer: T) -> Result<T::Ok, T::Error> {
+    serializer.serialize_str("user defined function")
+}
+
 /// Carries information about how the mesh should shaped
 #[derive(Debug, Serialize, Clone)]
 pub struct MeshConfig {
@@ -29,11 +132,19 @@ pub struct MeshConfig {
 
     /// xi coordinate at the outer/eastern boundary of the computational area
     pub xi_out: f64,
+
+    /// The number of ghost cells at each edge of the mesh; needs to be at least 2
+    pub n_gc: usize,
+
+    /// The number of cells in the mesh, including ghost cells. Runtime-sized meshes, built with
+    /// `S =` [RUNTIME_SIZE], need this to be set and take their size from it. For any other `S`,
+    /// this is optional and has to match `S` if it is set.
+    pub n_all: Option<usize>,
 }
 
 impl MeshConfig {
     /// Sets up a MeshConfig that can be used for most Riemann tests, which uses a Cartesian mesh
-    /// with coordinate boundaries set to 1.0 and 2.0.
+    /// with coordinate boundaries set to 1.0 and 2.0, and 2 ghost cells at each edge.
     ///
     /// # Examples
     ///
@@ -44,12 +155,48 @@ impl MeshConfig {
     /// assert_eq!(meshconfig.mode, MeshMode::Cartesian);
     /// assert_eq!(meshconfig.xi_in, 1.0);
     /// assert_eq!(meshconfig.xi_out, 2.0);
+    /// assert_eq!(meshconfig.n_gc, 2);
+    /// assert_eq!(meshconfig.n_all, None);
     /// ```
     pub fn default_riemann_test() -> Self {
         Self {
             mode: MeshMode::Cartesian,
             xi_in: 1.0,
             xi_out: 2.0,
+            n_gc: 2,
+            n_all: None,
+        }
+    }
+
+    /// Returns the number of cells of a [Mesh](crate::mesh::Mesh) with the size `S` built from
+    /// this config, i.e. [MeshConfig::n_all] for runtime-sized meshes and `S` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use corries::prelude::*;
+    ///
+    /// let mut meshconfig = MeshConfig::default_riemann_test();
+    /// assert_eq!(meshconfig.n_all_for::<100>().unwrap(), 100);
+    /// assert!(meshconfig.n_all_for::<RUNTIME_SIZE>().is_err());
+    ///
+    /// meshconfig.n_all = Some(50);
+    /// assert_eq!(meshconfig.n_all_for::<RUNTIME_SIZE>().unwrap(), 50);
+    /// assert!(meshconfig.n_all_for::<100>().is_err());
+    /// ```
+    pub fn n_all_for<const S: usize>(&self) -> Result<usize> {
+        match self.n_all {
+            None if S == RUNTIME_SIZE => bail!("Runtime-sized meshes need MeshConfig::n_all to be set!"),
+            None => Ok(S),
+            Some(n_all) => {
+                ensure!(
+                    S == RUNTIME_SIZE || n_all == S,
+                    "MeshConfig::n_all does not match the mesh's size S! Got n_all = {} ; S = {}",
+                    n_all,
+                    S
+                );
+                Ok(n_all)
+            },
         }
     }
 }
@@ -65,6 +212,63 @@ impl Validation for MeshConfig {
             self.xi_in,
             self.xi_out
         );
+        ensure!(self.n_gc >= 2, "This must hold: n_gc >= 2! Got n_gc = {}", self.n_gc);
+        if let Some(n_all) = self.n_all {
+            ensure!(
+                n_all >= 3 * self.n_gc,
+                "This must hold: n_all >= 3 * n_gc! Got n_all = {} ; n_gc = {}",
+                n_all,
+                self.n_gc
+            );
+        }
+        match &self.mode {
+            MeshMode::Cartesian => (),
+            MeshMode::Mapped {
+                geometry: MeshGeometry::Cartesian,
+                ..
+            } => (),
+            MeshMode::Cylindrical | MeshMode::Spherical | MeshMode::Mapped { .. } => ensure!(
+                self.xi_in >= 0.0,
+                "This must hold for {:?} meshes: xi_in >= 0! Got xi_in = {}",
+                self.mode,
+                self.xi_in
+            ),
+            MeshMode::LogCylindrical | MeshMode::LogSpherical => ensure!(
+                self.xi_in > 0.0,
+                "This must hold for {:?} meshes: xi_in > 0! Got xi_in = {}",
+                self.mode,
+                self.xi_in
+            ),
+        };
+        if let MeshMode::Mapped { mapping, .. } = &self.mode {
+            match mapping {
+                MeshMapping::Function(f) => ensure!(
+                    f(0.0).abs() < 1.0e-12 && (f(1.0) - 1.0).abs() < 1.0e-12,
+                    "This must hold for mapping functions: f(0.0) == 0.0 and f(1.0) == 1.0! Got f(0.0) = {} ; \
+                     f(1.0) = {}",
+                    f(0.0),
+                    f(1.0)
+                ),
+                MeshMapping::Faces(faces) => {
+                    ensure!(
+                        faces.len() >= 2,
+                        "Mapping faces need to have at least two entries! Got {} entries",
+                        faces.len()
+                    );
+                    ensure!(
+                        faces[0] == self.xi_in && faces[faces.len() - 1] == self.xi_out,
+                        "Mapping faces need to start at xi_in and end at xi_out! Got faces[0] = {} ; \
+                         faces[last] = {}",
+                        faces[0],
+                        faces[faces.len() - 1]
+                    );
+                    ensure!(
+                        faces.windows(2).all(|w| w[0] < w[1]),
+                        "Mapping faces need to be strictly increasing!"
+                    );
+                },
+            };
+        }
         Ok(())
     }
 }
@@ -11,10 +11,12 @@ use color_eyre::{
     eyre::{ensure, Context},
     Result,
 };
+pub use meshmotionconfig::*;
 pub use numfluxconfig::*;
 use serde::Serialize;
 pub use timeintegrationconfig::*;
 
+mod meshmotionconfig;
 mod numfluxconfig;
 mod timeintegrationconfig;
 
@@ -44,6 +46,9 @@ pub struct NumericsConfig {
 
     /// CFL parameter
     pub dt_cfl_param: f64,
+
+    /// Whether the cell faces move with the fluid
+    pub mesh_motion: MeshMotion,
 }
 
 impl NumericsConfig {
@@ -81,6 +86,7 @@ impl NumericsConfig {
     /// assert_eq!(numerics_config.dt_min, 1.0e-12);
     /// assert_eq!(numerics_config.dt_max, f64::MAX);
     /// assert_eq!(numerics_config.dt_cfl_param, 0.4);
+    /// assert_eq!(numerics_config.mesh_motion, MeshMotion::Eulerian);
     /// ```
     pub fn default_riemann_test<N: NumFlux<E, S> + 'static, const E: usize, const S: usize>(t_end: f64) -> Self {
         Self {
@@ -103,6 +109,7 @@ impl NumericsConfig {
             dt_min: 1.0e-12,
             dt_max: f64::MAX,
             dt_cfl_param: 0.4,
+            mesh_motion: MeshMotion::Eulerian,
         }
     }
 }
@@ -129,6 +136,9 @@ impl Validation for NumericsConfig {
         self.time_integration_config
             .validate()
             .context("Validating config.numericsconfig.time_integration_config")?;
+        self.mesh_motion
+            .validate()
+            .context("Validating config.numericsconfig.mesh_motion")?;
         Ok(())
     }
 }
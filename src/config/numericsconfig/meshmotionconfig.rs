@@ -0,0 +1,52 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [MeshMotion] for configuring whether the [Mesh](crate::mesh::Mesh) moves with the fluid.
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Enumerates the ways the cell faces of the [Mesh](crate::mesh::Mesh) may move during the
+/// simulation.
+///
+/// Defaults to [Eulerian](MeshMotion::Eulerian).
+#[derive(Debug, Serialize, Copy, Clone, Default, PartialEq)]
+pub enum MeshMotion {
+    /// The cell faces stay where they are
+    #[default]
+    Eulerian,
+
+    /// The cell faces move with the fluid, i.e. with the average of [Physics::xi_vel](crate::Physics)
+    /// in the two cells sharing the face, while the edges of the computational area stay in place.
+    /// This is the arbitrary Lagrangian-Eulerian (ALE) scheme.
+    ///
+    /// The numerical fluxes are then calculated relative to the moving faces. Note that this
+    /// removes the errors from advecting features through the mesh, but not the dissipation that
+    /// [Hll](crate::Hll) and [Kt](crate::Kt) add at discontinuities.
+    Lagrangian {
+        /// If this is `Some(n)`, the conservative variables are remapped onto the initial mesh
+        /// every `n` time steps; with `None` the mesh keeps following the fluid.
+        remap_interval: Option<usize>,
+    },
+}
+
+unsafe impl Send for MeshMotion {}
+unsafe impl Sync for MeshMotion {}
+
+impl Validation for MeshMotion {
+    fn validate(&self) -> Result<()> {
+        if let Self::Lagrangian {
+            remap_interval: Some(n),
+        } = self
+        {
+            ensure!(
+                *n >= 1,
+                "This must hold: remap_interval >= 1! Got remap_interval = {}",
+                n
+            );
+        }
+        Ok(())
+    }
+}
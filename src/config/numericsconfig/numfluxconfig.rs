@@ -51,17 +51,17 @@ impl Validation for NumFluxConfig {
 ///
 /// * `i`: mesh cell index
 /// * `j`: equation index
-/// * `dxi`: line differential along the `xi` coordinate
+/// * `x`: xi coordinates of the cell centres
 /// * `uc`: conservative variables at cell centres
-/// * `a`: `uc[[j, i]] - uc[[j, i-1]]`
-/// * `b`: `uc[[j, i+1]] - uc[[j, i]]`
-/// * `c`: `uc[[j, i+1]] - uc[[j, i-1]]`
+/// * `a`: `(uc[[j, i]] - uc[[j, i-1]]) / (x[i] - x[i-1])`
+/// * `b`: `(uc[[j, i+1]] - uc[[j, i]]) / (x[i+1] - x[i])`
+/// * `c`: `(uc[[j, i+1]] - uc[[j, i-1]]) / (x[i+1] - x[i-1])`
 /// * `theta`: monocent parameter
 ///
 /// Then the different limiter functions calculate the slop at each mesh cell index `i` and each
 /// equation index `j` by:
 ///
-/// * `NoLimiter`: `1 / dxi * 0.5 * (a + b)`
+/// * `NoLimiter`: `0.5 * (a + b)`
 /// * `MinMod`: `if signum(a) * signum(b) > 0 { signum(a) * min(abs(a), abs(b)) } else { 0 }`
 /// * `Superbee`:
 /// ```text
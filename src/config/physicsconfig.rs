@@ -5,15 +5,52 @@
 //! Exports [PhysicsConfig] for configuring [Physics](crate::state::Physics) objects.
 
 use crate::{errorhandling::Validation, UnitsMode};
-use color_eyre::{eyre::ensure, Result};
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
+};
 use serde::Serialize;
 
+pub use advectionconfig::*;
+pub use alphaviscosityconfig::*;
+pub use bathymetry::*;
+pub use dragconfig::*;
+pub use eosconfig::*;
+pub use mhdconfig::*;
+pub use networkconfig::*;
+pub use potential::*;
+pub use radiationconfig::*;
+pub use shallowwaterconfig::*;
+pub use soundspeedprofile::*;
+pub use systemsconfig::*;
+pub use transportconfig::*;
+
+mod advectionconfig;
+mod alphaviscosityconfig;
+mod bathymetry;
+mod dragconfig;
+mod eosconfig;
+mod mhdconfig;
+mod networkconfig;
+mod potential;
+mod radiationconfig;
+mod shallowwaterconfig;
+mod soundspeedprofile;
+mod systemsconfig;
+mod transportconfig;
+
 /// Carries information about how to construct [Physics](crate::state::Physics) objects.
 ///
 /// Defaults to setting:
 ///
 /// * `adiabatic_index` to 5.0 / 3.0
 /// * `units_mode` to [UnitsMode::default()]
+/// * `eos` to [EosConfig::default()]
+/// * `c_sound_profile` to [SoundSpeedProfile::default()]
+/// * `systems` to [SystemsConfig::default()]
+/// * `transport` to `None`
+/// * `alpha_viscosity` to `None`
+/// * `potential` to `None`
 #[derive(Debug, Serialize, Clone)]
 pub struct PhysicsConfig {
     /// Ratio of specific heats
@@ -21,6 +58,25 @@ pub struct PhysicsConfig {
 
     /// The units system
     pub units_mode: UnitsMode,
+
+    /// The equation of state used by adiabatic systems
+    pub eos: EosConfig,
+
+    /// How the speed of sound of isothermal systems is set
+    pub c_sound_profile: SoundSpeedProfile,
+
+    /// The parameters that only apply to specific [Physics](crate::state::Physics) types
+    pub systems: SystemsConfig,
+
+    /// The physical viscosity and heat conduction; `None` leaves the flow inviscid
+    pub transport: Option<TransportConfig>,
+
+    /// The turbulent viscosity of an accretion disk on a cylindrical mesh; `None` turns it off
+    pub alpha_viscosity: Option<AlphaViscosityConfig>,
+
+    /// The external gravitational potential the gas moves in; `None` turns gravity off, apart from
+    /// the [ShallowWaterConfig::gravity] of [ShallowWater](crate::state::ShallowWater) systems
+    pub potential: Option<Potential>,
 }
 
 impl Default for PhysicsConfig {
@@ -28,6 +84,12 @@ impl Default for PhysicsConfig {
         Self {
             adiabatic_index: 5.0 / 3.0,
             units_mode: UnitsMode::default(),
+            eos: EosConfig::default(),
+            c_sound_profile: SoundSpeedProfile::default(),
+            systems: SystemsConfig::default(),
+            transport: None,
+            alpha_viscosity: None,
+            potential: None,
         }
     }
 }
@@ -37,11 +99,36 @@ unsafe impl Sync for PhysicsConfig {}
 
 impl Validation for PhysicsConfig {
     fn validate(&self) -> Result<()> {
-        ensure!(
-            self.adiabatic_index > 1.0 && self.adiabatic_index < 2.0,
-            "This must hold: 1 < adiabatic_index < 2 ! Got {}",
-            self.adiabatic_index
-        );
+        if self.eos == EosConfig::IdealGas {
+            ensure!(
+                self.adiabatic_index > 1.0 && self.adiabatic_index <= 2.0,
+                "This must hold: 1 < adiabatic_index <= 2 ! Got {}",
+                self.adiabatic_index
+            );
+        } else {
+            // liquids and polytropes may well be stiffer than an ideal gas
+            ensure!(
+                self.adiabatic_index > 1.0,
+                "This must hold: 1 < adiabatic_index ! Got {}",
+                self.adiabatic_index
+            );
+        }
+        self.eos.validate().context("Validating PhysicsConfig::eos")?;
+        self.c_sound_profile
+            .validate()
+            .context("Validating PhysicsConfig::c_sound_profile")?;
+        self.systems.validate().context("Validating PhysicsConfig::systems")?;
+        if let Some(transport) = &self.transport {
+            transport.validate().context("Validating PhysicsConfig::transport")?;
+        }
+        if let Some(alpha_viscosity) = &self.alpha_viscosity {
+            alpha_viscosity
+                .validate()
+                .context("Validating PhysicsConfig::alpha_viscosity")?;
+        }
+        if let Some(potential) = &self.potential {
+            potential.validate().context("Validating PhysicsConfig::potential")?;
+        }
         Ok(())
     }
 }
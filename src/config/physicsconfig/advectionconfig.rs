@@ -0,0 +1,39 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [AdvectionConfig] for configuring [LinearAdvection](crate::state::LinearAdvection)
+//! systems.
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Configures [LinearAdvection](crate::state::LinearAdvection) systems.
+///
+/// Defaults to setting `velocity` to 1.0.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct AdvectionConfig {
+    /// The velocity quantities are carried with
+    pub velocity: f64,
+}
+
+impl Default for AdvectionConfig {
+    fn default() -> Self {
+        Self { velocity: 1.0 }
+    }
+}
+
+unsafe impl Send for AdvectionConfig {}
+unsafe impl Sync for AdvectionConfig {}
+
+impl Validation for AdvectionConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.velocity.is_finite(),
+            "This must hold: velocity is finite! Got {}",
+            self.velocity
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [AlphaViscosityConfig] for configuring the turbulent viscosity of accretion disks, see
+//! [AlphaViscositySource](crate::rhs::AlphaViscositySource).
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Configures the Shakura-Sunyaev alpha viscosity of an accretion disk, i.e. the kinematic
+/// viscosity `nu = alpha * c_s * H`, with the scale height `H = c_s / Omega_K` of a disk orbiting
+/// a central mass.
+///
+/// The Kepler frequency `Omega_K` is taken from the
+/// [Potential::PointMass](crate::Potential::PointMass), which therefore needs to be set as
+/// [PhysicsConfig::potential](crate::PhysicsConfig::potential) as well.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct AlphaViscosityConfig {
+    /// The dimensionless viscosity parameter `alpha`
+    pub alpha: f64,
+}
+
+unsafe impl Send for AlphaViscosityConfig {}
+unsafe impl Sync for AlphaViscosityConfig {}
+
+impl Validation for AlphaViscosityConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.alpha.is_finite() && self.alpha >= 0.0,
+            "This must hold: alpha is finite and non-negative! Got {}",
+            self.alpha
+        );
+        Ok(())
+    }
+}
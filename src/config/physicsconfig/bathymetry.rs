@@ -0,0 +1,54 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [Bathymetry] for configuring the bottom elevation of
+//! [ShallowWater](crate::state::ShallowWater) systems.
+
+use serde::{Serialize, Serializer};
+
+/// Enumerates the ways the elevation of the bottom, i.e. the bathymetry, of
+/// [ShallowWater](crate::state::ShallowWater) systems is set.
+///
+/// The bathymetry is evaluated at the cell centres, while the values on the cell faces are the
+/// averages of the neighbouring cell centres. This is what keeps a lake at rest exactly at rest,
+/// see [BathymetrySource](crate::rhs::BathymetrySource). Any variant other than
+/// [Flat](Bathymetry::Flat) needs a cartesian mesh.
+///
+/// Defaults to [Flat](Bathymetry::Flat).
+#[derive(Debug, Serialize, Clone, Default)]
+pub enum Bathymetry {
+    /// The bottom sits at an elevation of 0.0 everywhere
+    #[default]
+    Flat,
+
+    /// User defined function mapping `xi` to the elevation of the bottom
+    Function(#[serde(serialize_with = "serialize_bathymetry_fn")] fn(f64) -> f64),
+}
+
+unsafe impl Send for Bathymetry {}
+unsafe impl Sync for Bathymetry {}
+
+impl PartialEq for Bathymetry {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Flat, Self::Flat) => true,
+            (Self::Function(f), Self::Function(g)) => std::ptr::fn_addr_eq(*f, *g),
+            _ => false,
+        }
+    }
+}
+
+fn serialize_bathymetry_fn<T: Serializer>(_: &fn(f64) -> f64, serializer: T) -> Result<T::Ok, T::Error> {
+    serializer.serialize_str("user defined function")
+}
+
+impl Bathymetry {
+    /// Returns the elevation of the bottom at coordinate `xi`
+    pub fn elevation(&self, xi: f64) -> f64 {
+        match self {
+            Self::Flat => 0.0,
+            Self::Function(f) => f(xi),
+        }
+    }
+}
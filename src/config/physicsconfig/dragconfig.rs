@@ -0,0 +1,62 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [DragConfig] and [DragUpdate] for configuring the drag between gas and dust in
+//! [DustyGas](crate::state::DustyGas) systems.
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Enumerates the ways the drag between gas and dust is integrated in time.
+///
+/// Defaults to [Explicit](DragUpdate::Explicit).
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DragUpdate {
+    /// The drag is a source term on the right-hand side, integrated by the
+    /// [TimeSolver](crate::time::TimeSolver) like any other source. This limits the time step width
+    /// to the stopping time, see [DtKind::Drag](crate::time::DtKind::Drag).
+    #[default]
+    Explicit,
+
+    /// The drag is applied after each time step with a backward Euler step, which is stable for
+    /// any time step width, but smears out the relaxation if the time step is much larger than the
+    /// stopping time.
+    Implicit,
+
+    /// The drag is applied after each time step with the analytic solution for the relaxation of
+    /// the relative velocity, which is exact for any time step width in a cell without any
+    /// transport.
+    Exponential,
+}
+
+unsafe impl Send for DragUpdate {}
+unsafe impl Sync for DragUpdate {}
+
+/// Configures the drag between gas and dust in [DustyGas](crate::state::DustyGas) systems.
+///
+/// The drag accelerates the dust towards the gas velocity on the stopping time `t_s`, and pushes
+/// back on the gas, i.e. the force density on the dust is `rho_dust * (v_gas - v_dust) / t_s`.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct DragConfig {
+    /// The time scale on which the dust velocity approaches the gas velocity
+    pub stopping_time: f64,
+
+    /// How the drag is integrated in time
+    pub update: DragUpdate,
+}
+
+unsafe impl Send for DragConfig {}
+unsafe impl Sync for DragConfig {}
+
+impl Validation for DragConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.stopping_time.is_finite() && self.stopping_time > 0.0,
+            "This must hold: stopping_time is finite and positive! Got {}",
+            self.stopping_time
+        );
+        Ok(())
+    }
+}
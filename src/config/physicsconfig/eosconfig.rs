@@ -0,0 +1,86 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [EosConfig] for configuring the
+//! [EquationOfState](crate::state::EquationOfState) of adiabatic systems.
+
+use crate::{errorhandling::Validation, state::EosTable};
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Enumerates the equations of state, and carries their parameters apart from the adiabatic index,
+/// which is always taken from [PhysicsConfig::adiabatic_index](super::PhysicsConfig).
+///
+/// The variant needs to match the [EquationOfState](crate::state::EquationOfState) type of your
+/// adiabatic [Physics](crate::state::Physics) type, i.e. [IdealGas](EosConfig::IdealGas) for
+/// [IdealGas](crate::state::IdealGas) and so on.
+///
+/// Defaults to [IdealGas](EosConfig::IdealGas).
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub enum EosConfig {
+    /// Ideal gas, i.e. `p = (gamma - 1) * e`
+    #[default]
+    IdealGas,
+
+    /// Polytropic gas, i.e. `p = K * rho^gamma`, regardless of the internal energy
+    Polytropic {
+        /// The polytropic constant `K`
+        polytropic_constant: f64,
+    },
+
+    /// Stiffened gas, i.e. `p = (gamma - 1) * e - gamma * p_inf`, which models liquids like water
+    StiffenedGas {
+        /// The stiffening pressure `p_inf`
+        p_inf: f64,
+    },
+
+    /// Tabulated pressure and speed of sound, for equations of state that cannot be written in
+    /// closed form; read the table with [EosTable::from_file]
+    Tabulated {
+        /// The table to interpolate in
+        table: EosTable,
+
+        /// How to interpolate between the entries of the table
+        interpolation: EosInterpolation,
+    },
+}
+
+unsafe impl Send for EosConfig {}
+unsafe impl Sync for EosConfig {}
+
+/// Enumerates the ways to interpolate in an [EosTable].
+///
+/// Defaults to [Bilinear](EosInterpolation::Bilinear).
+#[derive(Debug, Serialize, Copy, Clone, Default, PartialEq, Eq)]
+pub enum EosInterpolation {
+    /// Bilinear interpolation between the four surrounding entries
+    #[default]
+    Bilinear,
+
+    /// Bicubic interpolation between the sixteen surrounding entries, using cubic Hermite
+    /// polynomials with derivatives from finite differences
+    Bicubic,
+}
+
+unsafe impl Send for EosInterpolation {}
+unsafe impl Sync for EosInterpolation {}
+
+impl Validation for EosConfig {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::IdealGas => (),
+            Self::Polytropic { polytropic_constant } => ensure!(
+                *polytropic_constant > 0.0,
+                "This must hold: polytropic_constant > 0! Got polytropic_constant = {}",
+                polytropic_constant
+            ),
+            Self::StiffenedGas { p_inf } => {
+                ensure!(*p_inf >= 0.0, "This must hold: p_inf >= 0! Got p_inf = {}", p_inf)
+            },
+            // the table is checked while reading it
+            Self::Tabulated { .. } => (),
+        };
+        Ok(())
+    }
+}
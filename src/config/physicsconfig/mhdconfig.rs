@@ -0,0 +1,32 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [MhdConfig] for configuring [IdealMhd](crate::state::IdealMhd) systems.
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Configures [IdealMhd](crate::state::IdealMhd) systems.
+///
+/// Defaults to setting `b_xi` to 0.0.
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq)]
+pub struct MhdConfig {
+    /// The constant magnetic field along xi
+    pub b_xi: f64,
+}
+
+unsafe impl Send for MhdConfig {}
+unsafe impl Sync for MhdConfig {}
+
+impl Validation for MhdConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.b_xi.is_finite(),
+            "This must hold: b_xi is finite! Got {}",
+            self.b_xi
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [NetworkConfig], [SpeciesConfig], and [ReactionConfig] for configuring the reaction
+//! network of [ReactingFlow](crate::state::ReactingFlow) systems.
+
+use crate::{errorhandling::Validation, units::Units, UnitsMode};
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Mass of a proton in atomic mass units
+const PROTON_MASS_AMU: f64 = 1.007276466621;
+
+/// Mass of an electron in atomic mass units
+const ELECTRON_MASS_AMU: f64 = 5.48579909065e-4;
+
+/// Ionization energy of hydrogen in eV
+const HYDROGEN_IONIZATION_EV: f64 = 13.598434;
+
+/// Configures a single species of a reaction network.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SpeciesConfig {
+    /// The name of the species, only used for error messages and output
+    pub name: String,
+
+    /// The mass of a particle of this species in atomic mass units
+    pub mass: f64,
+
+    /// The energy it takes to form a particle of this species, e.g. the ionization energy for an
+    /// ion, in the units of the simulation
+    pub formation_energy: f64,
+}
+
+unsafe impl Send for SpeciesConfig {}
+unsafe impl Sync for SpeciesConfig {}
+
+/// Configures a single reaction of a reaction network.
+///
+/// The reaction turns one particle of each of the `reactants` into one particle of each of the
+/// `products`, and runs at the rate `k(T) * n_0 * n_1 * ...` per unit volume, with the number
+/// densities `n_j` of the reactants and the modified Arrhenius rate coefficient
+/// `k(T) = prefactor * T^temperature_exponent * exp(-1.0 * activation_temperature / T)`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ReactionConfig {
+    /// Indexes into [NetworkConfig::species] of the particles reacting; list a species twice if
+    /// two of its particles react
+    pub reactants: Vec<usize>,
+
+    /// Indexes into [NetworkConfig::species] of the particles produced
+    pub products: Vec<usize>,
+
+    /// The prefactor of the rate coefficient, in units of `volume^(reactants.len() - 1) / time`
+    pub prefactor: f64,
+
+    /// The exponent of the temperature in the rate coefficient
+    pub temperature_exponent: f64,
+
+    /// The temperature scale of the exponential in the rate coefficient
+    pub activation_temperature: f64,
+}
+
+unsafe impl Send for ReactionConfig {}
+unsafe impl Sync for ReactionConfig {}
+
+/// Configures the reaction network of [ReactingFlow](crate::state::ReactingFlow) systems.
+///
+/// Each species gets its own mass fraction row, in the same order as they are listed in
+/// `species`, and the network is integrated implicitly per cell, see
+/// [ReactionSource](crate::rhs::ReactionSource).
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct NetworkConfig {
+    /// The species carried by the flow
+    pub species: Vec<SpeciesConfig>,
+
+    /// The reactions between the species
+    pub reactions: Vec<ReactionConfig>,
+
+    /// The largest change in any mass fraction during a Newton iteration of the implicit update
+    /// that counts as converged
+    pub tolerance: f64,
+}
+
+unsafe impl Send for NetworkConfig {}
+unsafe impl Sync for NetworkConfig {}
+
+impl NetworkConfig {
+    /// Returns a network of atomic hydrogen, protons, and electrons, in this order, with collisional
+    /// ionization `H + e -> H+ + e + e` and case B recombination `H+ + e -> H`.
+    ///
+    /// The rate coefficients are `5.85e-11 * sqrt(T) * exp(-157809.1 / T) cm^3/s` for the
+    /// ionization, and `2.59e-13 * (T / 1e4)^-0.7 cm^3/s` for the recombination. The formation
+    /// energy of the protons is the ionization energy of hydrogen, and since there are no radiative
+    /// losses, recombinations return it to the gas.
+    ///
+    /// # Arguments
+    ///
+    /// * `units_mode` - The units the simulation runs in
+    pub fn hydrogen_ionization(units_mode: &UnitsMode) -> Self {
+        let units = Units::new(units_mode.clone());
+        let cm3 = units.convert_length_to_cgs(1.0).powi(-3);
+        let ion_energy = HYDROGEN_IONIZATION_EV * units.electron_volt();
+        Self {
+            species: vec![
+                SpeciesConfig {
+                    name: "H".to_string(),
+                    mass: PROTON_MASS_AMU + ELECTRON_MASS_AMU,
+                    formation_energy: 0.0,
+                },
+                SpeciesConfig {
+                    name: "H+".to_string(),
+                    mass: PROTON_MASS_AMU,
+                    formation_energy: ion_energy,
+                },
+                SpeciesConfig {
+                    name: "e".to_string(),
+                    mass: ELECTRON_MASS_AMU,
+                    formation_energy: 0.0,
+                },
+            ],
+            reactions: vec![
+                ReactionConfig {
+                    reactants: vec![0, 2],
+                    products: vec![1, 2, 2],
+                    prefactor: 5.85e-11 * cm3,
+                    temperature_exponent: 0.5,
+                    activation_temperature: 157809.1,
+                },
+                ReactionConfig {
+                    reactants: vec![1, 2],
+                    products: vec![0],
+                    prefactor: 2.59e-13 * 1.0e4f64.powf(0.7) * cm3,
+                    temperature_exponent: -0.7,
+                    activation_temperature: 0.0,
+                },
+            ],
+            tolerance: 1.0e-10,
+        }
+    }
+}
+
+impl Validation for NetworkConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.species.is_empty(),
+            "This must hold: a reaction network has at least one species!"
+        );
+        for species in self.species.iter() {
+            ensure!(
+                species.mass.is_finite() && species.mass > 0.0,
+                "This must hold: the mass of species {} is finite and positive! Got {}",
+                species.name,
+                species.mass
+            );
+            ensure!(
+                species.formation_energy.is_finite(),
+                "This must hold: the formation energy of species {} is finite! Got {}",
+                species.name,
+                species.formation_energy
+            );
+        }
+        for (r, reaction) in self.reactions.iter().enumerate() {
+            ensure!(
+                !reaction.reactants.is_empty() && !reaction.products.is_empty(),
+                "This must hold: reaction {} has reactants and products! Got reactants = {:?}, products = {:?}",
+                r,
+                reaction.reactants,
+                reaction.products
+            );
+            ensure!(
+                reaction
+                    .reactants
+                    .iter()
+                    .chain(reaction.products.iter())
+                    .all(|&k| k < self.species.len()),
+                "This must hold: reaction {} only refers to the {} species! Got reactants = {:?}, products = {:?}",
+                r,
+                self.species.len(),
+                reaction.reactants,
+                reaction.products
+            );
+            ensure!(
+                reaction.prefactor.is_finite() && reaction.prefactor >= 0.0,
+                "This must hold: the prefactor of reaction {} is finite and non-negative! Got {}",
+                r,
+                reaction.prefactor
+            );
+            ensure!(
+                reaction.temperature_exponent.is_finite() && reaction.activation_temperature.is_finite(),
+                "This must hold: the temperature_exponent and activation_temperature of reaction {} are finite! Got \
+                 {} and {}",
+                r,
+                reaction.temperature_exponent,
+                reaction.activation_temperature
+            );
+            let mass = |ks: &[usize]| ks.iter().map(|&k| self.species[k].mass).sum::<f64>();
+            let (mass_reactants, mass_products) = (mass(&reaction.reactants), mass(&reaction.products));
+            ensure!(
+                (mass_reactants - mass_products).abs() <= 1.0e-10 * mass_reactants,
+                "This must hold: reaction {} conserves mass! Got {} amu in the reactants and {} amu in the products",
+                r,
+                mass_reactants,
+                mass_products
+            );
+        }
+        ensure!(
+            self.tolerance.is_finite() && self.tolerance > 0.0,
+            "This must hold: tolerance is finite and positive! Got {}",
+            self.tolerance
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [Potential] for configuring an external gravitational potential, see
+//! [GravitySource](crate::rhs::GravitySource).
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::{Serialize, Serializer};
+
+/// Enumerates the external gravitational potentials `Phi(xi, t)` the gas can move in.
+///
+/// The potential is evaluated on the cell faces, and the gravitational acceleration in each cell
+/// is the difference of the potential across the cell, divided by its width.
+#[derive(Debug, Serialize, Clone)]
+pub enum Potential {
+    /// A point mass `M` at `xi = 0`, i.e. `Phi = -1.0 * G * M / sqrt(xi^2 + eps^2)` with the
+    /// softening length `eps`, which needs to be positive if the mesh touches the origin
+    PointMass {
+        /// The mass `M`
+        mass: f64,
+
+        /// The softening length `eps`
+        softening: f64,
+    },
+
+    /// A uniform field, i.e. `Phi = -1.0 * g * xi`, which accelerates the gas by `g` along xi
+    Uniform {
+        /// The acceleration `g` along xi
+        acceleration: f64,
+    },
+
+    /// User defined function mapping `xi` and the time `t` to the potential
+    Function(#[serde(serialize_with = "serialize_potential_fn")] fn(f64, f64) -> f64),
+}
+
+unsafe impl Send for Potential {}
+unsafe impl Sync for Potential {}
+
+impl PartialEq for Potential {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::PointMass { mass, softening },
+                Self::PointMass {
+                    mass: mass_other,
+                    softening: softening_other,
+                },
+            ) => mass == mass_other && softening == softening_other,
+            (
+                Self::Uniform { acceleration },
+                Self::Uniform {
+                    acceleration: acceleration_other,
+                },
+            ) => acceleration == acceleration_other,
+            (Self::Function(f), Self::Function(g)) => std::ptr::fn_addr_eq(*f, *g),
+            _ => false,
+        }
+    }
+}
+
+fn serialize_potential_fn<T: Serializer>(_: &fn(f64, f64) -> f64, serializer: T) -> Result<T::Ok, T::Error> {
+    serializer.serialize_str("user defined function")
+}
+
+impl Potential {
+    /// Returns the potential at coordinate `xi` and time `t`
+    ///
+    /// # Arguments
+    ///
+    /// * `xi` - the coordinate
+    /// * `t` - the time
+    /// * `gravitational_constant` - the gravitational constant in the units of the simulation
+    pub fn phi(&self, xi: f64, t: f64, gravitational_constant: f64) -> f64 {
+        match self {
+            Self::PointMass { mass, softening } => {
+                -gravitational_constant * mass / (xi * xi + softening * softening).sqrt()
+            },
+            Self::Uniform { acceleration } => -acceleration * xi,
+            Self::Function(f) => f(xi, t),
+        }
+    }
+}
+
+impl Validation for Potential {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::PointMass { mass, softening } => {
+                ensure!(
+                    mass.is_finite() && *mass > 0.0,
+                    "This must hold: mass is finite and positive! Got {}",
+                    mass
+                );
+                ensure!(
+                    softening.is_finite() && *softening >= 0.0,
+                    "This must hold: softening is finite and non-negative! Got {}",
+                    softening
+                );
+            },
+            Self::Uniform { acceleration } => ensure!(
+                acceleration.is_finite(),
+                "This must hold: acceleration is finite! Got {}",
+                acceleration
+            ),
+            Self::Function(_) => (),
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [RadiationConfig] and [Opacity] for configuring the radiation of
+//! [RadiationHydro](crate::state::RadiationHydro) systems.
+
+use crate::errorhandling::Validation;
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
+};
+use serde::Serialize;
+
+/// Enumerates the ways the opacity, i.e. the cross section per unit mass, is calculated.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum Opacity {
+    /// The same opacity everywhere
+    Constant(f64),
+
+    /// Power law in mass density and temperature, i.e. `kappa = kappa_0 * rho^a * T^b`, like
+    /// Kramers' opacity with `a = 1` and `b = -3.5`
+    PowerLaw {
+        /// The opacity at unit mass density and temperature
+        kappa_0: f64,
+
+        /// The exponent `a` of the mass density
+        rho_exponent: f64,
+
+        /// The exponent `b` of the temperature
+        temperature_exponent: f64,
+    },
+}
+
+unsafe impl Send for Opacity {}
+unsafe impl Sync for Opacity {}
+
+impl Opacity {
+    /// Returns the opacity at mass density `rho` and `temperature`
+    #[inline(always)]
+    pub fn kappa(&self, rho: f64, temperature: f64) -> f64 {
+        match self {
+            Self::Constant(kappa) => *kappa,
+            Self::PowerLaw {
+                kappa_0,
+                rho_exponent,
+                temperature_exponent,
+            } => kappa_0 * rho.powf(*rho_exponent) * temperature.powf(*temperature_exponent),
+        }
+    }
+}
+
+impl Validation for Opacity {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Constant(kappa) => ensure!(
+                kappa.is_finite() && *kappa >= 0.0,
+                "This must hold: a constant opacity is finite and non-negative! Got {}",
+                kappa
+            ),
+            Self::PowerLaw {
+                kappa_0,
+                rho_exponent,
+                temperature_exponent,
+            } => {
+                ensure!(
+                    kappa_0.is_finite() && *kappa_0 >= 0.0,
+                    "This must hold: kappa_0 is finite and non-negative! Got {}",
+                    kappa_0
+                );
+                ensure!(
+                    rho_exponent.is_finite() && temperature_exponent.is_finite(),
+                    "This must hold: rho_exponent and temperature_exponent are finite! Got {} and {}",
+                    rho_exponent,
+                    temperature_exponent
+                );
+            },
+        };
+        Ok(())
+    }
+}
+
+/// Configures the radiation of [RadiationHydro](crate::state::RadiationHydro) systems, see
+/// [RadiationSource](crate::rhs::RadiationSource).
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct RadiationConfig {
+    /// The mean molecular weight of the gas in atomic mass units, which relates its pressure to
+    /// its temperature
+    pub mean_molecular_weight: f64,
+
+    /// The Rosseland mean opacity, which sets how fast radiation diffuses
+    pub opacity_rosseland: Opacity,
+
+    /// The Planck mean opacity, which sets how fast gas and radiation exchange energy; set it to
+    /// `Opacity::Constant(0.0)` to decouple them
+    pub opacity_planck: Opacity,
+}
+
+unsafe impl Send for RadiationConfig {}
+unsafe impl Sync for RadiationConfig {}
+
+impl Validation for RadiationConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.mean_molecular_weight.is_finite() && self.mean_molecular_weight > 0.0,
+            "This must hold: mean_molecular_weight is finite and positive! Got {}",
+            self.mean_molecular_weight
+        );
+        self.opacity_rosseland
+            .validate()
+            .context("Validating RadiationConfig::opacity_rosseland")?;
+        self.opacity_planck
+            .validate()
+            .context("Validating RadiationConfig::opacity_planck")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [ShallowWaterConfig] for configuring [ShallowWater](crate::state::ShallowWater)
+//! systems.
+
+use crate::{errorhandling::Validation, Bathymetry};
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Configures [ShallowWater](crate::state::ShallowWater) systems.
+///
+/// Defaults to setting:
+///
+/// * `gravity` to 1.0
+/// * `bathymetry` to [Bathymetry::default()]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ShallowWaterConfig {
+    /// The gravitational acceleration
+    pub gravity: f64,
+
+    /// The elevation of the bottom
+    pub bathymetry: Bathymetry,
+}
+
+impl Default for ShallowWaterConfig {
+    fn default() -> Self {
+        Self {
+            gravity: 1.0,
+            bathymetry: Bathymetry::default(),
+        }
+    }
+}
+
+unsafe impl Send for ShallowWaterConfig {}
+unsafe impl Sync for ShallowWaterConfig {}
+
+impl Validation for ShallowWaterConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.gravity.is_finite() && self.gravity > 0.0,
+            "This must hold: gravity is finite and positive! Got {}",
+            self.gravity
+        );
+        Ok(())
+    }
+}
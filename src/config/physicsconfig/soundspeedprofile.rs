@@ -0,0 +1,94 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [SoundSpeedProfile] for configuring the speed of sound of isothermal systems.
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::{Serialize, Serializer};
+
+/// Enumerates the ways the speed of sound of isothermal [Physics](crate::state::Physics) types,
+/// like [Euler1DIsot](crate::state::Euler1DIsot), is set.
+///
+/// With any variant other than [Global](SoundSpeedProfile::Global), the system is locally
+/// isothermal, i.e. the speed of sound is a fixed function of the `xi` coordinate. It is
+/// evaluated at the cell centres, and at the west and east cell faces, so that numerical flux
+/// schemes reconstructing the face values, like [Kt](crate::rhs::numflux::kt::Kt), see the speed
+/// of sound at the faces. Adiabatic systems only accept [Global](SoundSpeedProfile::Global).
+///
+/// Defaults to [Global](SoundSpeedProfile::Global).
+#[derive(Debug, Serialize, Clone, Default)]
+pub enum SoundSpeedProfile {
+    /// The speed of sound is set once in the init function, and left untouched afterwards
+    #[default]
+    Global,
+
+    /// Power law `c_sound = c_sound_0 * (xi / xi_0)^(-q)`, as used for protoplanetary disks;
+    /// needs `xi > 0` on the whole mesh, including the ghost cells
+    PowerLaw {
+        /// The speed of sound at `xi_0`
+        c_sound_0: f64,
+
+        /// The reference coordinate
+        xi_0: f64,
+
+        /// The power law index
+        q: f64,
+    },
+
+    /// User defined function mapping `xi` to the speed of sound
+    Function(#[serde(serialize_with = "serialize_profile_fn")] fn(f64) -> f64),
+}
+
+unsafe impl Send for SoundSpeedProfile {}
+unsafe impl Sync for SoundSpeedProfile {}
+
+impl PartialEq for SoundSpeedProfile {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Global, Self::Global) => true,
+            (
+                Self::PowerLaw { c_sound_0, xi_0, q },
+                Self::PowerLaw {
+                    c_sound_0: c_sound_0_other,
+                    xi_0: xi_0_other,
+                    q: q_other,
+                },
+            ) => c_sound_0 == c_sound_0_other && xi_0 == xi_0_other && q == q_other,
+            (Self::Function(f), Self::Function(g)) => std::ptr::fn_addr_eq(*f, *g),
+            _ => false,
+        }
+    }
+}
+
+fn serialize_profile_fn<T: Serializer>(_: &fn(f64) -> f64, serializer: T) -> Result<T::Ok, T::Error> {
+    serializer.serialize_str("user defined function")
+}
+
+impl SoundSpeedProfile {
+    /// Returns the speed of sound at coordinate `xi`, or `None` for
+    /// [Global](SoundSpeedProfile::Global)
+    pub fn c_sound(&self, xi: f64) -> Option<f64> {
+        match self {
+            Self::Global => None,
+            Self::PowerLaw { c_sound_0, xi_0, q } => Some(c_sound_0 * (xi / xi_0).powf(-q)),
+            Self::Function(f) => Some(f(xi)),
+        }
+    }
+}
+
+impl Validation for SoundSpeedProfile {
+    fn validate(&self) -> Result<()> {
+        if let Self::PowerLaw { c_sound_0, xi_0, q } = self {
+            ensure!(
+                *c_sound_0 > 0.0 && *xi_0 > 0.0 && q.is_finite(),
+                "This must hold: c_sound_0 > 0, xi_0 > 0, and q is finite! Got c_sound_0 = {}, xi_0 = {}, q = {}",
+                c_sound_0,
+                xi_0,
+                q
+            );
+        }
+        Ok(())
+    }
+}
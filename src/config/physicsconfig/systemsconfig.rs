@@ -0,0 +1,71 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [SystemsConfig] for grouping the parameters that only apply to specific
+//! [Physics](crate::state::Physics) types.
+
+use crate::{
+    errorhandling::Validation, AdvectionConfig, DragConfig, MhdConfig, NetworkConfig, RadiationConfig,
+    ShallowWaterConfig,
+};
+use color_eyre::{eyre::Context, Result};
+use serde::Serialize;
+
+/// Groups the parameters that only apply to specific [Physics](crate::state::Physics) types.
+///
+/// Each system validates its own parameters through
+/// [Physics::validate_physics_config](crate::state::Physics::validate_physics_config), so that
+/// the parameters of systems that are not in use do not need to make sense. Only the optional
+/// parameters are validated here as soon as they are set, since
+/// [CorriesConfig::init_corries](crate::CorriesConfig::init_corries) rejects them for any other
+/// system anyways.
+///
+/// Defaults to setting:
+///
+/// * `mhd` to [MhdConfig::default()]
+/// * `shallow_water` to [ShallowWaterConfig::default()]
+/// * `advection` to [AdvectionConfig::default()]
+/// * `drag` to `None`
+/// * `network` to `None`
+/// * `radiation` to `None`
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct SystemsConfig {
+    /// The parameters of [IdealMhd](crate::state::IdealMhd)
+    pub mhd: MhdConfig,
+
+    /// The parameters of [ShallowWater](crate::state::ShallowWater)
+    pub shallow_water: ShallowWaterConfig,
+
+    /// The parameters of [LinearAdvection](crate::state::LinearAdvection)
+    pub advection: AdvectionConfig,
+
+    /// The drag between gas and dust, used by [DustyGas](crate::state::DustyGas); `None` leaves
+    /// both fluids uncoupled
+    pub drag: Option<DragConfig>,
+
+    /// The reaction network between the species of a [ReactingFlow](crate::state::ReactingFlow)
+    /// system
+    pub network: Option<NetworkConfig>,
+
+    /// The radiation of a [RadiationHydro](crate::state::RadiationHydro) system
+    pub radiation: Option<RadiationConfig>,
+}
+
+unsafe impl Send for SystemsConfig {}
+unsafe impl Sync for SystemsConfig {}
+
+impl Validation for SystemsConfig {
+    fn validate(&self) -> Result<()> {
+        if let Some(drag) = &self.drag {
+            drag.validate().context("Validating SystemsConfig::drag")?;
+        }
+        if let Some(network) = &self.network {
+            network.validate().context("Validating SystemsConfig::network")?;
+        }
+        if let Some(radiation) = &self.radiation {
+            radiation.validate().context("Validating SystemsConfig::radiation")?;
+        }
+        Ok(())
+    }
+}
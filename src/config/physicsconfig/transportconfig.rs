@@ -0,0 +1,133 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [TransportConfig] and [Viscosity] for configuring the physical viscosity and the heat
+//! conduction, see [Transport](crate::rhs::Transport).
+
+use crate::errorhandling::Validation;
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
+};
+use serde::Serialize;
+
+/// Enumerates the ways the dynamic viscosity `mu` is calculated.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum Viscosity {
+    /// The same dynamic viscosity everywhere; `Constant(0.0)` turns the viscosity off
+    Constant(f64),
+
+    /// Sutherland's law for dilute gases, i.e.
+    /// `mu = mu_ref * (T / T_ref)^1.5 * (T_ref + T_s) / (T + T_s)`
+    Sutherland {
+        /// The dynamic viscosity `mu_ref` at the reference temperature
+        mu_ref: f64,
+
+        /// The reference temperature `T_ref`
+        temperature_ref: f64,
+
+        /// Sutherland's temperature `T_s`
+        sutherland_temperature: f64,
+    },
+}
+
+unsafe impl Send for Viscosity {}
+unsafe impl Sync for Viscosity {}
+
+impl Viscosity {
+    /// Returns the dynamic viscosity at `temperature`
+    #[inline(always)]
+    pub fn mu(&self, temperature: f64) -> f64 {
+        match self {
+            Self::Constant(mu) => *mu,
+            Self::Sutherland {
+                mu_ref,
+                temperature_ref,
+                sutherland_temperature,
+            } => {
+                mu_ref * (temperature / temperature_ref).powf(1.5) * (temperature_ref + sutherland_temperature)
+                    / (temperature + sutherland_temperature)
+            },
+        }
+    }
+
+    /// Returns whether this viscosity vanishes everywhere
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::Constant(mu) => *mu == 0.0,
+            Self::Sutherland { mu_ref, .. } => *mu_ref == 0.0,
+        }
+    }
+}
+
+impl Validation for Viscosity {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Constant(mu) => ensure!(
+                mu.is_finite() && *mu >= 0.0,
+                "This must hold: a constant viscosity is finite and non-negative! Got {}",
+                mu
+            ),
+            Self::Sutherland {
+                mu_ref,
+                temperature_ref,
+                sutherland_temperature,
+            } => {
+                ensure!(
+                    mu_ref.is_finite() && *mu_ref >= 0.0,
+                    "This must hold: mu_ref is finite and non-negative! Got {}",
+                    mu_ref
+                );
+                ensure!(
+                    temperature_ref.is_finite() && *temperature_ref > 0.0,
+                    "This must hold: temperature_ref is finite and positive! Got {}",
+                    temperature_ref
+                );
+                ensure!(
+                    sutherland_temperature.is_finite() && *sutherland_temperature >= 0.0,
+                    "This must hold: sutherland_temperature is finite and non-negative! Got {}",
+                    sutherland_temperature
+                );
+            },
+        };
+        Ok(())
+    }
+}
+
+/// Configures the physical viscosity and the heat conduction, which turn the Euler equations into
+/// the Navier-Stokes equations, see [Transport](crate::rhs::Transport).
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct TransportConfig {
+    /// The dynamic viscosity
+    pub viscosity: Viscosity,
+
+    /// The thermal conductivity of Fourier's law; 0.0 turns the heat conduction off
+    pub thermal_conductivity: f64,
+
+    /// The mean molecular weight of the gas in atomic mass units, which relates its pressure to
+    /// its temperature
+    pub mean_molecular_weight: f64,
+}
+
+unsafe impl Send for TransportConfig {}
+unsafe impl Sync for TransportConfig {}
+
+impl Validation for TransportConfig {
+    fn validate(&self) -> Result<()> {
+        self.viscosity
+            .validate()
+            .context("Validating TransportConfig::viscosity")?;
+        ensure!(
+            self.thermal_conductivity.is_finite() && self.thermal_conductivity >= 0.0,
+            "This must hold: thermal_conductivity is finite and non-negative! Got {}",
+            self.thermal_conductivity
+        );
+        ensure!(
+            self.mean_molecular_weight.is_finite() && self.mean_molecular_weight > 0.0,
+            "This must hold: mean_molecular_weight is finite and positive! Got {}",
+            self.mean_molecular_weight
+        );
+        Ok(())
+    }
+}
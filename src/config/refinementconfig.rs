@@ -0,0 +1,127 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [RefinementConfig] and [AdaptiveRefinementConfig] for configuring the refined patches of
+//! [StaticRefinement](crate::refinement::StaticRefinement) and
+//! [AdaptiveRefinement](crate::refinement::AdaptiveRefinement) respectively.
+
+use crate::errorhandling::Validation;
+use color_eyre::{eyre::ensure, Result};
+use serde::Serialize;
+
+/// Carries information about where the coarse mesh should be refined, and by how much
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct RefinementConfig {
+    /// The number of fine cells each coarse cell is split into; needs to be at least 2
+    pub ratio: usize,
+
+    /// The xi coordinates of the western and eastern edge of each patch. These have to coincide
+    /// with cell faces of the coarse mesh, and the patches have to be sorted from west to east.
+    pub patches: Vec<(f64, f64)>,
+}
+
+unsafe impl Send for RefinementConfig {}
+unsafe impl Sync for RefinementConfig {}
+
+impl Validation for RefinementConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.ratio >= 2,
+            "This must hold: ratio >= 2! Got ratio = {}",
+            self.ratio
+        );
+        ensure!(!self.patches.is_empty(), "RefinementConfig needs at least one patch!");
+        for (xi_in, xi_out) in self.patches.iter() {
+            ensure!(
+                xi_in < xi_out,
+                "This must hold for every patch: xi_in < xi_out! Got xi_in = {} ; xi_out = {}",
+                xi_in,
+                xi_out
+            );
+        }
+        ensure!(
+            self.patches.windows(2).all(|w| w[0].1 < w[1].0),
+            "Patches need to be sorted from west to east and must not overlap! Got patches = {:?}",
+            self.patches
+        );
+        Ok(())
+    }
+}
+
+/// Enumerates the indicators that flag coarse cells for refinement in an
+/// [AdaptiveRefinement](crate::refinement::AdaptiveRefinement).
+///
+/// Both are calculated from the density at the cell centres, and are dimensionless.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum RefinementIndicator {
+    /// The relative jump in density across a cell, i.e. `|rho[i+1] - rho[i-1]| / (2 * rho[i])`
+    DensityGradient,
+
+    /// Löhner's error estimator, i.e. the second derivative of the density normalised with its
+    /// first derivative, which is in `[0, 1]`. `filter` damps the estimator for small ripples;
+    /// 0.01 is a common choice.
+    Lohner {
+        /// Filters out small ripples in the density
+        filter: f64,
+    },
+}
+
+unsafe impl Send for RefinementIndicator {}
+unsafe impl Sync for RefinementIndicator {}
+
+/// Carries information about how an [AdaptiveRefinement](crate::refinement::AdaptiveRefinement)
+/// decides where to put its patches
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AdaptiveRefinementConfig {
+    /// The number of fine cells each coarse cell is split into; needs to be at least 2
+    pub ratio: usize,
+
+    /// The indicator that flags coarse cells for refinement
+    pub indicator: RefinementIndicator,
+
+    /// Coarse cells where the indicator exceeds this threshold are flagged for refinement
+    pub threshold: f64,
+
+    /// The number of coarse cells around each flagged cell that are flagged as well, so that
+    /// features do not leave their patch between two regrids
+    pub buffer: usize,
+
+    /// The number of coarse steps between two regrids
+    pub regrid_interval: usize,
+
+    /// The maximum number of patches
+    pub max_patches: usize,
+}
+
+unsafe impl Send for AdaptiveRefinementConfig {}
+unsafe impl Sync for AdaptiveRefinementConfig {}
+
+impl Validation for AdaptiveRefinementConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.ratio >= 2,
+            "This must hold: ratio >= 2! Got ratio = {}",
+            self.ratio
+        );
+        ensure!(
+            self.threshold > 0.0,
+            "This must hold: threshold > 0! Got threshold = {}",
+            self.threshold
+        );
+        ensure!(
+            self.regrid_interval >= 1,
+            "This must hold: regrid_interval >= 1! Got regrid_interval = {}",
+            self.regrid_interval
+        );
+        ensure!(
+            self.max_patches >= 1,
+            "This must hold: max_patches >= 1! Got max_patches = {}",
+            self.max_patches
+        );
+        if let RefinementIndicator::Lohner { filter } = self.indicator {
+            ensure!(filter >= 0.0, "This must hold: filter >= 0! Got filter = {}", filter);
+        }
+        Ok(())
+    }
+}
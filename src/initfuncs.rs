@@ -6,7 +6,10 @@
 //! integration tests. These functions all can be passed to
 //! [CorriesConfig::init_corries()](crate::config::CorriesConfig::init_corries()).
 
-use crate::{Euler1DAdiabatic, Euler1DIsot, Mesh, NumFlux, Physics, Solver, State, TimeSolver};
+use crate::{
+    Burgers, Euler1DAdiabatic, Euler1DIsot, Euler2DAdiabatic, Euler2DIsot, IdealMhd, LinearAdvection, Mesh, NumFlux,
+    Physics, ShallowWater, Solver, State, TimeSolver,
+};
 use color_eyre::{eyre::bail, Result};
 use std::any::TypeId;
 
@@ -72,16 +75,23 @@ where
     // TODO:
     // I can probably turn this into a static assert by attaching a constant identifier to
     // my P type that I can then static assert here
-    if TypeId::of::<P>() != TypeId::of::<Euler1DIsot<S>>() && TypeId::of::<P>() != TypeId::of::<Euler1DAdiabatic<S>>() {
+    if ![
+        TypeId::of::<Euler1DIsot<S>>(),
+        TypeId::of::<Euler1DAdiabatic<S>>(),
+        TypeId::of::<Euler2DIsot<S>>(),
+        TypeId::of::<Euler2DAdiabatic<S>>(),
+    ]
+    .contains(&TypeId::of::<P>())
+    {
         bail!("init_noh cannot run when the Physics type is set to: {}!", P::name())
     };
-    let breakpoint_index = (S as f64 * 0.5) as usize;
+    let breakpoint_index = (u.cent.n_all() as f64 * 0.5) as usize;
     u.cent.prim.row_mut(P::JRHO).fill(1.0);
 
     for i in 0..breakpoint_index {
         u.cent.prim[[P::JXI, i]] = 1.0;
     }
-    for i in breakpoint_index..S {
+    for i in breakpoint_index..u.cent.n_all() {
         u.cent.prim[[P::JXI, i]] = -1.0;
     }
 
@@ -148,19 +158,397 @@ where
     // TODO:
     // I can probably turn this into a static assert by attaching a constant identifier to
     // my P type that I can then static assert here
-    if TypeId::of::<P>() != TypeId::of::<Euler1DAdiabatic<S>>() {
+    if TypeId::of::<P>() != TypeId::of::<Euler1DAdiabatic<S>>()
+        && TypeId::of::<P>() != TypeId::of::<Euler2DAdiabatic<S>>()
+    {
         bail!("init_sod cannot run when the Physics type is set to: {}!", P::name())
     };
 
-    let breakpoint_index = (S as f64 * 0.5) as usize;
+    let breakpoint_index = (u.cent.n_all() as f64 * 0.5) as usize;
     u.cent.prim.fill(0.0);
     for i in 0..breakpoint_index {
         u.cent.prim[[P::JRHO, i]] = 1.0;
         u.cent.prim[[P::JPRESSURE, i]] = 1.0;
     }
-    for i in breakpoint_index..S {
+    for i in breakpoint_index..u.cent.n_all() {
         u.cent.prim[[P::JRHO, i]] = 0.125;
         u.cent.prim[[P::JPRESSURE, i]] = 0.1;
     }
     Ok(())
 }
+
+/// Sets up the initial conditions for the Brio-Wu test, i.e. the magnetised version of the Sod
+/// test.
+///
+/// This sets up:
+///
+/// * mass density and pressure of 1.0, and eta magnetic field of 1.0 on the left hand side of the
+///   shocktube
+/// * mass density of 0.125, pressure of 0.1, and eta magnetic field of -1.0 on the right hand side
+///   of the shocktube
+/// * all velocities and the Phi magnetic field set to 0.0
+///
+/// This only runs with [IdealMhd]. The original test sets
+/// [MhdConfig::b_xi](crate::MhdConfig) to 0.75 and the adiabatic index to 2.0, which is
+/// left to the config.
+///
+/// ```
+/// use corries::prelude::*;
+///
+/// const S: usize = 100;
+/// set_Physics_and_E!(IdealMhd);
+/// type N = Hll<E, S>;
+/// type T = RungeKuttaFehlberg<P, E, S>;
+///
+/// let mut config = CorriesConfig::default_riemann_test::<N, E, S>(
+///     0.1,
+///     "results/integrationtests/brio_wu",
+///     "brio_wu",
+/// );
+/// config.physics_config.adiabatic_index = 2.0;
+/// config.physics_config.systems.mhd.b_xi = 0.75;
+/// let (u, _, _, _) = config.init_corries::<P, N, T, E, S>(corries::initfuncs::init_brio_wu).unwrap();
+///
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(u.cent.prim[[P::JBETA, 0]], 1.0);
+/// assert_relative_eq!(u.cent.prim[[P::JBETA, S - 1]], -1.0);
+/// assert_relative_eq!(u.cent.prim[[P::JPRESSURE, S - 1]], 0.1);
+/// ```
+pub fn init_brio_wu<P, N, T, const E: usize, const S: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    if TypeId::of::<P>() != TypeId::of::<IdealMhd<S>>() {
+        bail!(
+            "init_brio_wu cannot run when the Physics type is set to: {}!",
+            P::name()
+        )
+    };
+
+    let breakpoint_index = (u.cent.n_all() as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..breakpoint_index {
+        u.cent.prim[[P::JRHO, i]] = 1.0;
+        u.cent.prim[[P::JPRESSURE, i]] = 1.0;
+        u.cent.prim[[IdealMhd::<S>::JBETA, i]] = 1.0;
+    }
+    for i in breakpoint_index..u.cent.n_all() {
+        u.cent.prim[[P::JRHO, i]] = 0.125;
+        u.cent.prim[[P::JPRESSURE, i]] = 0.1;
+        u.cent.prim[[IdealMhd::<S>::JBETA, i]] = -1.0;
+    }
+    Ok(())
+}
+
+/// Sets up the initial conditions for a dam break onto a dry bottom, which Ritter solved
+/// analytically.
+///
+/// This sets up:
+///
+/// * water depth of 1.0 on the left hand side of the dam
+/// * water depth of 0.0, i.e. a dry bottom, on the right hand side of the dam
+/// * xi velocity of 0.0 everywhere
+///
+/// With the gravitational acceleration `g`, the initial wave speed `c0 = sqrt(g)`, and `x` being
+/// the distance to the dam, the analytic solution for a flat bottom is
+///
+/// ```text
+/// h = 1.0                                  ;                x <= -c0 * t
+/// h = (2.0 * c0 - x / t)^2 / (9.0 * g)     ; -c0 * t <  x <= 2.0 * c0 * t
+/// h = 0.0                                  ;  2.0 * c0 * t < x
+/// ```
+///
+/// with the velocity `u = 2.0 / 3.0 * (x / t + c0)` inside of the rarefaction wave.
+///
+/// This only runs with [ShallowWater].
+///
+/// ```
+/// use corries::prelude::*;
+///
+/// const S: usize = 100;
+/// set_Physics_and_E!(ShallowWater);
+/// type N = Hll<E, S>;
+/// type T = RungeKuttaFehlberg<P, E, S>;
+///
+/// let (u, _, _, _) = CorriesConfig::default_riemann_test::<N, E, S>(
+///     0.1,
+///     "results/integrationtests/dam_break_dry",
+///     "dam_break_dry",
+/// )
+/// .init_corries::<P, N, T, E, S>(corries::initfuncs::init_dam_break_dry).unwrap();
+///
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(u.cent.prim[[P::JRHO, 0]], 1.0);
+/// assert_relative_eq!(u.cent.prim[[P::JRHO, S - 1]], 0.0);
+/// ```
+pub fn init_dam_break_dry<P, N, T, const E: usize, const S: usize>(
+    u: &mut State<P, E, S>,
+    solver: &mut Solver<P, N, T, E, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    init_dam_break("init_dam_break_dry", 0.0, u, solver, mesh)
+}
+
+/// Sets up the initial conditions for a dam break onto still water, which Stoker solved
+/// analytically.
+///
+/// This sets up:
+///
+/// * water depth of 1.0 on the left hand side of the dam
+/// * water depth of 0.1 on the right hand side of the dam
+/// * xi velocity of 0.0 everywhere
+///
+/// Unlike the dry dam break of [init_dam_break_dry], this launches a bore, i.e. a shock, into the
+/// still water.
+///
+/// This only runs with [ShallowWater].
+///
+/// ```
+/// use corries::prelude::*;
+///
+/// const S: usize = 100;
+/// set_Physics_and_E!(ShallowWater);
+/// type N = Kt<E, S>;
+/// type T = RungeKuttaFehlberg<P, E, S>;
+///
+/// let (u, _, _, _) = CorriesConfig::default_riemann_test::<N, E, S>(
+///     0.1,
+///     "results/integrationtests/dam_break_wet",
+///     "dam_break_wet",
+/// )
+/// .init_corries::<P, N, T, E, S>(corries::initfuncs::init_dam_break_wet).unwrap();
+///
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(u.cent.prim[[P::JRHO, 0]], 1.0);
+/// assert_relative_eq!(u.cent.prim[[P::JRHO, S - 1]], 0.1);
+/// ```
+pub fn init_dam_break_wet<P, N, T, const E: usize, const S: usize>(
+    u: &mut State<P, E, S>,
+    solver: &mut Solver<P, N, T, E, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    init_dam_break("init_dam_break_wet", 0.1, u, solver, mesh)
+}
+
+/// Dam break with a water depth of 1.0 on the left and `depth_east` on the right hand side
+fn init_dam_break<P, N, T, const E: usize, const S: usize>(
+    name: &str,
+    depth_east: f64,
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    if TypeId::of::<P>() != TypeId::of::<ShallowWater<S>>() {
+        bail!("{} cannot run when the Physics type is set to: {}!", name, P::name())
+    };
+
+    let breakpoint_index = (u.cent.n_all() as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..u.cent.n_all() {
+        u.cent.prim[[P::JRHO, i]] = if i < breakpoint_index { 1.0 } else { depth_east };
+    }
+    Ok(())
+}
+
+/// Sets up a sine wave over the computational domain, i.e. between `xi_in` and `xi_out`.
+///
+/// This sets up the single variable of the model equations to:
+///
+/// ```text
+/// q = 1.0 + 0.5 * sin(2 * pi * (xi - xi_in) / (xi_out - xi_in))
+/// ```
+///
+/// With [LinearAdvection], the analytic solution is the same wave shifted by `a * t`, where `a`
+/// is the advection velocity. Since the wave is smooth, this is the test case for measuring the
+/// convergence order of a [NumFlux] and [TimeSolver] combination.
+///
+/// This only runs with [LinearAdvection] or [Burgers].
+///
+/// ```
+/// use corries::prelude::*;
+///
+/// const S: usize = 100;
+/// set_Physics_and_E!(LinearAdvection);
+/// type N = Hll<E, S>;
+/// type T = RungeKuttaFehlberg<P, E, S>;
+///
+/// let (u, _, mesh, _) = CorriesConfig::default_riemann_test::<N, E, S>(
+///     0.1,
+///     "results/integrationtests/sine_wave",
+///     "sine_wave",
+/// )
+/// .init_corries::<P, N, T, E, S>(corries::initfuncs::init_sine_wave).unwrap();
+///
+/// use approx::assert_relative_eq;
+///
+/// let i = mesh.ixi_in + mesh.n_comp / 4;
+/// assert_relative_eq!(u.cent.prim[[P::JRHO, i]], 1.5, max_relative = 1.0e-3);
+/// ```
+pub fn init_sine_wave<P, N, T, const E: usize, const S: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    check_model_equation::<P, E, S>("init_sine_wave")?;
+    let length = mesh.xi_out - mesh.xi_in;
+    for i in 0..u.cent.n_all() {
+        u.cent.prim[[0, i]] = 1.0 + 0.5 * (2.0 * std::f64::consts::PI * (mesh.xi_cent[i] - mesh.xi_in) / length).sin();
+    }
+    Ok(())
+}
+
+/// Sets up a square wave, i.e. a plateau in the first half of the computational domain.
+///
+/// This sets up the single variable of the model equations to:
+///
+/// * 2.0 between `xi_in + L / 4` and `xi_in + L / 2`, where `L = xi_out - xi_in`
+/// * 1.0 everywhere else
+///
+/// With [LinearAdvection], the analytic solution is the same square wave shifted by `a * t`,
+/// where `a` is the advection velocity. The discontinuities show how much a limiter smears out
+/// jumps, and whether it keeps the solution between 1.0 and 2.0.
+///
+/// With [Burgers], the western edge of the plateau turns into a rarefaction, while the eastern
+/// edge turns into a shock moving with a velocity of 1.5.
+///
+/// This only runs with [LinearAdvection] or [Burgers].
+///
+/// ```
+/// use corries::prelude::*;
+///
+/// const S: usize = 100;
+/// set_Physics_and_E!(LinearAdvection);
+/// type N = Hll<E, S>;
+/// type T = RungeKuttaFehlberg<P, E, S>;
+///
+/// let (u, _, mesh, _) = CorriesConfig::default_riemann_test::<N, E, S>(
+///     0.1,
+///     "results/integrationtests/square_wave",
+///     "square_wave",
+/// )
+/// .init_corries::<P, N, T, E, S>(corries::initfuncs::init_square_wave).unwrap();
+///
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(u.cent.prim[[P::JRHO, mesh.ixi_in]], 1.0);
+/// assert_relative_eq!(u.cent.prim[[P::JRHO, mesh.ixi_in + 3 * mesh.n_comp / 8]], 2.0);
+/// ```
+pub fn init_square_wave<P, N, T, const E: usize, const S: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    check_model_equation::<P, E, S>("init_square_wave")?;
+    let length = mesh.xi_out - mesh.xi_in;
+    let (xi_west, xi_east) = (mesh.xi_in + 0.25 * length, mesh.xi_in + 0.5 * length);
+    for i in 0..u.cent.n_all() {
+        u.cent.prim[[0, i]] = if (xi_west..xi_east).contains(&mesh.xi_cent[i]) {
+            2.0
+        } else {
+            1.0
+        };
+    }
+    Ok(())
+}
+
+/// Sets up a velocity ramp that steepens into a shock with [Burgers].
+///
+/// This sets up, with `L = xi_out - xi_in`:
+///
+/// * xi velocity of 1.0 west of `xi_in + 0.2 * L`
+/// * xi velocity falling off linearly from 1.0 to 0.0 up to `xi_in + 0.4 * L`
+/// * xi velocity of 0.0 everywhere else
+///
+/// All characteristics starting on the ramp meet at the time `t_s = 0.2 * L` at
+/// `xi_in + 0.4 * L`, where the shock forms. Before that, the ramp keeps its eastern end in place
+/// while its western end moves with a velocity of 1.0. After that, the shock moves east with a
+/// velocity of 0.5, i.e. it sits at `xi_in + 0.4 * L + 0.5 * (t - t_s)`.
+///
+/// This only runs with [Burgers].
+///
+/// ```
+/// use corries::prelude::*;
+///
+/// const S: usize = 100;
+/// set_Physics_and_E!(Burgers);
+/// type N = Hll<E, S>;
+/// type T = RungeKuttaFehlberg<P, E, S>;
+///
+/// let (u, _, _, _) = CorriesConfig::default_riemann_test::<N, E, S>(
+///     0.4,
+///     "results/integrationtests/shock_formation",
+///     "shock_formation",
+/// )
+/// .init_corries::<P, N, T, E, S>(corries::initfuncs::init_shock_formation).unwrap();
+///
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(u.cent.prim[[P::JXI, 0]], 1.0);
+/// assert_relative_eq!(u.cent.prim[[P::JXI, S - 1]], 0.0);
+/// ```
+pub fn init_shock_formation<P, N, T, const E: usize, const S: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    if TypeId::of::<P>() != TypeId::of::<Burgers<S>>() {
+        bail!(
+            "init_shock_formation cannot run when the Physics type is set to: {}!",
+            P::name()
+        )
+    };
+
+    let length = mesh.xi_out - mesh.xi_in;
+    let (xi_west, xi_east) = (mesh.xi_in + 0.2 * length, mesh.xi_in + 0.4 * length);
+    for i in 0..u.cent.n_all() {
+        u.cent.prim[[P::JXI, i]] = ((xi_east - mesh.xi_cent[i]) / (xi_east - xi_west)).clamp(0.0, 1.0);
+    }
+    Ok(())
+}
+
+/// Bails unless `P` is one of the scalar model equations, i.e. [LinearAdvection] or [Burgers]
+fn check_model_equation<P: Physics<E, S> + 'static, const E: usize, const S: usize>(name: &str) -> Result<()> {
+    if TypeId::of::<P>() != TypeId::of::<LinearAdvection<S>>() && TypeId::of::<P>() != TypeId::of::<Burgers<S>>() {
+        bail!("{} cannot run when the Physics type is set to: {}!", name, P::name())
+    };
+    Ok(())
+}
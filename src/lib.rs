@@ -51,7 +51,8 @@
 //! The main building blocks of preparing and running a simulation with corries are:
 //!
 //! * Choosing a couple of type parameters, namely for the traits: [Physics], [NumFlux], and
-//! [TimeSolver], as well as the [Mesh] size (also through a compile time constant)
+//! [TimeSolver], as well as the [Mesh] size (also through a compile time constant, or
+//!   [RUNTIME_SIZE] to take it from [MeshConfig::n_all] at runtime)
 //! * Building a [CorriesConfig], which allows for fine-grained control over the simulation
 //! * Constructing and applying initial conditions to the necessary objects through the
 //! [CorriesConfig::init_corries] method
@@ -137,11 +138,14 @@
 //!
 //!     // Sets up a cartesian mesh, with the edges of the computational area (not counting ghost
 //!     // cells) are at the coordinates `xi = 1.0` and `xi = 2.0`. Remember that `xi` is the name
-//!     // of the primary coordinate in corries.
+//!     // of the primary coordinate in corries. Each edge of the mesh gets two ghost cells, which
+//!     // is the minimum that the numerical flux schemes need.
 //!     mesh_config: MeshConfig {
 //!         mode: MeshMode::Cartesian,
 //!         xi_in: 1.0,
 //!         xi_out: 2.0,
+//!         n_gc: 2,
+//!         n_all: None,
 //!     },
 //!
 //!     // Since we want to run an adiabatic simulaion, we need to set the adiabatic index.
@@ -152,6 +156,13 @@
 //!     physics_config: PhysicsConfig {
 //!         adiabatic_index: 1.4,
 //!         units_mode: UnitsMode::SI,
+//!
+//!         // The equation of state; only matters for adiabatic physics
+//!         eos: EosConfig::IdealGas,
+//!
+//!         // The parameters of specific physics systems, and the optional physics like viscosity
+//!         // or external potentials, are left at their defaults
+//!         ..Default::default()
 //!     },
 //!
 //!     // Sets the boundary conditions for the west (inner) edge of the computational area.
@@ -237,6 +248,9 @@
 //!         /// The CFL (Courant-Friedrichs-Lewy) condition parameter. Basically a safety factor to
 //!         // dampen the time step widths calculated by the CFL condition.
 //!         dt_cfl_param: 0.4,
+//!
+//!         // Whether the cell faces stay in place, or move with the fluid.
+//!         mesh_motion: MeshMotion::Eulerian,
 //!     },
 //!
 //!     // How many times should [Writer] write outputs during the simulation (not counting the
@@ -416,6 +430,7 @@
 //! #         physics_config: PhysicsConfig {
 //! #             units_mode: UnitsMode::SI,
 //! #             adiabatic_index: 1.4,
+//! #             ..Default::default()
 //! #         },
 //! #         boundary_condition_west: BoundaryMode::NoGradients,
 //! #         boundary_condition_east: BoundaryMode::NoGradients,
@@ -482,6 +497,7 @@
 //! #         physics_config: PhysicsConfig {
 //! #             units_mode: UnitsMode::SI,
 //! #             adiabatic_index: 1.4,
+//! #             ..Default::default()
 //! #         },
 //! #         boundary_condition_west: BoundaryMode::NoGradients,
 //! #         boundary_condition_east: BoundaryMode::NoGradients,
@@ -523,16 +539,11 @@
 //!
 //! # Plans
 //!
-//! Currently, corries only supports cartesian meshes, though non-cartesian meshes are coming very
-//! soon.
-//!
-//! * Add cylindrical geometry + Source trait + GeometricSource + Sedov test
-//! * Add spherical geometry + new Sedov case
+//! * Add Sedov tests for cylindrical and spherical meshes
 //! * Add 2D adiabatic Euler and 2D isothermal Euler
 //! * Add adiabatic and isothermal Navier Stokes physics
-//! * Add logcylindrical geometry + Vortex test
-//! * Add gravitational source + Bondi test
-//! * Add viscosity source + Pringle test
+//! * Add Vortex test on logcylindrical meshes
+//! * Add Bondi test
 //! * investigate the possibility of putting boundary conditions into [State]
 //!   * not really possible currently, because I cannot split the borrows between the boundary
 //!   conditions and the variables without using Cell for interior mutability.
@@ -548,6 +559,7 @@ mod errorhandling;
 pub mod macros;
 pub mod initfuncs;
 pub mod mesh;
+pub mod refinement;
 pub mod rhs;
 pub mod solver;
 pub mod state;
@@ -562,6 +574,7 @@ pub mod writer;
 /// * [corries::directions](crate::directions)
 /// * [corries::initfuncs](crate::initfuncs)
 /// * [corries::mesh](crate::mesh)
+/// * [corries::refinement](crate::refinement)
 /// * [corries::rhs](crate::rhs)
 /// * [corries::solver](crate::solver)
 /// * [corries::state](crate::state)
@@ -569,12 +582,14 @@ pub mod writer;
 /// * [corries::units](crate::units)
 /// * [corries::writer](crate::writer)
 ///
-/// as well as the [set_Physics_and_E] macro
+/// as well as the [set_Physics_and_E] and [dispatch_S] macros
 pub mod prelude {
     pub use crate::components::*;
     pub use crate::config::*;
     pub use crate::directions::*;
+    pub use crate::dispatch_S;
     pub use crate::mesh::*;
+    pub use crate::refinement::*;
     pub use crate::rhs::*;
     pub use crate::set_Physics_and_E;
     pub use crate::solver::*;
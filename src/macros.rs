@@ -18,6 +18,20 @@
 ///
 /// * `Euler1DAdiabatic`
 /// * `Euler1DIsot`
+/// * `Euler2DAdiabatic`
+/// * `Euler2DIsot`
+/// * `IdealMhd`
+/// * `Srhd`
+/// * `ShallowWater`
+/// * `LinearAdvection`
+/// * `Burgers`
+/// * `DustyGas`
+/// * `RadiationHydro`
+///
+/// or either of `Euler1DAdiabatic` and `Euler1DIsot`, followed by the number of passive scalars,
+/// which selects [Euler1DAdiabaticScalars](crate::state::systems::Euler1DAdiabaticScalars) and
+/// [Euler1DIsotScalars](crate::state::systems::Euler1DIsotScalars) respectively, or
+/// `ReactingFlow`, followed by the number of species in its reaction network.
 ///
 /// # Examples
 ///
@@ -42,6 +56,51 @@
 /// assert_eq!(TypeId::of::<P>(), TypeId::of::<Euler1DIsot<S>>());
 /// assert_eq!(E, 2);
 /// ```
+///
+/// ```
+/// use corries::prelude::*;
+/// use std::any::TypeId;
+///
+/// // Set up isothermal Euler physics that carries the eta momentum
+/// const S: usize = 100;
+/// set_Physics_and_E!(Euler2DIsot);
+/// assert_eq!(TypeId::of::<P>(), TypeId::of::<Euler2DIsot<S>>());
+/// assert_eq!(E, 3);
+/// ```
+///
+/// ```
+/// use corries::prelude::*;
+/// use std::any::TypeId;
+///
+/// // Set up ideal magnetohydrodynamics
+/// const S: usize = 100;
+/// set_Physics_and_E!(IdealMhd);
+/// assert_eq!(TypeId::of::<P>(), TypeId::of::<IdealMhd<S>>());
+/// assert_eq!(E, 7);
+/// ```
+///
+/// ```
+/// use corries::prelude::*;
+/// use std::any::TypeId;
+///
+/// // Set up adiabatic 1d Euler physics that carries two passive scalars
+/// const S: usize = 100;
+/// set_Physics_and_E!(Euler1DAdiabatic, 2);
+/// assert_eq!(TypeId::of::<P>(), TypeId::of::<Euler1DAdiabaticScalars<E, S>>());
+/// assert_eq!(E, 5);
+/// assert_eq!(P::NUM_SCALARS, 2);
+/// ```
+///
+/// ```
+/// use corries::prelude::*;
+/// use std::any::TypeId;
+///
+/// // Set up reacting flow with atomic hydrogen, protons, and electrons
+/// const S: usize = 100;
+/// set_Physics_and_E!(ReactingFlow, 3);
+/// assert_eq!(TypeId::of::<P>(), TypeId::of::<ReactingFlow<E, S>>());
+/// assert_eq!(E, 6);
+/// ```
 #[macro_export]
 macro_rules! set_Physics_and_E {
     (Euler1DAdiabatic) => {
@@ -52,6 +111,116 @@ macro_rules! set_Physics_and_E {
         type P = Euler1DIsot<S>;
         const E: usize = P::NUM_EQ;
     };
+    (Euler2DAdiabatic) => {
+        type P = Euler2DAdiabatic<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (Euler2DIsot) => {
+        type P = Euler2DIsot<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (IdealMhd) => {
+        type P = IdealMhd<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (Srhd) => {
+        type P = Srhd<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (ShallowWater) => {
+        type P = ShallowWater<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (LinearAdvection) => {
+        type P = LinearAdvection<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (Burgers) => {
+        type P = Burgers<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (DustyGas) => {
+        type P = DustyGas<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (RadiationHydro) => {
+        type P = RadiationHydro<S>;
+        const E: usize = P::NUM_EQ;
+    };
+    (Euler1DAdiabatic, $n:expr) => {
+        const E: usize = 3 + $n;
+        type P = Euler1DAdiabaticScalars<E, S>;
+    };
+    (Euler1DIsot, $n:expr) => {
+        const E: usize = 2 + $n;
+        type P = Euler1DIsotScalars<E, S>;
+    };
+    (ReactingFlow, $n:expr) => {
+        const E: usize = 3 + $n;
+        type P = ReactingFlow<E, S>;
+    };
+}
+
+/// Runs a block of code with the constant `S` picked at runtime from a fixed list of mesh sizes.
+///
+/// Since `S` is a const generic parameter throughout [corries](crate), it has to be known at
+/// compile time. This macro takes a runtime cell count and a list of literal sizes, and expands to
+/// a `match` that compiles the block once for each of these sizes, defining the constant `S` for
+/// each arm. That way a single executable can run simulations with a couple of different
+/// resolutions, for example for convergence studies, without giving up the performance of a
+/// compile time `S`.
+///
+/// NOTE: Every size the executable is supposed to run with has to be listed when compiling it, and
+/// each listed size compiles the block once more. If the sizes are not known at compile time, use
+/// `S =` [RUNTIME_SIZE](crate::mesh::RUNTIME_SIZE) instead, which sizes the simulation after
+/// [MeshConfig::n_all](crate::MeshConfig) at the cost of the compile time loop bounds.
+///
+/// The block has to evaluate to a [`color_eyre::Result`]. If the runtime cell count does not
+/// match any of the listed sizes, the macro evaluates to an error.
+///
+/// NOTE: Just like with [set_Physics_and_E], you need to have `color_eyre` as a dependency.
+///
+/// # Arguments
+///
+/// * the runtime cell count, for example taken from [MeshConfig::n_all](crate::MeshConfig)
+/// * a list of literal sizes that the block is compiled for
+/// * the block to run
+///
+/// # Examples
+///
+/// ```
+/// use corries::prelude::*;
+/// use color_eyre::Result;
+///
+/// fn n_comp(n_all: usize) -> Result<usize> {
+///     dispatch_S!(n_all, [10, 20, 40], {
+///         let mut meshconfig = MeshConfig::default_riemann_test();
+///         meshconfig.n_all = Some(n_all);
+///         let mesh = Mesh::<S>::new(&meshconfig)?;
+///         Ok(mesh.n_comp)
+///     })
+/// }
+///
+/// assert_eq!(n_comp(20).unwrap(), 16);
+/// assert!(n_comp(30).is_err());
+/// ```
+#[macro_export]
+macro_rules! dispatch_S {
+    ($n:expr, [$($s:literal),+ $(,)?], $body:block) => {
+        match $n {
+            $(
+                $s => {
+                    const S: usize = $s;
+                    $body
+                },
+            )+
+            n => Err(color_eyre::eyre::eyre!(
+                "dispatch_S was not compiled for a mesh with {} cells! Available sizes: {:?}",
+                n,
+                [$($s),+]
+            )),
+        }
+    };
 }
 
 /// Macro to check that each double in a list is finite.
@@ -4,17 +4,41 @@
 
 //! Exports the [Mesh] struct that provides coordinates and geometric information
 
-use crate::config::meshconfig::{MeshConfig, MeshMode};
+use crate::config::meshconfig::{MeshConfig, MeshGeometry, MeshMapping, MeshMode};
 use crate::data::{Data, DataName, StructAssociation};
 use crate::errorhandling::Validation;
 use crate::Collectable;
 use color_eyre::eyre::{bail, ensure, Context};
 use color_eyre::Result;
-use ndarray::Array1;
+use ndarray::{s, Array1, Array2};
+use std::f64::consts::PI;
+
+/// The size `S` of runtime-sized simulations.
+///
+/// [Mesh], [State](crate::state::State), and the types built around them usually take their
+/// number of cells from the const generic `S`, which lets the compiler specialise every loop over
+/// the mesh for that size. Setting `S = RUNTIME_SIZE` instead takes the number of cells from
+/// [MeshConfig::n_all] when building the [Mesh], and sizes everything else after the mesh. That
+/// way, one executable can run the same simulation at different resolutions, for example for a
+/// convergence study.
+///
+/// # Examples
+///
+/// ```
+/// use corries::prelude::*;
+///
+/// for n_all in [20, 40] {
+///     let mut meshconfig = MeshConfig::default_riemann_test();
+///     meshconfig.n_all = Some(n_all);
+///     let mesh = Mesh::<RUNTIME_SIZE>::new(&meshconfig).unwrap();
+///     assert_eq!(mesh.n_comp, n_all - 4);
+/// }
+/// ```
+pub const RUNTIME_SIZE: usize = 0;
 
 /// The mesh the simulation runs on.
 ///
-/// The template argument defines the number of cells in the mesh.
+/// The template argument defines the number of cells in the mesh, unless it is [RUNTIME_SIZE].
 ///
 /// This struct contains general information about the current mesh, like the number of cells,
 /// and special indexes like the first non-ghost-cell cell index, but also vectors containing the
@@ -22,8 +46,12 @@ use ndarray::Array1;
 ///
 /// Note that we use generalised coordinates xi, Phi, eta for the coordinate names. For example, in
 /// cartesian coordinates, you would then usually name these xi = x, Phi = y, eta = z, or in
-/// cylindrical coordinates you would name them xi = r (the radius), Phi = phi (the polar angle),
-/// eta = z.
+/// cylindrical coordinates you would name them xi = r (the radius), eta = phi (the azimuthal
+/// angle), Phi = z, and in spherical coordinates xi = r, eta = theta (the polar angle), Phi = phi.
+///
+/// Curvilinear meshes are one-dimensional, i.e. a cell always covers the full annulus or spherical
+/// shell. If such a mesh starts at the origin (`xi_in == 0.0`), the western ghost cells are placed
+/// at negative xi and their geometry mirrors the cells on the other side of the origin.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Mesh<const S: usize> {
     /// The kind of mesh this is, i.e. cartesian, log-cylindrical, etc.
@@ -187,6 +215,10 @@ pub struct Mesh<const S: usize> {
 
     /// Shorthand: `-1.0 * cepe`
     pub minus_cepe: Array1<f64>,
+
+    /// Velocity of the east border of each cell along xi; non-zero only for meshes that move with
+    /// the fluid, see [MeshMotion](crate::config::numericsconfig::MeshMotion)
+    pub face_velocity_east: Array1<f64>,
 }
 
 unsafe impl<const S: usize> Send for Mesh<S> {}
@@ -208,15 +240,25 @@ impl<const S: usize> Mesh<S> {
     /// let mesh: Mesh<S> = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
     /// ```
     pub fn new(meshconf: &MeshConfig) -> Result<Mesh<S>> {
-        let mode = meshconf.mode;
+        let mode = meshconf.mode.clone();
+        let geometry = mode.geometry();
         let is_logarithmic = match mode {
-            MeshMode::Cartesian => false,
+            MeshMode::Cartesian | MeshMode::Cylindrical | MeshMode::Spherical | MeshMode::Mapped { .. } => false,
+            MeshMode::LogCylindrical | MeshMode::LogSpherical => true,
         };
         let is_linear = !is_logarithmic;
 
-        let n_all = S;
-        let n_gc = 2;
-        let n_comp = S - 2 * n_gc;
+        let n_all = meshconf.n_all_for::<S>()?;
+        let n_gc = meshconf.n_gc;
+        // the ghost cells are filled from the same number of cells in the computational area
+        ensure!(
+            n_all >= 3 * n_gc,
+            "The computational area needs at least as many cells as each edge has ghost cells! Got n_all = {} ; \
+             n_gc = {}",
+            n_all,
+            n_gc
+        );
+        let n_comp = n_all - 2 * n_gc;
 
         let imin = 0;
         let imax = imin + n_all - 1;
@@ -230,66 +272,177 @@ impl<const S: usize> Mesh<S> {
         // let idisk_end = n_gc + n_disk;
 
         let dxi = match mode {
-            MeshMode::Cartesian => (xi_out - xi_in) / n_comp as f64,
+            MeshMode::Cartesian | MeshMode::Cylindrical | MeshMode::Spherical => (xi_out - xi_in) / n_comp as f64,
+            MeshMode::LogCylindrical | MeshMode::LogSpherical => (xi_out / xi_in).ln() / n_comp as f64,
+            MeshMode::Mapped { .. } => 1.0 / n_comp as f64,
         };
-        let dphi = match mode {
-            MeshMode::Cartesian => 1.0,
+        // for spherical meshes, deta * dphi has to be the full solid angle 4 * pi, where deta = 2
+        // is the integral of sin(theta) over the polar angle
+        let dphi = match geometry {
+            MeshGeometry::Cartesian | MeshGeometry::Cylindrical => 1.0,
+            MeshGeometry::Spherical => 2.0 * PI,
         };
-        let deta = match mode {
-            MeshMode::Cartesian => 1.0,
+        let deta = match geometry {
+            MeshGeometry::Cartesian => 1.0,
+            MeshGeometry::Cylindrical => 2.0 * PI,
+            MeshGeometry::Spherical => 2.0,
         };
-
-        let xi_west = {
-            let mut v = Array1::zeros(S);
-            let mut f = |x: f64| {
+        let at_origin = geometry != MeshGeometry::Cartesian && xi_in == 0.0;
+
+        let (xi_west, xi_cent, xi_east) = match &mode {
+            MeshMode::Mapped { mapping, .. } => {
+                let faces = match mapping {
+                    MeshMapping::Function(f) => {
+                        Array1::from_shape_fn(n_comp + 1, |i| xi_in + (xi_out - xi_in) * f(i as f64 * dxi))
+                    },
+                    MeshMapping::Faces(faces) => {
+                        ensure!(
+                            faces.len() == n_comp + 1,
+                            "Mapping faces need to have n_all - 2 * n_gc + 1 = {} entries! Got {} entries",
+                            n_comp + 1,
+                            faces.len()
+                        );
+                        Array1::from_vec(faces.clone())
+                    },
+                };
+                let width_in = faces[1] - faces[0];
+                let width_out = faces[n_comp] - faces[n_comp - 1];
+                let mut xi_west = Array1::zeros(n_all);
                 for i in imin..=imax {
-                    v[i] = x + dxi * (i as i64 - ixi_in as i64) as f64;
+                    xi_west[i] = if i < ixi_in {
+                        if at_origin {
+                            -faces[ixi_in - i]
+                        } else {
+                            xi_in - width_in * (ixi_in - i) as f64
+                        }
+                    } else if i <= ixi_out + 1 {
+                        faces[i - ixi_in]
+                    } else {
+                        xi_out + width_out * (i - ixi_out - 1) as f64
+                    };
                 }
-            };
-            match mode {
-                MeshMode::Cartesian => f(xi_in),
-            };
-            v
-        };
-        let xi_cent = match mode {
-            MeshMode::Cartesian => xi_west.mapv(|xi| xi + 0.5 * dxi),
-        };
-        let xi_east = match mode {
-            MeshMode::Cartesian => xi_west.mapv(|xi| xi + dxi),
+                let mut xi_east = Array1::zeros(n_all);
+                for i in imin..imax {
+                    xi_east[i] = xi_west[i + 1];
+                }
+                xi_east[imax] = xi_west[imax] + width_out;
+                let xi_cent = match mapping {
+                    MeshMapping::Function(f) => Array1::from_shape_fn(n_all, |i| {
+                        if (ixi_in..=ixi_out).contains(&i) {
+                            xi_in + (xi_out - xi_in) * f(((i - ixi_in) as f64 + 0.5) * dxi)
+                        } else {
+                            0.5 * (xi_west[i] + xi_east[i])
+                        }
+                    }),
+                    MeshMapping::Faces(_) => 0.5 * (&xi_west + &xi_east),
+                };
+                (xi_west, xi_cent, xi_east)
+            },
+            MeshMode::Cartesian
+            | MeshMode::Cylindrical
+            | MeshMode::LogCylindrical
+            | MeshMode::Spherical
+            | MeshMode::LogSpherical => {
+                let xi_west = {
+                    let mut v = Array1::zeros(n_all);
+                    let mut f = |x: f64| {
+                        for i in imin..=imax {
+                            v[i] = x + dxi * (i as i64 - ixi_in as i64) as f64;
+                        }
+                    };
+                    if is_logarithmic {
+                        f(xi_in.ln());
+                        v.mapv_inplace(f64::exp);
+                    } else {
+                        f(xi_in);
+                    }
+                    v
+                };
+                let xi_cent = if is_logarithmic {
+                    xi_west.mapv(|xi| xi * (0.5 * dxi).exp())
+                } else {
+                    xi_west.mapv(|xi| xi + 0.5 * dxi)
+                };
+                let xi_east = if is_logarithmic {
+                    xi_west.mapv(|xi| xi * dxi.exp())
+                } else {
+                    xi_west.mapv(|xi| xi + dxi)
+                };
+                (xi_west, xi_cent, xi_east)
+            },
         };
 
-        let xi_cent_inv = xi_cent.mapv(f64::recip);
-        let xi_west_inv = xi_west.mapv(f64::recip);
-        let xi_east_inv = xi_east.mapv(f64::recip);
+        let cell_width = &xi_east - &xi_west;
+        let cell_width_inv = cell_width.mapv(f64::recip);
+        ensure!(
+            cell_width.iter().all(|&w| w > 0.0),
+            "The cell faces of a {:?} mesh must be strictly increasing!",
+            mode
+        );
+
+        if geometry != MeshGeometry::Cartesian && !at_origin {
+            ensure!(
+                xi_west[imin] > 0.0,
+                "The ghost cells of a {:?} mesh must not cross the origin! Got xi_west[imin] = {}",
+                mode,
+                xi_west[imin]
+            );
+        }
+
+        // the distances to the origin; these only differ from xi for the ghost cells of meshes
+        // starting at the origin, which mirror the cells on the other side of the origin
+        let r_cent = xi_cent.mapv(f64::abs);
+        let r_west = xi_west.mapv(f64::abs);
+        let r_east = xi_east.mapv(f64::abs);
+
+        // the inverse of xi = 0.0 is set to 0.0 to keep the arrays finite
+        let recip_or_zero = |xi: f64| if xi == 0.0 { 0.0 } else { 1.0 / xi };
+        let xi_cent_inv = xi_cent.mapv(recip_or_zero);
+        let xi_west_inv = xi_west.mapv(recip_or_zero);
+        let xi_east_inv = xi_east.mapv(recip_or_zero);
 
+        // for mapped meshes, the metric along xi is the derivative of the mapping, approximated
+        // with the cell widths
         let h_xi_cent = match mode {
-            MeshMode::Cartesian => xi_cent.mapv(|_| 1.0),
+            MeshMode::Cartesian | MeshMode::Cylindrical | MeshMode::Spherical => r_cent.mapv(|_| 1.0),
+            MeshMode::LogCylindrical | MeshMode::LogSpherical => r_cent.clone(),
+            MeshMode::Mapped { .. } => &cell_width / dxi,
         };
         let h_xi_west = match mode {
-            MeshMode::Cartesian => xi_west.mapv(|_| 1.0),
+            MeshMode::Cartesian | MeshMode::Cylindrical | MeshMode::Spherical => r_west.mapv(|_| 1.0),
+            MeshMode::LogCylindrical | MeshMode::LogSpherical => r_west.clone(),
+            MeshMode::Mapped { .. } => &cell_width / dxi,
         };
         let h_xi_east = match mode {
-            MeshMode::Cartesian => xi_east.mapv(|_| 1.0),
+            MeshMode::Cartesian | MeshMode::Cylindrical | MeshMode::Spherical => r_east.mapv(|_| 1.0),
+            MeshMode::LogCylindrical | MeshMode::LogSpherical => r_east.clone(),
+            MeshMode::Mapped { .. } => &cell_width / dxi,
         };
 
-        let h_eta_cent = match mode {
-            MeshMode::Cartesian => xi_cent.mapv(|_| 1.0),
+        let h_eta_cent = match geometry {
+            MeshGeometry::Cartesian => r_cent.mapv(|_| 1.0),
+            MeshGeometry::Cylindrical | MeshGeometry::Spherical => r_cent.clone(),
         };
-        let h_eta_west = match mode {
-            MeshMode::Cartesian => xi_west.mapv(|_| 1.0),
+        let h_eta_west = match geometry {
+            MeshGeometry::Cartesian => r_west.mapv(|_| 1.0),
+            MeshGeometry::Cylindrical | MeshGeometry::Spherical => r_west.clone(),
         };
-        let h_eta_east = match mode {
-            MeshMode::Cartesian => xi_east.mapv(|_| 1.0),
+        let h_eta_east = match geometry {
+            MeshGeometry::Cartesian => r_east.mapv(|_| 1.0),
+            MeshGeometry::Cylindrical | MeshGeometry::Spherical => r_east.clone(),
         };
 
-        let h_phi_cent = match mode {
-            MeshMode::Cartesian => xi_cent.mapv(|_| 1.0),
+        let h_phi_cent = match geometry {
+            MeshGeometry::Cartesian | MeshGeometry::Cylindrical => r_cent.mapv(|_| 1.0),
+            MeshGeometry::Spherical => r_cent.clone(),
         };
-        let h_phi_west = match mode {
-            MeshMode::Cartesian => xi_west.mapv(|_| 1.0),
+        let h_phi_west = match geometry {
+            MeshGeometry::Cartesian | MeshGeometry::Cylindrical => r_west.mapv(|_| 1.0),
+            MeshGeometry::Spherical => r_west.clone(),
         };
-        let h_phi_east = match mode {
-            MeshMode::Cartesian => xi_east.mapv(|_| 1.0),
+        let h_phi_east = match geometry {
+            MeshGeometry::Cartesian | MeshGeometry::Cylindrical => r_east.mapv(|_| 1.0),
+            MeshGeometry::Spherical => r_east.clone(),
         };
 
         let sqrt_g = &h_xi_cent * &h_eta_cent * &h_phi_cent;
@@ -300,26 +453,36 @@ impl<const S: usize> Mesh<S> {
         let d_area_xi_deta_dphi_west = &h_eta_west * &h_phi_west;
         let d_area_xi_deta_dphi_east = &h_eta_east * &h_phi_east;
 
-        let area_cell = match mode {
-            MeshMode::Cartesian => (&xi_east - &xi_west) * (&xi_east - &xi_west),
-        }
-        .map(|a| a.abs());
+        let area_cell = match geometry {
+            MeshGeometry::Cartesian => &cell_width * &cell_width,
+            MeshGeometry::Cylindrical => 0.5 * deta * &cell_width * (&r_east + &r_west),
+            // cross section of the shell in a meridional plane, i.e. a half annulus
+            MeshGeometry::Spherical => 0.5 * PI * &cell_width * (&r_east + &r_west),
+        };
 
         let area_west = &d_area_xi_deta_dphi_west * deta * dphi;
         let area_east = &d_area_xi_deta_dphi_east * deta * dphi;
-        let volume = &sqrt_g * dxi * deta * dphi;
+        // integrate annuli and shells exactly, because sqrt_g * dxi is only exact for cartesian meshes
+        let volume = match geometry {
+            MeshGeometry::Cartesian => &cell_width * deta * dphi,
+            MeshGeometry::Cylindrical => 0.5 * deta * dphi * &cell_width * (&r_east + &r_west),
+            MeshGeometry::Spherical => {
+                deta * dphi / 3.0 * &cell_width * (&r_east * &r_east + &r_east * &r_west + &r_west * &r_west)
+            },
+        };
         let deta_dphi_d_volume = deta * dphi / (f64::EPSILON + &volume);
 
-        let cell_width = &xi_east - &xi_west;
-        let cell_width_inv = cell_width.mapv(f64::recip);
-
         let cexe = 0.5 * (&h_phi_east + &h_phi_west) * (&h_eta_east - &h_eta_west) * &deta_dphi_d_volume;
         let cpxp = 0.5 * (&h_eta_east + &h_eta_west) * (&h_phi_east - &h_phi_west) * &deta_dphi_d_volume;
 
-        let cxex = xi_cent.mapv(|_| 1.0);
-        let cpep = xi_cent.mapv(|_| 1.0);
-        let cxpx = xi_cent.mapv(|_| 1.0);
-        let cepe = xi_cent.mapv(|_| 1.0);
+        // the geometric scales never change along eta and Phi, because all curvilinear meshes are
+        // symmetric along those directions, so these commutators vanish there; cartesian meshes keep
+        // their unit coefficients
+        let c_transverse = if mode == MeshMode::Cartesian { 1.0 } else { 0.0 };
+        let cxex = xi_cent.mapv(|_| c_transverse);
+        let cpep = xi_cent.mapv(|_| c_transverse);
+        let cxpx = xi_cent.mapv(|_| c_transverse);
+        let cepe = xi_cent.mapv(|_| c_transverse);
 
         let minus_cexe = cexe.mapv(|c| -1.0 * c);
         let minus_cpxp = cpxp.mapv(|c| -1.0 * c);
@@ -327,6 +490,7 @@ impl<const S: usize> Mesh<S> {
         let minus_cpep = cpep.mapv(|c| -1.0 * c);
         let minus_cxpx = cxpx.mapv(|c| -1.0 * c);
         let minus_cepe = cepe.mapv(|c| -1.0 * c);
+        let face_velocity_east = Array1::zeros(n_all);
 
         let mesh = Mesh {
             mode,
@@ -386,6 +550,7 @@ impl<const S: usize> Mesh<S> {
             minus_cpep,
             minus_cxpx,
             minus_cepe,
+            face_velocity_east,
         };
 
         if cfg!(feature = "validation") {
@@ -394,6 +559,130 @@ impl<const S: usize> Mesh<S> {
 
         Ok(mesh)
     }
+
+    /// Moves the faces of the computational area by `dt * face_velocity_east`, and rebuilds the
+    /// geometry of the mesh around them.
+    ///
+    /// The moved mesh is a [MeshMode::Mapped] mesh with the same [MeshGeometry], and the edges of
+    /// the computational area stay in place. The face velocities are kept as they are, and so is
+    /// `is_logarithmic`, so that boundary conditions on a moving logarithmic mesh keep
+    /// extrapolating logarithmically.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - the time the faces move for
+    pub fn move_faces(&mut self, dt: f64) -> Result<()> {
+        // the west face of cell i is the east face of cell i - 1
+        let faces = (self.ixi_in..=self.ixi_out + 1)
+            .map(|i| {
+                if i == self.ixi_in {
+                    self.xi_in
+                } else if i == self.ixi_out + 1 {
+                    self.xi_out
+                } else {
+                    self.xi_west[i] + dt * self.face_velocity_east[i - 1]
+                }
+            })
+            .collect();
+        let face_velocity_east = self.face_velocity_east.clone();
+        let is_logarithmic = self.is_logarithmic;
+        *self = Mesh::new(&MeshConfig {
+            mode: MeshMode::Mapped {
+                geometry: self.mode.geometry(),
+                mapping: MeshMapping::Faces(faces),
+            },
+            xi_in: self.xi_in,
+            xi_out: self.xi_out,
+            n_gc: self.n_gc,
+            n_all: Some(self.n_all),
+        })
+        .context("Rebuilding the Mesh with moved faces in Mesh::move_faces")?;
+        self.face_velocity_east = face_velocity_east;
+        self.is_logarithmic = is_logarithmic;
+        self.is_linear = !is_logarithmic;
+        Ok(())
+    }
+
+    /// Remaps the conservative variables `cons` on this mesh onto the `target` mesh.
+    ///
+    /// Inside each cell of this mesh, `cons` is reconstructed linearly with minmod limited slopes
+    /// around the volume weighted centre of the cell, and that reconstruction is integrated exactly
+    /// over the overlap with each cell of `target`. Thus, the sum of `cons * volume` over the
+    /// computational area is the same on both meshes, up to round-off errors.
+    ///
+    /// Both meshes need to cover the same computational area, and only the computational area of
+    /// `cons_target` is written to.
+    ///
+    /// # Arguments
+    ///
+    /// * `cons` - conservative variables on this mesh, including up-to-date ghost cells
+    /// * `target` - the mesh to remap onto
+    /// * `cons_target` - receives the conservative variables on `target`
+    pub fn remap(&self, cons: &Array2<f64>, target: &Mesh<S>, cons_target: &mut Array2<f64>) {
+        let geometry = self.mode.geometry();
+        let deta_dphi = self.deta * self.dphi;
+        let xc = &self.xi_cent;
+        cons_target.slice_mut(s![.., target.ixi_in..=target.ixi_out]).fill(0.0);
+        let mut slopes = Array1::zeros(cons.nrows());
+        let mut k = target.ixi_in;
+        for i in self.ixi_in..=self.ixi_out {
+            let (west, east) = (self.xi_west[i], self.xi_east[i]);
+            let (m0, m1) = volume_moments(geometry, west, east);
+            let centre = m1 / m0;
+            for (j, slope) in slopes.iter_mut().enumerate() {
+                *slope = minmod(
+                    (cons[[j, i]] - cons[[j, i - 1]]) / (xc[i] - xc[i - 1]),
+                    (cons[[j, i + 1]] - cons[[j, i]]) / (xc[i + 1] - xc[i]),
+                );
+            }
+            loop {
+                let overlap_west = west.max(target.xi_west[k]);
+                let overlap_east = east.min(target.xi_east[k]);
+                if overlap_east > overlap_west {
+                    let (m0, m1) = volume_moments(geometry, overlap_west, overlap_east);
+                    for (j, slope) in slopes.iter().enumerate() {
+                        cons_target[[j, k]] += deta_dphi * (cons[[j, i]] * m0 + slope * (m1 - centre * m0));
+                    }
+                }
+                if target.xi_east[k] >= east || k == target.ixi_out {
+                    break;
+                }
+                k += 1;
+            }
+        }
+        for k in target.ixi_in..=target.ixi_out {
+            cons_target.column_mut(k).mapv_inplace(|c| c / target.volume[k]);
+        }
+    }
+}
+
+/// Returns the integrals of `1` and `xi` over `[west, east]` with the volume element of `geometry`,
+/// without the factor `deta * dphi`. These are factorised to avoid cancellation for thin cells.
+fn volume_moments(geometry: MeshGeometry, west: f64, east: f64) -> (f64, f64) {
+    let width = east - west;
+    match geometry {
+        MeshGeometry::Cartesian => (width, 0.5 * width * (east + west)),
+        MeshGeometry::Cylindrical => (
+            0.5 * width * (east + west),
+            width * (east * east + east * west + west * west) / 3.0,
+        ),
+        MeshGeometry::Spherical => (
+            width * (east * east + east * west + west * west) / 3.0,
+            0.25 * width * (east + west) * (east * east + west * west),
+        ),
+    }
+}
+
+/// The minmod slope limiter, i.e. the argument with the smaller magnitude if both have the same
+/// sign, and 0 otherwise
+pub(crate) fn minmod(a: f64, b: f64) -> f64 {
+    if a * b <= 0.0 {
+        0.0
+    } else if a.abs() < b.abs() {
+        a
+    } else {
+        b
+    }
 }
 
 impl<const S: usize> Validation for Mesh<S> {
@@ -441,7 +730,8 @@ impl<const S: usize> Validation for Mesh<S> {
             self.minus_cxex,
             self.minus_cpep,
             self.minus_cxpx,
-            self.minus_cepe
+            self.minus_cepe,
+            self.face_velocity_east
         ];
 
         Ok(())
@@ -473,7 +763,353 @@ mod tests {
         fn arb_mesh(mode: MeshMode)
                     (xi_in in 0.1f64..100_000.0,
                     xi_out in 0.1f64..100_000.0) -> Result<Mesh<S>> {
-            Mesh::new(&MeshConfig { mode, xi_in, xi_out })
+            Mesh::new(&MeshConfig { mode: mode.clone(), xi_in, xi_out, n_gc: 2, n_all: None })
+        }
+    }
+
+    mod cylindrical_meshes {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn check_annuli(mesh: &Mesh<S>) {
+            assert_relative_eq!(mesh.xi_in, mesh.xi_west[mesh.ixi_in], max_relative = 1.0e-12);
+            assert_relative_eq!(mesh.xi_out, mesh.xi_east[mesh.ixi_out], max_relative = 1.0e-10);
+            for i in mesh.imin..=mesh.imax {
+                let (r_west, r_east) = (mesh.xi_west[i], mesh.xi_east[i]);
+                // annulus with unit height
+                let volume = PI * (r_east - r_west) * (r_east + r_west);
+                assert_relative_eq!(mesh.volume[i], volume, max_relative = 1.0e-10);
+                assert_relative_eq!(mesh.area_cell[i], volume, max_relative = 1.0e-10);
+                assert_relative_eq!(mesh.area_west[i], 2.0 * PI * r_west, max_relative = 1.0e-12);
+                assert_relative_eq!(mesh.area_east[i], 2.0 * PI * r_east, max_relative = 1.0e-12);
+                assert_relative_eq!(mesh.cexe[i], 2.0 / (r_west + r_east), max_relative = 1.0e-8);
+                assert_eq!(mesh.cpxp[i], 0.0);
+            }
+            let comp_volume = mesh.volume.slice(ndarray::s![mesh.ixi_in..=mesh.ixi_out]).sum();
+            assert_relative_eq!(
+                comp_volume,
+                PI * (mesh.xi_out - mesh.xi_in) * (mesh.xi_out + mesh.xi_in),
+                max_relative = 1.0e-8
+            );
+        }
+
+        proptest! {
+            #[test]
+            fn cylindrical_meshes_constructed_correctly(mesh in arb_mesh(MeshMode::Cylindrical)) {
+                prop_assume!(mesh.is_ok());
+                let mesh = mesh.unwrap();
+                prop_assume!(mesh.xi_in < mesh.xi_out);
+                check_annuli(&mesh);
+                for i in mesh.imin..mesh.imax {
+                    assert_relative_eq!(mesh.xi_cent[i + 1] - mesh.xi_cent[i], mesh.dxi, max_relative = 1.0e-6);
+                }
+            }
+
+            #[test]
+            fn logcylindrical_meshes_constructed_correctly(mesh in arb_mesh(MeshMode::LogCylindrical)) {
+                prop_assume!(mesh.is_ok());
+                let mesh = mesh.unwrap();
+                prop_assume!(mesh.xi_in < mesh.xi_out);
+                assert!(mesh.is_logarithmic);
+                check_annuli(&mesh);
+                for i in mesh.imin..=mesh.imax {
+                    assert_relative_eq!(mesh.xi_east[i] / mesh.xi_west[i], mesh.dxi.exp(), max_relative = 1.0e-12);
+                    assert_relative_eq!(
+                        mesh.xi_cent[i],
+                        (mesh.xi_west[i] * mesh.xi_east[i]).sqrt(),
+                        max_relative = 1.0e-12
+                    );
+                }
+            }
+        }
+    }
+
+    mod spherical_meshes {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        fn check_shells(mesh: &Mesh<S>) {
+            assert_relative_eq!(mesh.xi_in, mesh.xi_west[mesh.ixi_in], max_relative = 1.0e-12);
+            assert_relative_eq!(mesh.xi_out, mesh.xi_east[mesh.ixi_out], max_relative = 1.0e-10);
+            for i in mesh.imin..=mesh.imax {
+                let (r_west, r_east) = (mesh.xi_west[i], mesh.xi_east[i]);
+                // factorised to avoid cancellation for thin shells
+                let volume = 4.0 / 3.0 * PI * (r_east - r_west) * (r_east * r_east + r_east * r_west + r_west * r_west);
+                assert_relative_eq!(mesh.volume[i], volume, max_relative = 1.0e-10);
+                assert_relative_eq!(mesh.area_west[i], 4.0 * PI * r_west * r_west, max_relative = 1.0e-12);
+                assert_relative_eq!(mesh.area_east[i], 4.0 * PI * r_east * r_east, max_relative = 1.0e-12);
+                assert_relative_eq!(mesh.cexe[i], mesh.cpxp[i], max_relative = 1.0e-12);
+                assert_relative_eq!(
+                    mesh.cexe[i] + mesh.cpxp[i],
+                    (mesh.area_east[i] - mesh.area_west[i]) / mesh.volume[i],
+                    max_relative = 1.0e-8
+                );
+            }
+            let comp_volume = mesh.volume.slice(ndarray::s![mesh.ixi_in..=mesh.ixi_out]).sum();
+            assert_relative_eq!(
+                comp_volume,
+                4.0 / 3.0 * PI * (mesh.xi_out.powi(3) - mesh.xi_in.powi(3)),
+                max_relative = 1.0e-8
+            );
+        }
+
+        proptest! {
+            #[test]
+            fn spherical_meshes_constructed_correctly(mesh in arb_mesh(MeshMode::Spherical)) {
+                prop_assume!(mesh.is_ok());
+                let mesh = mesh.unwrap();
+                prop_assume!(mesh.xi_in < mesh.xi_out);
+                check_shells(&mesh);
+            }
+
+            #[test]
+            fn logspherical_meshes_constructed_correctly(mesh in arb_mesh(MeshMode::LogSpherical)) {
+                prop_assume!(mesh.is_ok());
+                let mesh = mesh.unwrap();
+                prop_assume!(mesh.xi_in < mesh.xi_out);
+                assert!(mesh.is_logarithmic);
+                check_shells(&mesh);
+                for i in mesh.imin..=mesh.imax {
+                    assert_relative_eq!(mesh.xi_east[i] / mesh.xi_west[i], mesh.dxi.exp(), max_relative = 1.0e-12);
+                }
+            }
+        }
+    }
+
+    mod origin_meshes {
+        use super::*;
+        use approx::assert_relative_eq;
+
+        #[test]
+        fn meshes_at_origin_mirror_ghost_cells() {
+            for mode in [MeshMode::Cylindrical, MeshMode::Spherical] {
+                let mesh: Mesh<S> = Mesh::new(&MeshConfig {
+                    mode,
+                    xi_in: 0.0,
+                    xi_out: 1.0,
+                    n_gc: 2,
+                    n_all: None,
+                })
+                .unwrap();
+                assert_eq!(mesh.xi_west[mesh.ixi_in], 0.0);
+                assert_eq!(mesh.area_west[mesh.ixi_in], 0.0);
+                for k in 0..mesh.n_gc {
+                    let (ghost, mirror) = (mesh.ixi_in - 1 - k, mesh.ixi_in + k);
+                    assert_relative_eq!(mesh.xi_cent[ghost], -mesh.xi_cent[mirror], max_relative = 1.0e-12);
+                    assert_relative_eq!(mesh.volume[ghost], mesh.volume[mirror], max_relative = 1.0e-12);
+                    assert_relative_eq!(mesh.area_east[ghost], mesh.area_west[mirror], max_relative = 1.0e-12);
+                    assert_relative_eq!(mesh.area_west[ghost], mesh.area_east[mirror], max_relative = 1.0e-12);
+                }
+            }
+        }
+
+        #[test]
+        fn log_meshes_cannot_start_at_origin() {
+            for mode in [MeshMode::LogCylindrical, MeshMode::LogSpherical] {
+                let meshconfig = MeshConfig {
+                    mode,
+                    xi_in: 0.0,
+                    xi_out: 1.0,
+                    n_gc: 2,
+                    n_all: None,
+                };
+                assert!(meshconfig.validate().is_err());
+            }
+        }
+    }
+
+    mod mapped_meshes {
+        use super::*;
+        use approx::assert_relative_eq;
+        const S: usize = 104;
+
+        fn refine_centre(s: f64) -> f64 {
+            0.5 * (1.0 + (4.0 * (s - 0.5)).sinh() / 2.0f64.sinh())
+        }
+
+        #[test]
+        fn function_mapping_places_faces() {
+            let mesh: Mesh<S> = Mesh::new(&MeshConfig {
+                mode: MeshMode::Mapped {
+                    geometry: MeshGeometry::Cartesian,
+                    mapping: MeshMapping::Function(refine_centre),
+                },
+                xi_in: 1.0,
+                xi_out: 3.0,
+                n_gc: 2,
+                n_all: None,
+            })
+            .unwrap();
+            assert_relative_eq!(mesh.xi_west[mesh.ixi_in], 1.0, max_relative = 1.0e-12);
+            assert_relative_eq!(mesh.xi_east[mesh.ixi_out], 3.0, max_relative = 1.0e-12);
+            for i in mesh.imin..=mesh.imax {
+                assert!(mesh.xi_west[i] < mesh.xi_cent[i] && mesh.xi_cent[i] < mesh.xi_east[i]);
+                assert_relative_eq!(mesh.volume[i], mesh.cell_width[i], max_relative = 1.0e-12);
+                assert_relative_eq!(mesh.line_xi[i], mesh.cell_width[i], max_relative = 1.0e-12);
+            }
+            for i in mesh.imin..mesh.imax {
+                assert_eq!(mesh.xi_east[i], mesh.xi_west[i + 1]);
+            }
+            // the cells in the centre are finer than at the edges
+            let i_mid = (mesh.ixi_in + mesh.ixi_out) / 2;
+            assert!(mesh.cell_width[i_mid] < 0.5 * mesh.cell_width[mesh.ixi_in]);
+            assert_relative_eq!(mesh.cell_width[mesh.ixi_in - 1], mesh.cell_width[mesh.ixi_in]);
+            let comp_volume = mesh.volume.slice(ndarray::s![mesh.ixi_in..=mesh.ixi_out]).sum();
+            assert_relative_eq!(comp_volume, 2.0, max_relative = 1.0e-12);
+        }
+
+        #[test]
+        fn face_mapping_places_faces() {
+            let n_comp = S - 4;
+            let faces: Vec<f64> = (0..=n_comp).map(|i| (i as f64 / n_comp as f64).powi(2)).collect();
+            let mesh: Mesh<S> = Mesh::new(&MeshConfig {
+                mode: MeshMode::Mapped {
+                    geometry: MeshGeometry::Spherical,
+                    mapping: MeshMapping::Faces(faces.clone()),
+                },
+                xi_in: 0.0,
+                xi_out: 1.0,
+                n_gc: 2,
+                n_all: None,
+            })
+            .unwrap();
+            for (k, face) in faces.iter().enumerate() {
+                assert_eq!(mesh.xi_west[mesh.ixi_in + k], *face);
+            }
+            for k in 0..mesh.n_gc {
+                let (ghost, mirror) = (mesh.ixi_in - 1 - k, mesh.ixi_in + k);
+                assert_relative_eq!(mesh.volume[ghost], mesh.volume[mirror], max_relative = 1.0e-12);
+            }
+            let comp_volume = mesh.volume.slice(ndarray::s![mesh.ixi_in..=mesh.ixi_out]).sum();
+            assert_relative_eq!(comp_volume, 4.0 / 3.0 * PI, max_relative = 1.0e-12);
+        }
+
+        #[test]
+        fn invalid_mappings_are_rejected() {
+            let wrong_len = MeshConfig {
+                mode: MeshMode::Mapped {
+                    geometry: MeshGeometry::Cartesian,
+                    mapping: MeshMapping::Faces(vec![1.0, 1.5, 2.0]),
+                },
+                xi_in: 1.0,
+                xi_out: 2.0,
+                n_gc: 2,
+                n_all: None,
+            };
+            assert!(wrong_len.validate().is_ok());
+            assert!(Mesh::<S>::new(&wrong_len).is_err());
+            let not_increasing = MeshConfig {
+                mode: MeshMode::Mapped {
+                    geometry: MeshGeometry::Cartesian,
+                    mapping: MeshMapping::Faces(vec![1.0, 1.7, 1.5, 2.0]),
+                },
+                xi_in: 1.0,
+                xi_out: 2.0,
+                n_gc: 2,
+                n_all: None,
+            };
+            assert!(not_increasing.validate().is_err());
+            let not_normalised = MeshConfig {
+                mode: MeshMode::Mapped {
+                    geometry: MeshGeometry::Cartesian,
+                    mapping: MeshMapping::Function(|s| 2.0 * s),
+                },
+                xi_in: 1.0,
+                xi_out: 2.0,
+                n_gc: 2,
+                n_all: None,
+            };
+            assert!(not_normalised.validate().is_err());
+        }
+    }
+
+    mod moving_meshes {
+        use super::*;
+        use approx::assert_relative_eq;
+        const S: usize = 54;
+
+        fn total(cons: &Array2<f64>, mesh: &Mesh<S>) -> f64 {
+            (mesh.ixi_in..=mesh.ixi_out)
+                .map(|i| cons[[0, i]] * mesh.volume[i])
+                .sum()
+        }
+
+        #[test]
+        fn moved_faces_keep_the_edges() {
+            let mut mesh: Mesh<S> = Mesh::new(&MeshConfig::default_riemann_test()).unwrap();
+            let xi_west = mesh.xi_west.clone();
+            mesh.face_velocity_east.fill(1.0);
+            mesh.move_faces(0.001).unwrap();
+            assert_eq!(mesh.xi_west[mesh.ixi_in], mesh.xi_in);
+            assert_eq!(mesh.xi_east[mesh.ixi_out], mesh.xi_out);
+            for i in mesh.ixi_in + 1..=mesh.ixi_out {
+                assert_relative_eq!(mesh.xi_west[i], xi_west[i] + 0.001, max_relative = 1.0e-12);
+            }
+            assert!(mesh.face_velocity_east.iter().all(|&w| w == 1.0));
+
+            // faces must not overtake each other
+            assert!(mesh.move_faces(1.0).is_err());
+        }
+
+        #[test]
+        fn moved_logarithmic_meshes_stay_logarithmic() {
+            let mut mesh: Mesh<S> = Mesh::new(&MeshConfig {
+                mode: MeshMode::LogCylindrical,
+                ..MeshConfig::default_riemann_test()
+            })
+            .unwrap();
+            mesh.face_velocity_east.fill(1.0);
+            mesh.move_faces(0.001).unwrap();
+            assert_eq!(mesh.mode.geometry(), MeshGeometry::Cylindrical);
+            assert!(mesh.is_logarithmic);
+            assert!(!mesh.is_linear);
+        }
+
+        #[test]
+        fn remap_conserves_cons() {
+            for mode in [MeshMode::Cartesian, MeshMode::Cylindrical, MeshMode::Spherical] {
+                let mesh: Mesh<S> = Mesh::new(&MeshConfig {
+                    mode,
+                    xi_in: 0.0,
+                    xi_out: 1.0,
+                    n_gc: 2,
+                    n_all: None,
+                })
+                .unwrap();
+                let mut moved = mesh.clone();
+                moved
+                    .face_velocity_east
+                    .assign(&mesh.xi_east.mapv(|xi| (2.0 * PI * xi).sin()));
+                moved.move_faces(0.01).unwrap();
+
+                // a ramp with a jump in the middle
+                let cons = Array2::from_shape_fn((1, S), |(_, i)| {
+                    let xi = mesh.xi_cent[i];
+                    if xi < 0.5 {
+                        1.0 + xi
+                    } else {
+                        0.1 + 0.5 * xi
+                    }
+                });
+                let mut cons_moved = Array2::zeros((1, S));
+                mesh.remap(&cons, &moved, &mut cons_moved);
+                assert_relative_eq!(total(&cons_moved, &moved), total(&cons, &mesh), max_relative = 1.0e-12);
+
+                // onto itself, the remap changes nothing
+                let mut cons_same = Array2::zeros((1, S));
+                mesh.remap(&cons, &mesh, &mut cons_same);
+                for i in mesh.ixi_in..=mesh.ixi_out {
+                    assert_relative_eq!(cons_same[[0, i]], cons[[0, i]], max_relative = 1.0e-12);
+                }
+
+                // and constants stay constant
+                let ones = Array2::ones((1, S));
+                let mut ones_moved = Array2::zeros((1, S));
+                mesh.remap(&ones, &moved, &mut ones_moved);
+                for i in moved.ixi_in..=moved.ixi_out {
+                    assert_relative_eq!(ones_moved[[0, i]], 1.0, max_relative = 1.0e-12);
+                }
+            }
         }
     }
 
@@ -488,6 +1124,9 @@ mod tests {
                 prop_assume!(mesh.xi_in < mesh.xi_out);
                 assert_relative_eq!(mesh.xi_in, mesh.xi_west[mesh.ixi_in], max_relative = 1.0e-12);
                 assert_relative_eq!(mesh.xi_out, mesh.xi_east[mesh.ixi_out], max_relative = 1.0e-12);
+                for c in [&mesh.cxex, &mesh.cpep, &mesh.cxpx, &mesh.cepe] {
+                    assert!(c.iter().all(|&c| c == 1.0));
+                }
             }
         }
     }
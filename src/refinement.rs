@@ -0,0 +1,622 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [StaticRefinement], which runs a simulation on a coarse [Mesh] with statically refined
+//! [Patch]es on top of it, and [AdaptiveRefinement], which moves these patches along with the flow.
+
+use crate::{
+    boundaryconditions::BoundaryCondition, errorhandling::Validation, initfuncs::InitFn, mesh::minmod, prelude::*,
+    variables::Variables,
+};
+use color_eyre::{
+    eyre::{bail, ensure, Context},
+    Result,
+};
+use ndarray::{Array1, Array2};
+use std::any::TypeId;
+
+pub mod adaptive;
+pub use self::adaptive::AdaptiveRefinement;
+
+/// Boundary condition for the edges of a [Patch].
+///
+/// The ghost cells of a patch are filled by interpolating from the coarse mesh before each step,
+/// so this boundary condition just leaves them untouched.
+struct CoarseFineBoundary;
+
+unsafe impl Send for CoarseFineBoundary {}
+unsafe impl Sync for CoarseFineBoundary {}
+
+impl<const E: usize, const S: usize> BoundaryCondition<E, S> for CoarseFineBoundary {
+    fn apply(&mut self, _: &mut Variables<E, S>, _: &Mesh<S>) {}
+}
+
+/// A refined region of the coarse mesh, with its own [State], [Solver], and [Mesh].
+///
+/// Each coarse cell covered by the patch is split into `ratio` fine cells.
+/// Runtime-sized patches, with `SF =` [RUNTIME_SIZE], take their size from the cells they cover.
+pub struct Patch<P, N, T, const E: usize, const SF: usize>
+where
+    P: Physics<E, SF>,
+    N: NumFlux<E, SF>,
+    T: TimeSolver<P, E, SF>,
+{
+    /// The state of the patch
+    pub u: State<P, E, SF>,
+
+    /// Advances the patch in time
+    pub solver: Solver<P, N, T, E, SF>,
+
+    /// The mesh of the patch
+    pub mesh: Mesh<SF>,
+
+    /// Index of the westernmost coarse cell covered by the patch
+    pub i_coarse_west: usize,
+
+    /// Index of the easternmost coarse cell covered by the patch
+    pub i_coarse_east: usize,
+
+    /// The number of fine cells per coarse cell
+    ratio: usize,
+
+    /// Triples `(i, k, w)`, such that ghost cell `i` is interpolated as `(1 - w)` times coarse
+    /// cell `k` plus `w` times coarse cell `k + 1`
+    ghost_weights: Vec<(usize, usize, f64)>,
+
+    /// Numerical flux through the western edge of the patch, integrated over the current coarse step
+    flux_west: Array1<f64>,
+
+    /// Numerical flux through the eastern edge of the patch, integrated over the current coarse step
+    flux_east: Array1<f64>,
+}
+
+unsafe impl<P, N, T, const E: usize, const SF: usize> Send for Patch<P, N, T, E, SF>
+where
+    P: Physics<E, SF>,
+    N: NumFlux<E, SF>,
+    T: TimeSolver<P, E, SF>,
+{
+}
+unsafe impl<P, N, T, const E: usize, const SF: usize> Sync for Patch<P, N, T, E, SF>
+where
+    P: Physics<E, SF>,
+    N: NumFlux<E, SF>,
+    T: TimeSolver<P, E, SF>,
+{
+}
+
+impl<P, N, T, const E: usize, const SF: usize> Patch<P, N, T, E, SF>
+where
+    P: Physics<E, SF>,
+    N: NumFlux<E, SF>,
+    T: TimeSolver<P, E, SF>,
+{
+    /// Constructs a new [Patch] covering the coarse cells between `xi_in` and `xi_out`, and fills
+    /// it with a conservative, linear reconstruction of the coarse cells it covers.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Configuration for the coarse simulation
+    /// * `xi_in` - Western edge of the patch; has to coincide with a coarse cell face
+    /// * `xi_out` - Eastern edge of the patch; has to coincide with a coarse cell face
+    /// * `ratio` - The number of fine cells per coarse cell
+    /// * `vars_coarse` - Variables at the cell centres of the coarse mesh
+    /// * `mesh_coarse` - The coarse mesh
+    pub fn new<const S: usize>(
+        config: &CorriesConfig,
+        (xi_in, xi_out): (f64, f64),
+        ratio: usize,
+        vars_coarse: &Variables<E, S>,
+        mesh_coarse: &Mesh<S>,
+    ) -> Result<Self> {
+        let find_face = |faces: &Array1<f64>, xi: f64| {
+            (mesh_coarse.ixi_in..=mesh_coarse.ixi_out).find(|&i| (faces[i] - xi).abs() <= 1.0e-10 * xi.abs().max(1.0))
+        };
+        let (i_coarse_west, i_coarse_east) = match (
+            find_face(&mesh_coarse.xi_west, xi_in),
+            find_face(&mesh_coarse.xi_east, xi_out),
+        ) {
+            (Some(iw), Some(ie)) => (iw, ie),
+            _ => bail!(
+                "Patch edges have to coincide with cell faces of the coarse mesh! Got xi_in = {} ; xi_out = {}",
+                xi_in,
+                xi_out
+            ),
+        };
+        ensure!(
+            i_coarse_west > mesh_coarse.ixi_in && i_coarse_east < mesh_coarse.ixi_out,
+            "Patches need to be separated from the edges of the coarse mesh by at least one cell! Got xi_in = {} ; \
+             xi_out = {}",
+            xi_in,
+            xi_out
+        );
+        let n_comp = ratio * (i_coarse_east - i_coarse_west + 1);
+        let n_all = n_comp + 2 * mesh_coarse.n_gc;
+        ensure!(
+            SF == RUNTIME_SIZE || n_all == SF,
+            "The patch size SF has to fit the refined cells plus ghost cells! Got SF = {} ; expected {}",
+            SF,
+            n_all
+        );
+
+        let (xi_in, xi_out) = (mesh_coarse.xi_west[i_coarse_west], mesh_coarse.xi_east[i_coarse_east]);
+        let mode = match &mesh_coarse.mode {
+            MeshMode::Mapped { geometry, .. } => {
+                let mut faces = Vec::with_capacity(n_comp + 1);
+                for k in i_coarse_west..=i_coarse_east {
+                    for l in 0..ratio {
+                        faces.push(mesh_coarse.xi_west[k] + l as f64 * mesh_coarse.cell_width[k] / ratio as f64);
+                    }
+                }
+                faces.push(xi_out);
+                MeshMode::Mapped {
+                    geometry: *geometry,
+                    mapping: MeshMapping::Faces(faces),
+                }
+            },
+            mode => mode.clone(),
+        };
+        let mut patch_config = config.clone();
+        patch_config.mesh_config = MeshConfig {
+            mode,
+            xi_in,
+            xi_out,
+            n_gc: mesh_coarse.n_gc,
+            n_all: Some(n_all),
+        };
+        let mesh = Mesh::<SF>::new(&patch_config.mesh_config).context("Constructing the Mesh of a Patch")?;
+        let mut solver = Solver::<P, N, T, E, SF>::new(&patch_config, &mesh).context("Constructing Patch::solver")?;
+        solver.rhs.boundary_west = Box::new(CoarseFineBoundary);
+        solver.rhs.boundary_east = Box::new(CoarseFineBoundary);
+        solver.timestep.iter_max = solver.timestep.iter_max.saturating_mul(ratio);
+
+        let mut ghost_weights = vec![];
+        for i in (mesh.imin..mesh.ixi_in).chain(mesh.ixi_out + 1..=mesh.imax) {
+            let xi = mesh.xi_cent[i];
+            let k = (mesh_coarse.imin..mesh_coarse.imax)
+                .find(|&k| mesh_coarse.xi_cent[k] <= xi && xi < mesh_coarse.xi_cent[k + 1]);
+            match k {
+                Some(k) => ghost_weights.push((
+                    i,
+                    k,
+                    (xi - mesh_coarse.xi_cent[k]) / (mesh_coarse.xi_cent[k + 1] - mesh_coarse.xi_cent[k]),
+                )),
+                None => bail!(
+                    "Could not interpolate the ghost cell at xi = {} from the coarse mesh! Try using fewer ghost \
+                     cells or moving the patch further away from the edges of the coarse mesh.",
+                    xi
+                ),
+            }
+        }
+
+        let mut patch = Self {
+            u: State::with_size(&config.physics_config, n_all),
+            solver,
+            mesh,
+            i_coarse_west,
+            i_coarse_east,
+            ratio,
+            ghost_weights,
+            flux_west: Array1::zeros(E),
+            flux_east: Array1::zeros(E),
+        };
+        // both only depend on the mesh and are needed to convert the prolongated cells; init_west_east keeps them
+        patch
+            .u
+            .apply_bathymetry(&patch.mesh)
+            .context("Applying the bathymetry to a Patch")?;
+        patch
+            .u
+            .apply_c_sound_profile(&patch.mesh)
+            .context("Applying the speed of sound profile to a Patch")?;
+        patch.prolongate(vars_coarse, mesh_coarse);
+        patch.fill_ghost_cells(&vars_coarse.cons, &vars_coarse.cons, &vars_coarse.c_sound, 0.0)?;
+        patch.u.init_west_east();
+        Ok(patch)
+    }
+
+    /// Fills the fine cells from the coarse cells they cover, using a linear reconstruction with
+    /// minmod limited slopes. The offsets are taken relative to the volume weighted centre of each
+    /// coarse cell, so that the fine cells add up to the coarse cell they are split from. Unless
+    /// it is set by a speed of sound profile, isothermal systems also copy the speed of sound of
+    /// the coarse cells.
+    fn prolongate<const S: usize>(&mut self, vars_coarse: &Variables<E, S>, mesh_coarse: &Mesh<S>) {
+        let (cons_coarse, xc) = (&vars_coarse.cons, &mesh_coarse.xi_cent);
+        let inherits_c_sound = self.inherits_c_sound();
+        for k in self.i_coarse_west..=self.i_coarse_east {
+            let i0 = self.mesh.ixi_in + (k - self.i_coarse_west) * self.ratio;
+            let fine = i0..i0 + self.ratio;
+            let volume: f64 = fine.clone().map(|i| self.mesh.volume[i]).sum();
+            let centre = fine
+                .clone()
+                .map(|i| self.mesh.volume[i] * self.mesh.xi_cent[i])
+                .sum::<f64>()
+                / volume;
+            for j in 0..E {
+                let slope = minmod(
+                    (cons_coarse[[j, k]] - cons_coarse[[j, k - 1]]) / (xc[k] - xc[k - 1]),
+                    (cons_coarse[[j, k + 1]] - cons_coarse[[j, k]]) / (xc[k + 1] - xc[k]),
+                );
+                for i in fine.clone() {
+                    self.u.cent.cons[[j, i]] = cons_coarse[[j, k]] + slope * (self.mesh.xi_cent[i] - centre);
+                }
+            }
+            if inherits_c_sound {
+                for i in fine {
+                    self.u.cent.c_sound[i] = vars_coarse.c_sound[k];
+                }
+            }
+        }
+    }
+
+    /// Whether the speed of sound is carried over from the coarse mesh, which is the case for
+    /// isothermal systems whose speed of sound is not set by a
+    /// [SoundSpeedProfile](crate::SoundSpeedProfile), but by their initial conditions.
+    fn inherits_c_sound(&self) -> bool {
+        !P::IS_ADIABATIC && self.u.cent.c_sound_profile == SoundSpeedProfile::Global
+    }
+
+    /// Copies the fine cells of `other` into `self`, wherever both patches cover the same coarse
+    /// cells.
+    fn copy_overlap(&mut self, other: &Self) {
+        let k_start = self.i_coarse_west.max(other.i_coarse_west);
+        let k_end = self.i_coarse_east.min(other.i_coarse_east);
+        if k_start > k_end {
+            return;
+        }
+        let i_self = self.mesh.ixi_in + (k_start - self.i_coarse_west) * self.ratio;
+        let i_other = other.mesh.ixi_in + (k_start - other.i_coarse_west) * other.ratio;
+        for l in 0..(k_end - k_start + 1) * self.ratio {
+            for j in 0..E {
+                self.u.cent.cons[[j, i_self + l]] = other.u.cent.cons[[j, i_other + l]];
+            }
+        }
+    }
+
+    /// Fills the ghost cells by linearly interpolating in space between coarse cell centres, and
+    /// in time between the coarse conservative variables `cons_old` and `cons_new`, with `theta`
+    /// being the relative position between them in time. The speed of sound `c_sound_coarse` of
+    /// the coarse mesh is interpolated in space, in case the patch carries it over, see
+    /// [Patch::prolongate]. Afterwards, updates the rest of the variables from the conservative
+    /// ones.
+    fn fill_ghost_cells(
+        &mut self,
+        cons_old: &Array2<f64>,
+        cons_new: &Array2<f64>,
+        c_sound_coarse: &Array1<f64>,
+        theta: f64,
+    ) -> Result<()> {
+        let inherits_c_sound = self.inherits_c_sound();
+        for &(i, k, w) in self.ghost_weights.iter() {
+            for j in 0..E {
+                let c0 = (1.0 - theta) * cons_old[[j, k]] + theta * cons_new[[j, k]];
+                let c1 = (1.0 - theta) * cons_old[[j, k + 1]] + theta * cons_new[[j, k + 1]];
+                self.u.cent.cons[[j, i]] = (1.0 - w) * c0 + w * c1;
+            }
+            if inherits_c_sound {
+                self.u.cent.c_sound[i] = (1.0 - w) * c_sound_coarse[k] + w * c_sound_coarse[k + 1];
+            }
+        }
+        self.u
+            .update_vars_from_cons(
+                &mut self.solver.rhs.boundary_west,
+                &mut self.solver.rhs.boundary_east,
+                &self.mesh,
+            )
+            .context("Updating the variables of a Patch after filling its ghost cells")
+    }
+
+    /// Sub-cycles the patch from its current time to `t_new`, using as many steps as its own time
+    /// step control requires, while recording the flux through its edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `cons_old` - Coarse conservative variables at the start of the coarse step
+    /// * `cons_new` - Coarse conservative variables at the end of the coarse step
+    /// * `c_sound_coarse` - Speed of sound on the coarse mesh
+    /// * `t_old` - Time at the start of the coarse step
+    /// * `t_new` - Time at the end of the coarse step
+    fn advance(
+        &mut self,
+        cons_old: &Array2<f64>,
+        cons_new: &Array2<f64>,
+        c_sound_coarse: &Array1<f64>,
+        t_old: f64,
+        t_new: f64,
+    ) -> Result<()> {
+        self.flux_west.fill(0.0);
+        self.flux_east.fill(0.0);
+        self.solver.timestep.t_next_output = t_new;
+        while self.solver.timestep.t + self.solver.timestep.dt_min * 0.01 < t_new {
+            let theta = (self.solver.timestep.t - t_old) / (t_new - t_old);
+            self.fill_ghost_cells(cons_old, cons_new, c_sound_coarse, theta)?;
+            self.solver
+                .next_solution(&mut self.u, &mut self.mesh)
+                .context("Calling Patch::solver.next_solution in Patch::advance")?;
+            let flux = self.solver.integrated_flux();
+            self.flux_west += &flux.column(self.mesh.ixi_in - 1);
+            self.flux_east += &flux.column(self.mesh.ixi_out);
+        }
+        self.solver.timestep.t = t_new;
+        Ok(())
+    }
+
+    /// Overwrites the coarse cells covered by this patch with the volume weighted average of the
+    /// fine cells, and corrects the coarse cells next to the patch, so that they see the same flux
+    /// through the edges of the patch as the fine cells did.
+    ///
+    /// # Arguments
+    ///
+    /// * `cons_coarse` - Coarse conservative variables at the end of the coarse step
+    /// * `flux_coarse` - Coarse flux through the cell faces integrated over the coarse step
+    /// * `mesh_coarse` - The coarse mesh
+    fn correct_coarse<const S: usize>(
+        &self,
+        cons_coarse: &mut Array2<f64>,
+        flux_coarse: &Array2<f64>,
+        mesh_coarse: &Mesh<S>,
+    ) {
+        let (iw, ie) = (self.i_coarse_west, self.i_coarse_east);
+        for j in 0..E {
+            for k in iw..=ie {
+                let i0 = self.mesh.ixi_in + (k - iw) * self.ratio;
+                cons_coarse[[j, k]] = (i0..i0 + self.ratio)
+                    .map(|i| self.u.cent.cons[[j, i]] * self.mesh.volume[i])
+                    .sum::<f64>()
+                    / mesh_coarse.volume[k];
+            }
+            cons_coarse[[j, iw - 1]] -=
+                mesh_coarse.deta_dphi_d_volume[iw - 1] * (self.flux_west[j] - flux_coarse[[j, iw - 1]]);
+            cons_coarse[[j, ie + 1]] +=
+                mesh_coarse.deta_dphi_d_volume[ie + 1] * (self.flux_east[j] - flux_coarse[[j, ie]]);
+        }
+    }
+}
+
+/// Runs a simulation on a coarse [Mesh] with statically refined [Patch]es on top of it.
+///
+/// Each call to [StaticRefinement::next_solution] advances the coarse state by one step, and then
+/// sub-cycles each patch up to the new coarse time. The ghost cells of the patches are interpolated
+/// linearly in space and time from the coarse state. Afterwards, the coarse cells covered by a
+/// patch are replaced with the averages of its fine cells, and the coarse cells next to a patch are
+/// corrected with the flux the patch saw through its edges, so that the scheme stays conservative.
+///
+/// Since [Physics], [NumFlux], and [TimeSolver] types depend on the mesh size, the patches need
+/// their own types `PF`, `NF`, and `TF` for the patch size `SF`. All patches share the same size,
+/// i.e. they need to cover the same number of coarse cells.
+pub struct StaticRefinement<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize>
+where
+    P: Physics<E, S>,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+    /// The state on the coarse mesh
+    pub u: State<P, E, S>,
+
+    /// Advances the coarse state in time
+    pub solver: Solver<P, N, T, E, S>,
+
+    /// The coarse mesh
+    pub mesh: Mesh<S>,
+
+    /// Writes the coarse state to the outputs
+    pub writer: Writer,
+
+    /// The refined patches
+    pub patches: Vec<Patch<PF, NF, TF, E, SF>>,
+
+    /// Coarse conservative variables at the start of the current coarse step
+    cons_old: Array2<f64>,
+}
+
+unsafe impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize> Send
+    for StaticRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S>,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+}
+unsafe impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize> Sync
+    for StaticRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S>,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+}
+
+impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize>
+    StaticRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+    /// Sets up the coarse simulation through [CorriesConfig::init_corries], and builds the patches
+    /// on top of it, which start out with a conservative, linear reconstruction of the coarse cells
+    /// they cover.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Configuration for the coarse simulation
+    /// * `refinement_config` - Configures where and by how much the coarse mesh is refined
+    /// * `init_fn` - Sets the initial conditions on the coarse mesh
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use corries::{initfuncs::init_sod, prelude::*};
+    ///
+    /// const S: usize = 24;
+    /// const SF: usize = 4 * 4 + 4;
+    /// set_Physics_and_E!(Euler1DAdiabatic);
+    /// type N = Hll<E, S>;
+    /// type T = RungeKuttaFehlberg<P, E, S>;
+    /// type PF = Euler1DAdiabatic<SF>;
+    /// type NF = Hll<E, SF>;
+    /// type TF = RungeKuttaFehlberg<PF, E, SF>;
+    ///
+    /// let mut config = CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results", "refinement");
+    /// // only write to stdout, so that the example does not leave files behind
+    /// config.writer_config.retain(|output| output.stream_mode == StreamMode::Stdout);
+    ///
+    /// // refine the coarse cells between xi = 1.4 and xi = 1.6 by a factor of 4
+    /// let refinement_config = RefinementConfig {
+    ///     ratio: 4,
+    ///     patches: vec![(1.4, 1.6)],
+    /// };
+    ///
+    /// let refinement = StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(
+    ///     &config,
+    ///     &refinement_config,
+    ///     init_sod::<P, N, T, E, S>,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(refinement.patches.len(), 1);
+    /// assert_eq!(refinement.patches[0].mesh.n_comp, 16);
+    /// ```
+    pub fn new(
+        config: &CorriesConfig,
+        refinement_config: &RefinementConfig,
+        init_fn: InitFn<P, N, T, E, S>,
+    ) -> Result<Self> {
+        refinement_config.validate().context("Validating RefinementConfig")?;
+        Self::with_patches(config, refinement_config.ratio, &refinement_config.patches, init_fn)
+    }
+
+    /// Sets up the coarse simulation and builds the patches between the xi coordinates in
+    /// `patches`, which may be empty.
+    fn with_patches(
+        config: &CorriesConfig,
+        ratio: usize,
+        patches: &[(f64, f64)],
+        init_fn: InitFn<P, N, T, E, S>,
+    ) -> Result<Self> {
+        ensure!(
+            TypeId::of::<P::Unsized>() == TypeId::of::<PF::Unsized>(),
+            "The coarse mesh and the patches need to use the same physics! Got P = {} ; PF = {}",
+            P::name(),
+            PF::name()
+        );
+        ensure!(
+            config.physics_config.alpha_viscosity.is_none(),
+            "Refined meshes cannot correct the torques of the alpha viscosity at the edges of the patches!"
+        );
+        ensure!(
+            config.numerics_config.mesh_motion == MeshMotion::Eulerian,
+            "Refined meshes cannot move with the fluid! Got mesh_motion = {:?}",
+            config.numerics_config.mesh_motion
+        );
+        let (u, solver, mesh, writer) = config
+            .init_corries::<P, N, T, E, S>(init_fn)
+            .context("Calling init_corries in StaticRefinement::new")?;
+        let patches_built = patches
+            .iter()
+            .map(|&edges| Patch::new(config, edges, ratio, &u.cent, &mesh))
+            .collect::<Result<Vec<_>>>()
+            .context("Constructing the patches in StaticRefinement::new")?;
+        for w in patches_built.windows(2) {
+            ensure!(
+                w[0].i_coarse_east + 1 < w[1].i_coarse_west,
+                "Patches need to be separated by at least one coarse cell! Got patches = {:?}",
+                patches
+            );
+        }
+        Ok(Self {
+            cons_old: u.cent.cons.clone(),
+            u,
+            solver,
+            mesh,
+            writer,
+            patches: patches_built,
+        })
+    }
+
+    /// Advances the coarse state by one step, sub-cycles the patches up to the new coarse time, and
+    /// corrects the coarse state with the solutions on the patches.
+    pub fn next_solution(&mut self) -> Result<()> {
+        self.cons_old.assign(&self.u.cent.cons);
+        let t_old = self.solver.timestep.t;
+        self.solver
+            .next_solution(&mut self.u, &mut self.mesh)
+            .context("Calling solver.next_solution in StaticRefinement::next_solution")?;
+        let t_new = self.solver.timestep.t;
+
+        for patch in self.patches.iter_mut() {
+            patch
+                .advance(&self.cons_old, &self.u.cent.cons, &self.u.cent.c_sound, t_old, t_new)
+                .context("Calling Patch::advance in StaticRefinement::next_solution")?;
+            patch.correct_coarse(&mut self.u.cent.cons, self.solver.integrated_flux(), &self.mesh);
+        }
+        self.u
+            .update_vars_from_cons(
+                &mut self.solver.rhs.boundary_west,
+                &mut self.solver.rhs.boundary_east,
+                &self.mesh,
+            )
+            .context("Calling u.update_vars_from_cons in StaticRefinement::next_solution")
+    }
+
+    /// Runs the simulation like [Runner::run_corries], but calls `before_step` before each call
+    /// to [StaticRefinement::next_solution].
+    fn run_with<F: FnMut(&mut Self) -> Result<()>>(&mut self, mut before_step: F) -> Result<()> {
+        loop {
+            if self.solver.timestep.t >= self.solver.timestep.t_next_output - self.solver.timestep.dt_min {
+                self.solver.timestep.t_next_output += self.solver.timestep.dt_output;
+                self.writer
+                    .update_data(&self.u.cent, &self.solver.timestep, &self.mesh)
+                    .context("Calling writer.update_data_matrices in StaticRefinement::run_with")?;
+                self.writer
+                    .write_output()
+                    .context("Calling writer.write_output in StaticRefinement::run_with")?;
+            }
+
+            if self.solver.timestep.t + self.solver.timestep.dt_min * 0.01 >= self.solver.timestep.t_end {
+                break;
+            }
+
+            if let err @ Err(_) = before_step(self).and_then(|_| self.next_solution()) {
+                self.solver.timestep.dt_kind = DtKind::ErrorDump;
+                self.writer
+                    .update_data(&self.u.cent, &self.solver.timestep, &self.mesh)
+                    .context(
+                        "Calling writer.update_data_matrices during the error dump in StaticRefinement::run_with",
+                    )?;
+                self.writer
+                    .write_output()
+                    .context("Calling writer.write_output during the error dump in StaticRefinement::run_with")?;
+                return err;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize> Runner
+    for StaticRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+    fn run_corries(&mut self) -> Result<()> {
+        self.run_with(|_| Ok(()))
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [AdaptiveRefinement], which moves the [Patch]es of a [StaticRefinement] to wherever a
+//! [RefinementIndicator] flags the coarse mesh.
+
+use super::{Patch, StaticRefinement};
+use crate::{errorhandling::Validation, initfuncs::InitFn, prelude::*};
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
+};
+use ndarray::{Array1, ArrayView1};
+
+/// Runs a simulation on a coarse [Mesh] with [Patch]es that follow the features of the flow.
+///
+/// Every `regrid_interval` coarse steps, the [RefinementIndicator] is calculated on the coarse
+/// density, and cells where it exceeds the threshold are flagged for refinement. The flagged cells
+/// are then covered with patches, each of which covers `(SF - 2 * n_gc) / ratio` coarse cells.
+/// Patches that no longer cover flagged cells are removed, which coarsens the mesh again; since
+/// the coarse cells always carry the average of the patches on top of them, this does not lose
+/// any mass, momentum, or energy. New patches are filled with a conservative, linear reconstruction
+/// of the coarse cells, except where they overlap the previous patches, in which case the fine
+/// cells are copied over.
+///
+/// Between two regrids, the simulation runs just like a [StaticRefinement], which is available
+/// through the `levels` field.
+pub struct AdaptiveRefinement<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize>
+where
+    P: Physics<E, S>,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+    /// The coarse simulation together with its current patches
+    pub levels: StaticRefinement<P, N, T, PF, NF, TF, E, S, SF>,
+
+    /// Decides where to put the patches
+    regridder: Regridder<S>,
+}
+
+unsafe impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize> Send
+    for AdaptiveRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S>,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+}
+unsafe impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize> Sync
+    for AdaptiveRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S>,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+}
+
+impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize>
+    AdaptiveRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+    /// Sets up the coarse simulation through [CorriesConfig::init_corries], and places the first
+    /// set of patches according to the initial conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Configuration for the coarse simulation
+    /// * `adaptive_config` - Configures how the patches are placed
+    /// * `init_fn` - Sets the initial conditions on the coarse mesh
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use corries::{initfuncs::init_sod, prelude::*};
+    ///
+    /// const S: usize = 54;
+    /// const SF: usize = 4 * 10 + 4;
+    /// set_Physics_and_E!(Euler1DAdiabatic);
+    /// type N = Hll<E, S>;
+    /// type T = RungeKuttaFehlberg<P, E, S>;
+    /// type PF = Euler1DAdiabatic<SF>;
+    /// type NF = Hll<E, SF>;
+    /// type TF = RungeKuttaFehlberg<PF, E, SF>;
+    ///
+    /// let mut config = CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results", "amr");
+    /// // only write to stdout, so that the example does not leave files behind
+    /// config.writer_config.retain(|output| output.stream_mode == StreamMode::Stdout);
+    /// let adaptive_config = AdaptiveRefinementConfig {
+    ///     ratio: 4,
+    ///     indicator: RefinementIndicator::Lohner { filter: 0.01 },
+    ///     threshold: 0.5,
+    ///     buffer: 2,
+    ///     regrid_interval: 4,
+    ///     max_patches: 3,
+    /// };
+    ///
+    /// let amr = AdaptiveRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(
+    ///     &config,
+    ///     &adaptive_config,
+    ///     init_sod::<P, N, T, E, S>,
+    /// )
+    /// .unwrap();
+    ///
+    /// // the discontinuity in the middle of the mesh gets a patch
+    /// assert_eq!(amr.levels.patches.len(), 1);
+    /// ```
+    pub fn new(
+        config: &CorriesConfig,
+        adaptive_config: &AdaptiveRefinementConfig,
+        init_fn: InitFn<P, N, T, E, S>,
+    ) -> Result<Self> {
+        adaptive_config
+            .validate()
+            .context("Validating AdaptiveRefinementConfig")?;
+        let n_gc = config.mesh_config.n_gc;
+        ensure!(
+            SF > 2 * n_gc && (SF - 2 * n_gc).is_multiple_of(adaptive_config.ratio),
+            "The patch size SF minus its ghost cells has to be a multiple of ratio! Got SF = {} ; n_gc = {} ; ratio \
+             = {}",
+            SF,
+            n_gc,
+            adaptive_config.ratio
+        );
+        let levels = StaticRefinement::with_patches(config, adaptive_config.ratio, &[], init_fn)
+            .context("Calling StaticRefinement::with_patches in AdaptiveRefinement::new")?;
+        let patch_width = (SF - 2 * n_gc) / adaptive_config.ratio;
+        ensure!(
+            levels.mesh.n_comp >= patch_width + 2,
+            "The patches need to fit into the coarse mesh with one cell to spare on each side! Got n_comp = {} ; \
+             patch width = {}",
+            levels.mesh.n_comp,
+            patch_width
+        );
+        let indicator = Array1::zeros(levels.mesh.n_all);
+        let mut adaptive_refinement = Self {
+            levels,
+            regridder: Regridder {
+                adaptive_config: adaptive_config.clone(),
+                config: config.clone(),
+                patch_width,
+                indicator,
+            },
+        };
+        adaptive_refinement
+            .regridder
+            .regrid(&mut adaptive_refinement.levels)
+            .context("Calling Regridder::regrid in AdaptiveRefinement::new")?;
+        Ok(adaptive_refinement)
+    }
+
+    /// Returns the [RefinementIndicator] on the coarse mesh, as calculated during the last regrid
+    pub fn indicator(&self) -> &Array1<f64> {
+        &self.regridder.indicator
+    }
+
+    /// Regrids if `regrid_interval` coarse steps have passed since the last regrid, and then calls
+    /// [StaticRefinement::next_solution].
+    pub fn next_solution(&mut self) -> Result<()> {
+        self.regridder
+            .regrid(&mut self.levels)
+            .context("Calling Regridder::regrid in AdaptiveRefinement::next_solution")?;
+        self.levels.next_solution()
+    }
+}
+
+impl<P, N, T, PF, NF, TF, const E: usize, const S: usize, const SF: usize> Runner
+    for AdaptiveRefinement<P, N, T, PF, NF, TF, E, S, SF>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+    PF: Physics<E, SF>,
+    NF: NumFlux<E, SF>,
+    TF: TimeSolver<PF, E, SF>,
+{
+    fn run_corries(&mut self) -> Result<()> {
+        let regridder = &mut self.regridder;
+        self.levels.run_with(|levels| {
+            regridder
+                .regrid(levels)
+                .context("Calling Regridder::regrid in AdaptiveRefinement::run_corries")
+        })
+    }
+}
+
+/// Decides where an [AdaptiveRefinement] puts its patches, and moves them there
+struct Regridder<const S: usize> {
+    /// Configures how the patches are placed
+    adaptive_config: AdaptiveRefinementConfig,
+
+    /// Configuration for the coarse simulation, used to construct new patches
+    config: CorriesConfig,
+
+    /// The number of coarse cells covered by each patch
+    patch_width: usize,
+
+    /// The refinement indicator on the coarse mesh
+    indicator: Array1<f64>,
+}
+
+impl<const S: usize> Regridder<S> {
+    /// Replaces the patches of `levels` with ones covering the currently flagged cells, if
+    /// `regrid_interval` coarse steps have passed since the last regrid.
+    fn regrid<P, N, T, PF, NF, TF, const E: usize, const SF: usize>(
+        &mut self,
+        levels: &mut StaticRefinement<P, N, T, PF, NF, TF, E, S, SF>,
+    ) -> Result<()>
+    where
+        P: Physics<E, S>,
+        N: NumFlux<E, S>,
+        T: TimeSolver<P, E, S>,
+        PF: Physics<E, SF>,
+        NF: NumFlux<E, SF>,
+        TF: TimeSolver<PF, E, SF>,
+    {
+        if !levels
+            .solver
+            .timestep
+            .iter
+            .is_multiple_of(self.adaptive_config.regrid_interval)
+        {
+            return Ok(());
+        }
+        let mesh = &levels.mesh;
+        // systems without a mass density, like Burgers, are refined on their first variable
+        let j_indicator = if P::JRHO < usize::MAX { P::JRHO } else { 0 };
+        calc_indicator(
+            &mut self.indicator,
+            &levels.u.cent.prim.row(j_indicator),
+            self.adaptive_config.indicator,
+            mesh,
+        );
+        let mut flags = vec![false; mesh.n_all];
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            if self.indicator[i] > self.adaptive_config.threshold {
+                let buffer = self.adaptive_config.buffer;
+                for flag in flags
+                    .iter_mut()
+                    .take((i + buffer).min(mesh.ixi_out) + 1)
+                    .skip(i.saturating_sub(buffer).max(mesh.ixi_in))
+                {
+                    *flag = true;
+                }
+            }
+        }
+        let regions = place_patches(&flags, self.patch_width, self.adaptive_config.max_patches, mesh);
+
+        let mut old_patches = std::mem::take(&mut levels.patches);
+        for (iw, ie) in regions {
+            if let Some(pos) = old_patches.iter().position(|patch| patch.i_coarse_west == iw) {
+                levels.patches.push(old_patches.swap_remove(pos));
+                continue;
+            }
+            let mut patch = Patch::new(
+                &self.config,
+                (mesh.xi_west[iw], mesh.xi_east[ie]),
+                self.adaptive_config.ratio,
+                &levels.u.cent,
+                mesh,
+            )
+            .context("Calling Patch::new in Regridder::regrid")?;
+            patch.solver.timestep.t = levels.solver.timestep.t;
+            for old_patch in old_patches.iter() {
+                patch.copy_overlap(old_patch);
+            }
+            patch.fill_ghost_cells(&levels.u.cent.cons, &levels.u.cent.cons, &levels.u.cent.c_sound, 0.0)?;
+            patch.u.init_west_east();
+            levels.patches.push(patch);
+        }
+        Ok(())
+    }
+}
+
+/// Calculates the refinement `indicator` in the computational area from the density `rho`.
+fn calc_indicator<const S: usize>(
+    indicator: &mut Array1<f64>,
+    rho: &ArrayView1<f64>,
+    mode: RefinementIndicator,
+    mesh: &Mesh<S>,
+) {
+    indicator.fill(0.0);
+    for i in mesh.ixi_in..=mesh.ixi_out {
+        let (west, cent, east) = (rho[i - 1], rho[i], rho[i + 1]);
+        indicator[i] = match mode {
+            // the guard keeps vanishing values, e.g. of a velocity standing in for the density,
+            // from dividing by zero
+            RefinementIndicator::DensityGradient => (east - west).abs() / (2.0 * cent.abs() + f64::MIN_POSITIVE),
+            RefinementIndicator::Lohner { filter } => {
+                let denominator =
+                    (east - cent).abs() + (cent - west).abs() + filter * (east.abs() + 2.0 * cent.abs() + west.abs());
+                if denominator > 0.0 {
+                    (east - 2.0 * cent + west).abs() / denominator
+                } else {
+                    0.0
+                }
+            },
+        };
+    }
+}
+
+/// Places up to `max_patches` patches of `width` coarse cells each over the `flags`ged cells, and
+/// returns the indexes of their westernmost and easternmost coarse cells.
+///
+/// Each patch is centred on the run of flagged cells it starts with, while keeping one coarse cell
+/// between the patches themselves and between the patches and the edges of the computational area.
+fn place_patches<const S: usize>(
+    flags: &[bool],
+    width: usize,
+    max_patches: usize,
+    mesh: &Mesh<S>,
+) -> Vec<(usize, usize)> {
+    let last_start = mesh.ixi_out - width;
+    let mut next_free = mesh.ixi_in + 1;
+    let mut regions = vec![];
+    let mut k = mesh.ixi_in;
+    while k <= mesh.ixi_out && regions.len() < max_patches {
+        if !flags[k] {
+            k += 1;
+            continue;
+        }
+        let mut end = k;
+        while end < mesh.ixi_out && flags[end + 1] && end + 1 - k < width {
+            end += 1;
+        }
+        let start = k
+            .saturating_sub((width - (end - k + 1)) / 2)
+            .max(next_free)
+            .min(last_start);
+        if start < next_free {
+            break;
+        }
+        regions.push((start, start + width - 1));
+        next_free = start + width + 1;
+        k = (start + width).max(end + 1);
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const S: usize = 24;
+
+    fn mesh() -> Mesh<S> {
+        Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap()
+    }
+
+    #[test]
+    fn indicators_flag_steps_but_not_ramps() {
+        let mesh = mesh();
+        let mut indicator = Array1::zeros(S);
+        let ramp = Array1::from_shape_fn(S, |i| 1.0 + 0.1 * i as f64);
+        let step = Array1::from_shape_fn(S, |i| if i < S / 2 { 1.0 } else { 0.125 });
+        for mode in [
+            RefinementIndicator::DensityGradient,
+            RefinementIndicator::Lohner { filter: 0.01 },
+        ] {
+            calc_indicator(&mut indicator, &step.view(), mode, &mesh);
+            assert!(indicator[S / 2] > 0.5);
+            assert_eq!(indicator[mesh.ixi_in], 0.0);
+        }
+        calc_indicator(
+            &mut indicator,
+            &ramp.view(),
+            RefinementIndicator::Lohner { filter: 0.01 },
+            &mesh,
+        );
+        assert!(indicator.iter().all(|&x| x < 1.0e-12));
+    }
+
+    #[test]
+    fn indicators_stay_finite_for_vanishing_values() {
+        let mesh = mesh();
+        let mut indicator = Array1::zeros(S);
+        let zeros = Array1::zeros(S);
+        let step = Array1::from_shape_fn(S, |i| if i < S / 2 { 1.0 } else { 0.0 });
+        for mode in [
+            RefinementIndicator::DensityGradient,
+            RefinementIndicator::Lohner { filter: 0.01 },
+        ] {
+            calc_indicator(&mut indicator, &zeros.view(), mode, &mesh);
+            assert!(indicator.iter().all(|&x| x == 0.0));
+            calc_indicator(&mut indicator, &step.view(), mode, &mesh);
+            assert!(indicator.iter().all(|x| x.is_finite()));
+            assert!(indicator[S / 2] > 0.5);
+        }
+    }
+
+    #[test]
+    fn patches_are_centred_and_separated() {
+        let mesh = mesh();
+        let mut flags = vec![false; S];
+        flags[10] = true;
+        flags[11] = true;
+        assert_eq!(place_patches(&flags, 6, 3, &mesh), vec![(8, 13)]);
+
+        // close to the edge, the patch is shifted inwards
+        let mut flags = vec![false; S];
+        flags[mesh.ixi_in] = true;
+        assert_eq!(
+            place_patches(&flags, 6, 3, &mesh),
+            vec![(mesh.ixi_in + 1, mesh.ixi_in + 6)]
+        );
+
+        // a long run of flagged cells is split into several patches with a gap between them
+        let mut flags = vec![false; S];
+        flags.iter_mut().take(18).skip(5).for_each(|f| *f = true);
+        assert_eq!(place_patches(&flags, 6, 3, &mesh), vec![(5, 10), (12, 17)]);
+        assert_eq!(place_patches(&flags, 6, 1, &mesh), vec![(5, 10)]);
+    }
+}
@@ -6,6 +6,11 @@
 //! set of equations.
 
 pub use self::numflux::{hll::Hll, kt::Kt, NumFlux};
+pub use self::sources::{
+    AlphaViscositySource, BathymetrySource, DragSource, GeometricSource, GravitySource, RadiationSource,
+    ReactionSource, Source, Sources,
+};
+pub use self::transport::Transport;
 use crate::{
     boundaryconditions::{init_boundary_condition, BoundaryCondition},
     errorhandling::Validation,
@@ -18,6 +23,8 @@ use color_eyre::{
 use ndarray::Array2;
 
 pub mod numflux;
+pub mod sources;
+pub mod transport;
 
 /// Carries objects and methods for solving the right-hand side of a set of equations.
 pub struct Rhs<N: NumFlux<E, S>, const E: usize, const S: usize> {
@@ -27,6 +34,15 @@ pub struct Rhs<N: NumFlux<E, S>, const E: usize, const S: usize> {
     /// Calculates the numerical flux
     numflux: N,
 
+    /// Calculates the source terms
+    sources: Sources<E, S>,
+
+    /// Calculates the parabolic fluxes of the physical viscosity and the heat conduction, if any
+    transport: Option<Transport<E, S>>,
+
+    /// Sum of the numerical and the parabolic fluxes, only updated if there is a [Transport]
+    flux: Array2<f64>,
+
     /// Boundary condition operator for the west boundary
     pub boundary_west: Box<dyn BoundaryCondition<E, S>>,
 
@@ -70,8 +86,15 @@ impl<N: NumFlux<E, S>, const E: usize, const S: usize> Rhs<N, E, S> {
     /// ```
     pub fn new(config: &CorriesConfig, mesh: &Mesh<S>) -> Result<Self> {
         Ok(Rhs {
-            full_rhs: Array2::zeros((E, S)),
+            full_rhs: Array2::zeros((E, mesh.n_all)),
             numflux: N::new(&config.numerics_config.numflux_config, mesh)?,
+            sources: Sources::new(config, mesh)?,
+            transport: config
+                .physics_config
+                .transport
+                .as_ref()
+                .map(|transport| Transport::new(transport, &config.physics_config.units_mode, mesh)),
+            flux: Array2::zeros((E, mesh.n_all)),
             boundary_west: Box::new(init_boundary_condition::<E, S>(Direction::West, config)),
             boundary_east: Box::new(init_boundary_condition::<E, S>(Direction::East, config)),
         })
@@ -112,12 +135,97 @@ impl<N: NumFlux<E, S>, const E: usize, const S: usize> Rhs<N, E, S> {
     /// ```
     pub fn update<P: Physics<E, S>>(&mut self, u: &mut State<P, E, S>, mesh: &Mesh<S>) -> Result<()> {
         // this assumes that u.cons is up-to-date
-        u.update_vars_from_cons(&mut self.boundary_west, &mut self.boundary_east, mesh);
+        u.update_vars_from_cons(&mut self.boundary_west, &mut self.boundary_east, mesh)
+            .context("Calling u.update_vars_from_cons in Rhs::update")?;
         self.numflux
             .calc_dflux_dxi(&mut self.full_rhs, u, mesh)
             .context("Calling Rhs::numflux::calc_dflux_dxi in Rhs::update_dflux_dxi")?;
+        if let Some(transport) = &mut self.transport {
+            transport
+                .apply(&mut self.full_rhs, u, mesh)
+                .context("Calling Rhs::transport::apply in Rhs::update")?;
+            self.flux.assign(self.numflux.flux_num());
+            self.flux += transport.flux();
+        }
+        self.sources
+            .apply(&mut self.full_rhs, u, mesh)
+            .context("Calling Rhs::sources::apply in Rhs::update")?;
+        Ok(())
+    }
+
+    /// Returns the shortest time scale of the explicitly integrated source terms, together with the
+    /// [DtKind] it corresponds to, see [Sources::calc_dt].
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The current [Physics] state
+    /// * `mesh` - Information about spatial properties
+    pub fn calc_dt_sources<P: Physics<E, S>>(&self, u: &State<P, E, S>, mesh: &Mesh<S>) -> Option<(f64, DtKind)> {
+        self.sources.calc_dt(u, mesh)
+    }
+
+    /// Returns the time the physical viscosity and the heat conduction need to diffuse across a
+    /// cell, or `None` if there are none, see [Transport::calc_dt].
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The current [Physics] state
+    /// * `mesh` - Information about spatial properties
+    pub fn calc_dt_diffusion<P: Physics<E, S>>(&self, u: &State<P, E, S>, mesh: &Mesh<S>) -> Option<f64> {
+        self.transport.as_ref().and_then(|transport| transport.calc_dt(u, mesh))
+    }
+
+    /// Sets the time that time dependent source terms are evaluated at, see
+    /// [Sources::update_time].
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time
+    pub fn update_time(&mut self, t: f64) {
+        self.sources.update_time(t);
+    }
+
+    /// Applies the source terms that are split off from the time integration over the time step
+    /// width `dt`, see [Sources::apply_split], and updates `u` from its conservative variables if
+    /// that changed them.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The current [Physics] state
+    /// * `dt` - The time step width
+    /// * `mesh` - Information about spatial properties
+    pub fn apply_split<P: Physics<E, S>>(&mut self, u: &mut State<P, E, S>, dt: f64, mesh: &Mesh<S>) -> Result<()> {
+        if self
+            .sources
+            .apply_split(u, dt, mesh)
+            .context("Calling Sources::apply_split in Rhs::apply_split")?
+        {
+            u.update_vars_from_cons(&mut self.boundary_west, &mut self.boundary_east, mesh)
+                .context("Calling u.update_vars_from_cons in Rhs::apply_split")?;
+        }
         Ok(())
     }
+
+    /// Returns the flux through the eastern cell faces from the last call to [Rhs::update], i.e.
+    /// the numerical flux, see [NumFlux::flux_num], plus the parabolic flux of the [Transport], if
+    /// any.
+    pub fn flux(&self) -> &Array2<f64> {
+        if self.transport.is_some() {
+            &self.flux
+        } else {
+            self.numflux.flux_num()
+        }
+    }
+
+    /// Updates everything derived from the [Mesh] after its cell faces moved, see
+    /// [NumFlux::update_mesh].
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - the moved [Mesh]
+    pub fn update_mesh(&mut self, mesh: &Mesh<S>) {
+        self.numflux.update_mesh(mesh);
+    }
 }
 
 impl<N: NumFlux<E, S>, const E: usize, const S: usize> Validation for Rhs<N, E, S> {
@@ -83,6 +83,20 @@ pub trait NumFlux<const E: usize, const S: usize> {
         u: &mut State<P, E, S>,
         mesh: &Mesh<S>,
     ) -> Result<()>;
+
+    /// Returns the numerical flux from the last call to [NumFlux::calc_dflux_dxi].
+    ///
+    /// Column `i` holds the flux through the eastern face of cell `i`, already multiplied with
+    /// `mesh.d_area_xi_deta_dphi_east`. Only the faces between `mesh.ixi_in - 1` and
+    /// `mesh.ixi_out` are calculated.
+    fn flux_num(&self) -> &Array2<f64>;
+
+    /// Updates everything this object derived from the [Mesh], after the cell faces moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - the moved [Mesh]
+    fn update_mesh(&mut self, mesh: &Mesh<S>);
 }
 
 /// Generic function to calculate the derivative of the numerical flux along the xi direction.
@@ -33,14 +33,18 @@ use ndarray::{par_azip, s, Array1, Array2};
 /// Then for each cell index `i`
 ///
 /// ```text
-/// sl[i] = min(0.0, min(eigen_min[i], eigen_min[i+1]))
-/// sr[i] = max(0.0, max(eigen_max[i], eigen_max[i+1]))
+/// sl[i] = min(0.0, min(eigen_min[i], eigen_min[i+1]) - w[i])
+/// sr[i] = max(0.0, max(eigen_max[i], eigen_max[i+1]) - w[i])
 /// ```
 ///
+/// where `w` is `mesh.face_velocity_east`, i.e. the characteristics are taken relative to the
+/// moving cell faces. For meshes that do not move, `w` is 0.
+///
 /// # calculating the numerical flux
 ///
 /// Let
 ///
+/// * `dA`: area differential for the cell along the `xi` and `eta` coordinates
 /// * `uc`: conservative variables
 /// * `Fp`: physical flux
 /// * `Fn`: numerical flux
@@ -48,9 +52,9 @@ use ndarray::{par_azip, s, Array1, Array2};
 /// Then the numerical flux is calculated for each equation index `j` and each mesh cell `i` by:
 ///
 /// ```text
-/// Fn[[j,i]] = (1 / (sr[i] - sl[i]))
-///           * (sr[i] * Fp[[j,i]]
-///              - sl[i] * Fp[[j,i+1]]
+/// Fn[[j,i]] = (dA / (sr[i] - sl[i]))
+///           * (sr[i] * (Fp[[j,i]] - w[i] * uc[[j,i]])
+///              - sl[i] * (Fp[[j,i+1]] - w[i] * uc[[j,i+1]])
 ///              + sr[i] * sl[i] * (uc[[j,i+1]] - uc[[j,i]]))
 /// ```
 ///
@@ -67,7 +71,7 @@ pub struct Hll<const E: usize, const S: usize> {
     /// Right-side characteristics
     sr: Array1<f64>,
 
-    /// Helper array containting `1.0 / (sr - sl)`
+    /// Helper array containting `dA / (sr - sl)`
     inv_sr_minus_sl: Array1<f64>,
 
     /// Helper array containting `sr * sl`
@@ -81,14 +85,14 @@ unsafe impl<const E: usize, const S: usize> Send for Hll<E, S> {}
 unsafe impl<const E: usize, const S: usize> Sync for Hll<E, S> {}
 
 impl<const E: usize, const S: usize> NumFlux<E, S> for Hll<E, S> {
-    fn new(numflux_config: &NumFluxConfig, _: &Mesh<S>) -> Result<Self> {
+    fn new(numflux_config: &NumFluxConfig, mesh: &Mesh<S>) -> Result<Self> {
         match numflux_config {
             NumFluxConfig::Hll => Ok(Self {
-                sl: Array1::zeros(S),
-                sr: Array1::zeros(S),
-                inv_sr_minus_sl: Array1::zeros(S),
-                sr_times_sl: Array1::zeros(S),
-                flux_num: Array2::zeros((E, S)),
+                sl: Array1::zeros(mesh.n_all),
+                sr: Array1::zeros(mesh.n_all),
+                inv_sr_minus_sl: Array1::zeros(mesh.n_all),
+                sr_times_sl: Array1::zeros(mesh.n_all),
+                flux_num: Array2::zeros((E, mesh.n_all)),
             }),
             _ => bail!("Tried constructing Hll, but numflux_config does not contain NumFluxConfig::Hll!"),
         }
@@ -114,15 +118,27 @@ impl<const E: usize, const S: usize> NumFlux<E, S> for Hll<E, S> {
         // parallel calls using rayon::join. The operations themselves, even on a 500 cell mesh,
         // were fast enough to outpace the overhead of the joins. Calling the parallel zip as 4
         // sequential calls was the clear benchmark winner on two seperate machines.
+        let w = &mesh.face_velocity_east;
         par_azip!((
-                sl in &mut self.sl.slice_mut(s), &ev1 in &eigen_min.slice(s), &ev2 in &eigen_min.slice(sp1))
-                *sl = 0.0f64.min(ev1.min(ev2)));
+                sl in &mut self.sl.slice_mut(s),
+                &ev1 in &eigen_min.slice(s),
+                &ev2 in &eigen_min.slice(sp1),
+                &w in &w.slice(s))
+                *sl = 0.0f64.min(ev1.min(ev2) - w));
         par_azip!((
-                sr in &mut self.sr.slice_mut(s), &ev1 in &eigen_max.slice(s), &ev2 in &eigen_max.slice(sp1))
-                *sr = 0.0f64.max(ev1.max(ev2)));
+                sr in &mut self.sr.slice_mut(s),
+                &ev1 in &eigen_max.slice(s),
+                &ev2 in &eigen_max.slice(sp1),
+                &w in &w.slice(s))
+                *sr = 0.0f64.max(ev1.max(ev2) - w));
         par_azip!((
-                a in &mut self.inv_sr_minus_sl.slice_mut(s), &sr in &self.sr.slice(s), &sl in &self.sl.slice(s))
-                *a = 1.0 / (sr - sl));
+                a in &mut self.inv_sr_minus_sl.slice_mut(s),
+                &y in &mesh.d_area_xi_deta_dphi_east.slice(s),
+                &sr in &self.sr.slice(s),
+                &sl in &self.sl.slice(s))
+                // faces without any waves, like between two dry cells in shallow water, do not
+                // carry any flux
+                *a = if sr > sl { y / (sr - sl) } else { 0.0 });
         par_azip!((
                 b in &mut self.sr_times_sl.slice_mut(s), &sr in &self.sr.slice(s), &sl in &self.sl.slice(s))
                 *b = sr * sl);
@@ -139,6 +155,17 @@ impl<const E: usize, const S: usize> NumFlux<E, S> for Hll<E, S> {
                         + &self.sr_times_sl.slice(s) * (&ucons.row(j).slice(sp1) - &ucons.row(j).slice(s)))),
             );
         }
+        if mesh.face_velocity_east.iter().any(|&w| w != 0.0) {
+            // the flux through moving faces is relative to the faces
+            for j in 0..E {
+                self.flux_num.row_mut(j).slice_mut(s).scaled_add(
+                    -1.0,
+                    &(&self.inv_sr_minus_sl.slice(s)
+                        * &w.slice(s)
+                        * (&self.sr.slice(s) * &ucons.row(j).slice(s) - &self.sl.slice(s) * &ucons.row(j).slice(sp1))),
+                );
+            }
+        }
 
         calc_dflux_xi_generic::<E, S>(dflux_dxi, &self.flux_num, mesh);
         if cfg!(feature = "validation") {
@@ -147,6 +174,12 @@ impl<const E: usize, const S: usize> NumFlux<E, S> for Hll<E, S> {
         }
         Ok(())
     }
+
+    fn flux_num(&self) -> &Array2<f64> {
+        &self.flux_num
+    }
+
+    fn update_mesh(&mut self, _: &Mesh<S>) {}
 }
 
 impl<const E: usize, const S: usize> Validation for Hll<E, S> {
@@ -169,11 +202,16 @@ mod tests {
         mode: MeshMode::Cartesian,
         xi_in: 2.0,
         xi_out: 3.0,
+        n_gc: 2,
+        n_all: None,
     };
-    const PHYSICSCONFIG: PhysicsConfig = PhysicsConfig {
-        units_mode: UnitsMode::SI,
-        adiabatic_index: 1.4,
-    };
+    fn physics_config() -> PhysicsConfig {
+        PhysicsConfig {
+            units_mode: UnitsMode::SI,
+            adiabatic_index: 1.4,
+            ..Default::default()
+        }
+    }
 
     fn init_noh<P: Physics<E, S>, const E: usize, const S: usize>(u: &mut State<P, E, S>) {
         let breakpoint_index = (S as f64 * 0.5) as usize;
@@ -202,7 +240,7 @@ mod tests {
     fn hll_test() {
         // Also test this on log meshes
         let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
-        let mut u = State::<P, E, S>::new(&PHYSICSCONFIG);
+        let mut u = State::<P, E, S>::new(&physics_config());
         init_noh(&mut u);
         u.update_cons();
         u.update_derived_variables();
@@ -220,4 +258,31 @@ mod tests {
         hll.calc_dflux_dxi(&mut dflux_dxi, &mut u, &mesh).unwrap();
         assert_relative_eq!(dflux_dxi, dflux_dxi_prim_expect, max_relative = 1.0e-12);
     }
+
+    #[test]
+    fn hll_spherical_test() {
+        // a fluid at rest only sees the pressure differences between the faces of the shells
+        let mesh: Mesh<S> = Mesh::new(&MeshConfig {
+            mode: MeshMode::Spherical,
+            ..MESHCONFIG
+        })
+        .unwrap();
+        let mut u = State::<P, E, S>::new(&physics_config());
+        u.cent.prim.row_mut(0).fill(2.0);
+        u.cent.c_sound.fill(1.5);
+        u.update_cons();
+        u.update_derived_variables();
+        let mut hll: Hll<E, S> = Hll::new(&NumFluxConfig::Hll, &mesh).unwrap();
+
+        let mut dflux_dxi = Array2::zeros((EQ, S));
+        hll.calc_dflux_dxi(&mut dflux_dxi, &mut u, &mesh).unwrap();
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_relative_eq!(dflux_dxi[[0, i]], 0.0);
+            assert_relative_eq!(
+                dflux_dxi[[1, i]],
+                2.0 * 1.5 * 1.5 * (mesh.area_east[i] - mesh.area_west[i]) / mesh.volume[i],
+                max_relative = 1.0e-12
+            );
+        }
+    }
 }
@@ -58,10 +58,13 @@ macro_rules! max {
 /// Then for each cell index `i`
 ///
 /// ```text
-/// aplus[i]  = max(0.0, max(eigen_max_west[i], eigen_max_east[i]))
-/// aminus[i] = min(0.0, min(eigen_min_west[i], eigen_min_east[i]))
+/// aplus[i]  = max(0.0, max(eigen_max_west[i+1], eigen_max_east[i]) - w[i])
+/// aminus[i] = min(0.0, min(eigen_min_west[i+1], eigen_min_east[i]) - w[i])
 /// ```
 ///
+/// where `w` is `mesh.face_velocity_east`, i.e. the characteristics are taken relative to the
+/// moving cell faces. For meshes that do not move, `w` is 0.
+///
 /// # calculating the numerical flux
 ///
 /// Let
@@ -77,8 +80,8 @@ macro_rules! max {
 ///
 /// ```text
 /// Fn[[j,i]] = (dA / (deta / dPhi * (aplus[i] - aminus[i])))
-///           * (aplus[i] * Fp_east[[j,i]]
-///              - aminus[i] * Fp_west[[j,i+1]]
+///           * (aplus[i] * (Fp_east[[j,i]] - w[i] * uc_east[[j,i]])
+///              - aminus[i] * (Fp_west[[j,i+1]] - w[i] * uc_west[[j,i+1]])
 ///              + aplus[i] * aminus[i] * (uc_west[[j,i+1]] - uc_east[[j,i]]))
 /// ```
 ///
@@ -108,8 +111,14 @@ pub struct Kt<const E: usize, const S: usize> {
     /// The type of limiter to use during reconstruction
     limiter_mode: LimiterMode,
 
-    /// inverse of the xi coordinate differential
-    inv_dxi: f64,
+    /// inverse distances between each cell centre and the centre of its western neighbour
+    inv_dist_cent_west: Array1<f64>,
+
+    /// inverse distances between each cell centre and the centre of its eastern neighbour
+    inv_dist_cent_east: Array1<f64>,
+
+    /// inverse distances between the centres of each cell's western and eastern neighbours
+    inv_dist_neighbours: Array1<f64>,
 
     /// monocent parameter, only used when limiter_mode == LimiterMode == Monocent; taken from that
     /// enums payload
@@ -127,22 +136,29 @@ unsafe impl<const E: usize, const S: usize> Sync for Kt<E, S> {}
 
 impl<const E: usize, const S: usize> NumFlux<E, S> for Kt<E, S> {
     fn new(numflux_config: &NumFluxConfig, mesh: &Mesh<S>) -> Result<Self> {
+        let n_all = mesh.n_all;
         match numflux_config {
-            NumFluxConfig::Kt { limiter_mode } => Ok(Self {
-                a_plus: Array1::zeros(S),
-                a_minus: Array1::zeros(S),
-                b: Array1::zeros(S),
-                c: Array1::zeros(S),
-                flux_num: Array2::zeros((E, S)),
-                limiter_mode: *limiter_mode,
-                inv_dxi: 1.0 / mesh.dxi,
-                theta: match limiter_mode {
-                    LimiterMode::Monocent(x) => *x,
-                    _ => 0.0,
-                },
-                dist_west: Array1::from_shape_fn(S, |i| mesh.xi_west[i] - mesh.xi_cent[i]),
-                dist_east: Array1::from_shape_fn(S, |i| mesh.xi_east[i] - mesh.xi_cent[i]),
-            }),
+            NumFluxConfig::Kt { limiter_mode } => {
+                let mut kt = Self {
+                    a_plus: Array1::zeros(n_all),
+                    a_minus: Array1::zeros(n_all),
+                    b: Array1::zeros(n_all),
+                    c: Array1::zeros(n_all),
+                    flux_num: Array2::zeros((E, n_all)),
+                    limiter_mode: *limiter_mode,
+                    inv_dist_cent_west: Array1::zeros(n_all),
+                    inv_dist_cent_east: Array1::zeros(n_all),
+                    inv_dist_neighbours: Array1::zeros(n_all),
+                    theta: match limiter_mode {
+                        LimiterMode::Monocent(x) => *x,
+                        _ => 0.0,
+                    },
+                    dist_west: Array1::zeros(n_all),
+                    dist_east: Array1::zeros(n_all),
+                };
+                kt.update_mesh(mesh);
+                Ok(kt)
+            },
             _ => bail!("Tried constructing Kt, but numflux_config does not contain NumFluxConfig::Kt!"),
         }
     }
@@ -156,8 +172,10 @@ impl<const E: usize, const S: usize> NumFlux<E, S> for Kt<E, S> {
         // NOTE: Assumes that u.cons is already up to date
         self.reconstruct(u);
 
-        u.update_prim_d::<{ Direction::West as u8 }>();
-        u.update_prim_d::<{ Direction::East as u8 }>();
+        u.update_prim_d::<{ Direction::West as u8 }>()
+            .context("Updating the primitive variables on the west cell faces in Kt::calc_dflux_dxi")?;
+        u.update_prim_d::<{ Direction::East as u8 }>()
+            .context("Updating the primitive variables on the east cell faces in Kt::calc_dflux_dxi")?;
         u.update_c_sound_d::<{ Direction::West as u8 }>();
         u.update_c_sound_d::<{ Direction::East as u8 }>();
         u.update_eigen_vals_min_d::<{ Direction::West as u8 }>();
@@ -180,23 +198,28 @@ impl<const E: usize, const S: usize> NumFlux<E, S> for Kt<E, S> {
         let s = s![(mesh.ixi_in - 1)..=mesh.ixi_out];
         let sp1 = s![mesh.ixi_in..=(mesh.ixi_out + 1)];
 
+        let w = &mesh.face_velocity_east;
         par_azip!((
                 a_plus in &mut self.a_plus.slice_mut(s),
                 &ev_max_west in &eigen_max_west.slice(sp1),
-                &ev_max_east in &eigen_max_east.slice(s))
-            *a_plus = max!(0.0, max!(ev_max_west, ev_max_east)));
+                &ev_max_east in &eigen_max_east.slice(s),
+                &w in &w.slice(s))
+            *a_plus = max!(0.0, max!(ev_max_west, ev_max_east) - w));
         par_azip!((
                 a_minus in &mut self.a_minus.slice_mut(s),
                 &ev_min_west in &eigen_min_west.slice(sp1),
-                &ev_min_east in &eigen_min_east.slice(s))
-            *a_minus = min!(0.0, min!(ev_min_west, ev_min_east)));
+                &ev_min_east in &eigen_min_east.slice(s),
+                &w in &w.slice(s))
+            *a_minus = min!(0.0, min!(ev_min_west, ev_min_east) - w));
 
         par_azip!((
                 b in &mut self.b.slice_mut(s),
                 &y in &mesh.d_area_xi_deta_dphi_east.slice(s),
                 &ap in &self.a_plus.slice(s),
                 &am in &self.a_minus.slice(s))
-            *b = y / (ap - am));
+            // faces without any waves, like between two dry cells in shallow water, do not carry
+            // any flux
+            *b = if ap > am { y / (ap - am) } else { 0.0 });
         par_azip!((
                 c in &mut self.c.slice_mut(s),
                 &ap in &self.a_plus.slice(s),
@@ -211,6 +234,18 @@ impl<const E: usize, const S: usize> NumFlux<E, S> for Kt<E, S> {
                         + &self.c.slice(s) * (&ucons_west.row(j).slice(sp1) - &ucons_east.row(j).slice(s)))),
             );
         }
+        if mesh.face_velocity_east.iter().any(|&w| w != 0.0) {
+            // the flux through moving faces is relative to the faces
+            for j in 0..E {
+                self.flux_num.row_mut(j).slice_mut(s).scaled_add(
+                    -1.0,
+                    &(&self.b.slice(s)
+                        * &w.slice(s)
+                        * (&self.a_plus.slice(s) * &ucons_east.row(j).slice(s)
+                            - &self.a_minus.slice(s) * &ucons_west.row(j).slice(sp1))),
+                );
+            }
+        }
 
         calc_dflux_xi_generic::<E, S>(dflux_dxi, &self.flux_num, mesh);
         if cfg!(feature = "validation") {
@@ -218,6 +253,37 @@ impl<const E: usize, const S: usize> NumFlux<E, S> for Kt<E, S> {
         }
         Ok(())
     }
+
+    fn flux_num(&self) -> &Array2<f64> {
+        &self.flux_num
+    }
+
+    fn update_mesh(&mut self, mesh: &Mesh<S>) {
+        let n_all = mesh.n_all;
+        self.inv_dist_cent_west = Array1::from_shape_fn(n_all, |i| {
+            1.0 / if i > 0 {
+                mesh.xi_cent[i] - mesh.xi_cent[i - 1]
+            } else {
+                mesh.cell_width[i]
+            }
+        });
+        self.inv_dist_cent_east = Array1::from_shape_fn(n_all, |i| {
+            1.0 / if i < n_all - 1 {
+                mesh.xi_cent[i + 1] - mesh.xi_cent[i]
+            } else {
+                mesh.cell_width[i]
+            }
+        });
+        self.inv_dist_neighbours = Array1::from_shape_fn(n_all, |i| {
+            if i > 0 && i < n_all - 1 {
+                1.0 / (mesh.xi_cent[i + 1] - mesh.xi_cent[i - 1])
+            } else {
+                0.5 / mesh.cell_width[i]
+            }
+        });
+        self.dist_west = Array1::from_shape_fn(n_all, |i| mesh.xi_west[i] - mesh.xi_cent[i]);
+        self.dist_east = Array1::from_shape_fn(n_all, |i| mesh.xi_east[i] - mesh.xi_cent[i]);
+    }
 }
 
 fn signum(a: f64) -> i32 {
@@ -260,14 +326,15 @@ impl<const E: usize, const S: usize> Kt<E, S> {
             },
         };
         for j in 0..E {
-            for i in 1..S - 1 {
-                let slope = self.inv_dxi
-                    * slope_fn(
-                        u.cent.cons[[j, i]] - u.cent.cons[[j, i - 1]],
-                        u.cent.cons[[j, i + 1]] - u.cent.cons[[j, i]],
-                        0.5 * (u.cent.cons[[j, i + 1]] - u.cent.cons[[j, i - 1]]),
-                        self.theta,
-                    );
+            for i in 1..u.cent.n_all() - 1 {
+                // the limiters are fed gradients, so that the slopes stay consistent on meshes with
+                // non-uniform cell widths
+                let slope = slope_fn(
+                    (u.cent.cons[[j, i]] - u.cent.cons[[j, i - 1]]) * self.inv_dist_cent_west[i],
+                    (u.cent.cons[[j, i + 1]] - u.cent.cons[[j, i]]) * self.inv_dist_cent_east[i],
+                    (u.cent.cons[[j, i + 1]] - u.cent.cons[[j, i - 1]]) * self.inv_dist_neighbours[i],
+                    self.theta,
+                );
                 u.west.cons[[j, i]] = u.cent.cons[[j, i]] + slope * self.dist_west[i];
                 u.east.cons[[j, i]] = u.cent.cons[[j, i]] + slope * self.dist_east[i];
             }
@@ -295,11 +362,16 @@ mod tests {
         mode: MeshMode::Cartesian,
         xi_in: 2.0,
         xi_out: 3.0,
+        n_gc: 2,
+        n_all: None,
     };
-    const PHYSICSCONFIG: PhysicsConfig = PhysicsConfig {
-        units_mode: UnitsMode::SI,
-        adiabatic_index: 1.4,
-    };
+    fn physics_config() -> PhysicsConfig {
+        PhysicsConfig {
+            units_mode: UnitsMode::SI,
+            adiabatic_index: 1.4,
+            ..Default::default()
+        }
+    }
 
     fn init_noh<P: Physics<E, S>, const E: usize, const S: usize>(u: &mut State<P, E, S>) {
         let breakpoint_index = (S as f64 * 0.5) as usize;
@@ -328,7 +400,7 @@ mod tests {
     fn kt_test() {
         // Also test this on log meshes
         let mesh: Mesh<S> = Mesh::new(&MESHCONFIG).unwrap();
-        let mut u = State::<P, E, S>::new(&PHYSICSCONFIG);
+        let mut u = State::<P, E, S>::new(&physics_config());
         init_noh(&mut u);
         u.update_cons();
         u.update_derived_variables();
@@ -0,0 +1,266 @@
+// Copyright (c) 2022-2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Source] trait that identifies structs that can calculate source terms, and the
+//! [Sources] struct that collects the source terms active in a simulation.
+
+use crate::{
+    errorhandling::Validation, mesh::Mesh, state::Physics, Bathymetry, CorriesConfig, DtKind, MeshGeometry, State,
+};
+use color_eyre::{eyre::Context, Result};
+use ndarray::Array2;
+
+pub mod alphaviscosity;
+pub mod bathymetry;
+pub mod drag;
+pub mod geometric;
+pub mod gravity;
+pub mod radiation;
+pub mod reactions;
+pub use self::alphaviscosity::AlphaViscositySource;
+pub use self::bathymetry::BathymetrySource;
+pub use self::drag::DragSource;
+pub use self::geometric::GeometricSource;
+pub use self::gravity::GravitySource;
+pub use self::radiation::RadiationSource;
+pub use self::reactions::ReactionSource;
+
+/// Trait for structs that can calculate source terms for the right-hand side
+pub trait Source<const E: usize, const S: usize> {
+    /// Applies the source terms to the right-hand side.
+    ///
+    /// Keep in mind that `full_rhs` carries the derivative of the numerical flux, which the time
+    /// integration subtracts from the conservative variables. This means that source terms need to
+    /// be subtracted from `full_rhs` as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `full_rhs` - the right-hand side the source terms are applied to
+    /// * `u` - current [State] of the simulation
+    /// * `mesh` - the [Mesh] this simulation runs on
+    fn apply<P: Physics<E, S>>(&mut self, full_rhs: &mut Array2<f64>, u: &State<P, E, S>, mesh: &Mesh<S>)
+        -> Result<()>;
+}
+
+/// Collects the [Source] objects active in a simulation.
+///
+/// Which of these are active is derived from the [CorriesConfig] passed to the constructor, for
+/// example the [GeometricSource] is only active for non-cartesian meshes, and the
+/// [BathymetrySource] only for bottoms that are not flat, and the [DragSource], the
+/// [ReactionSource], the [RadiationSource], the [AlphaViscositySource], and the [GravitySource]
+/// only if a drag, a reaction network, radiation, an alpha viscosity, or a potential is
+/// configured.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sources<const E: usize, const S: usize> {
+    /// Source terms arising from curvilinear meshes
+    geometric: Option<GeometricSource<E, S>>,
+
+    /// Source terms arising from the slope of the bottom in shallow water systems
+    bathymetry: Option<BathymetrySource<E, S>>,
+
+    /// Source terms arising from the drag between gas and dust
+    drag: Option<DragSource<E, S>>,
+
+    /// Reactions between the species of reacting flows
+    reactions: Option<ReactionSource<E, S>>,
+
+    /// Diffusion of radiation and its energy exchange with the gas
+    radiation: Option<RadiationSource<E, S>>,
+
+    /// Turbulent viscosity of accretion disks
+    alpha_viscosity: Option<AlphaViscositySource<E, S>>,
+
+    /// Source terms arising from an external gravitational potential
+    gravity: Option<GravitySource<E, S>>,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for Sources<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for Sources<E, S> {}
+
+impl<const E: usize, const S: usize> Sources<E, S> {
+    /// Constructs a new [Sources] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Configuration for the whole simulation
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn new(config: &CorriesConfig, mesh: &Mesh<S>) -> Result<Self> {
+        Ok(Self {
+            geometric: match config.mesh_config.mode.geometry() {
+                MeshGeometry::Cartesian => None,
+                MeshGeometry::Cylindrical | MeshGeometry::Spherical => Some(GeometricSource::new(mesh)),
+            },
+            bathymetry: match config.physics_config.systems.shallow_water.bathymetry {
+                Bathymetry::Flat => None,
+                Bathymetry::Function(_) => Some(BathymetrySource::new(mesh)),
+            },
+            drag: config
+                .physics_config
+                .systems
+                .drag
+                .as_ref()
+                .map(|drag| DragSource::new(drag, mesh)),
+            reactions: config
+                .physics_config
+                .systems
+                .network
+                .as_ref()
+                .map(|_| ReactionSource::new()),
+            radiation: config
+                .physics_config
+                .systems
+                .radiation
+                .as_ref()
+                .map(|radiation| RadiationSource::new(radiation, &config.physics_config.units_mode)),
+            alpha_viscosity: config
+                .physics_config
+                .alpha_viscosity
+                .as_ref()
+                .map(|alpha_viscosity| {
+                    AlphaViscositySource::new(
+                        alpha_viscosity,
+                        config.physics_config.potential.as_ref(),
+                        &config.physics_config.units_mode,
+                        mesh,
+                    )
+                })
+                .transpose()
+                .context("Constructing AlphaViscositySource")?,
+            gravity: config
+                .physics_config
+                .potential
+                .as_ref()
+                .map(|potential| GravitySource::new(potential, &config.physics_config.units_mode, mesh)),
+        })
+    }
+
+    /// Sets the time that time dependent source terms are evaluated at, see
+    /// [GravitySource::update_time].
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - the time
+    pub fn update_time(&mut self, t: f64) {
+        if let Some(gravity) = &mut self.gravity {
+            gravity.update_time(t);
+        }
+    }
+
+    /// Returns the shortest time scale of the source terms that are integrated explicitly, together
+    /// with the [DtKind] it corresponds to, or `None` if none of them limit the time step width.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn calc_dt<P: Physics<E, S>>(&self, u: &State<P, E, S>, mesh: &Mesh<S>) -> Option<(f64, DtKind)> {
+        let dt_drag = self
+            .drag
+            .as_ref()
+            .and_then(|drag| drag.calc_dt(u, mesh))
+            .map(|dt| (dt, DtKind::Drag));
+        let dt_alpha_viscosity = self
+            .alpha_viscosity
+            .as_ref()
+            .and_then(|alpha_viscosity| alpha_viscosity.calc_dt(u, mesh))
+            .map(|dt| (dt, DtKind::Diffusion));
+        let dt_gravity = self
+            .gravity
+            .as_ref()
+            .and_then(|gravity| gravity.calc_dt(mesh))
+            .map(|dt| (dt, DtKind::Gravity));
+        [dt_drag, dt_alpha_viscosity, dt_gravity]
+            .into_iter()
+            .flatten()
+            .min_by(|(dt_a, _), (dt_b, _)| dt_a.total_cmp(dt_b))
+    }
+
+    /// Applies the source terms that are split off from the time integration over the time step
+    /// width `dt` to the conservative variables in `u`.
+    ///
+    /// Returns whether `u.cent.cons` was changed, in which case the rest of `u` needs to be updated
+    /// from the conservative variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `dt` - the time step width
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn apply_split<P: Physics<E, S>>(&self, u: &mut State<P, E, S>, dt: f64, mesh: &Mesh<S>) -> Result<bool> {
+        let mut changed = self.drag.as_ref().is_some_and(|drag| drag.apply_split(u, dt, mesh));
+        if let Some(reactions) = &self.reactions {
+            changed |= reactions
+                .apply_split(u, dt, mesh)
+                .context("Calling ReactionSource::apply_split in Sources::apply_split")?;
+        }
+        if let Some(radiation) = &self.radiation {
+            changed |= radiation
+                .apply_split(u, dt, mesh)
+                .context("Calling RadiationSource::apply_split in Sources::apply_split")?;
+        }
+        Ok(changed)
+    }
+}
+
+impl<const E: usize, const S: usize> Source<E, S> for Sources<E, S> {
+    fn apply<P: Physics<E, S>>(
+        &mut self,
+        full_rhs: &mut Array2<f64>,
+        u: &State<P, E, S>,
+        mesh: &Mesh<S>,
+    ) -> Result<()> {
+        if let Some(geometric) = &mut self.geometric {
+            geometric
+                .apply(full_rhs, u, mesh)
+                .context("Calling GeometricSource::apply in Sources::apply")?;
+        }
+        if let Some(bathymetry) = &mut self.bathymetry {
+            bathymetry
+                .apply(full_rhs, u, mesh)
+                .context("Calling BathymetrySource::apply in Sources::apply")?;
+        }
+        if let Some(drag) = &mut self.drag {
+            drag.apply(full_rhs, u, mesh)
+                .context("Calling DragSource::apply in Sources::apply")?;
+        }
+        if let Some(alpha_viscosity) = &mut self.alpha_viscosity {
+            alpha_viscosity
+                .apply(full_rhs, u, mesh)
+                .context("Calling AlphaViscositySource::apply in Sources::apply")?;
+        }
+        if let Some(gravity) = &mut self.gravity {
+            gravity
+                .apply(full_rhs, u, mesh)
+                .context("Calling GravitySource::apply in Sources::apply")?;
+        }
+        Ok(())
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for Sources<E, S> {
+    fn validate(&self) -> Result<()> {
+        if let Some(geometric) = &self.geometric {
+            geometric.validate()?;
+        }
+        if let Some(bathymetry) = &self.bathymetry {
+            bathymetry.validate()?;
+        }
+        if let Some(drag) = &self.drag {
+            drag.validate()?;
+        }
+        if let Some(reactions) = &self.reactions {
+            reactions.validate()?;
+        }
+        if let Some(radiation) = &self.radiation {
+            radiation.validate()?;
+        }
+        if let Some(alpha_viscosity) = &self.alpha_viscosity {
+            alpha_viscosity.validate()?;
+        }
+        if let Some(gravity) = &self.gravity {
+            gravity.validate()?;
+        }
+        Ok(())
+    }
+}
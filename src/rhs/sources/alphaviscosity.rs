@@ -0,0 +1,262 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [AlphaViscositySource] struct.
+
+use super::Source;
+use crate::{
+    errorhandling::Validation, mesh::Mesh, state::Physics, AlphaViscosityConfig, Potential, State, Units, UnitsMode,
+};
+use color_eyre::{
+    eyre::{bail, ensure},
+    Result,
+};
+use ndarray::{s, Array1, Array2};
+
+/// Calculates the Shakura-Sunyaev alpha viscosity of an accretion disk on a cylindrical mesh, where
+/// xi is the radius `r` and eta the azimuth.
+///
+/// Let
+///
+/// * `rho`: mass density
+/// * `c_s`: speed of sound
+/// * `v_eta`: eta velocity, and `Omega = v_eta / r` the angular velocity
+/// * `Omega_K = sqrt(G * M / (r^2 + eps^2)^1.5)`: the Kepler frequency around the central mass
+///   `M` with the softening length `eps`, both taken from [Potential::PointMass]
+///
+/// Then the kinematic viscosity is `nu = alpha * c_s * H` with the scale height `H = c_s / Omega_K`,
+/// evaluated at the cell centres, and the viscous stress through a cell face is
+/// `tau = rho * nu * r * dOmega / dr`. The source terms are:
+///
+/// ```text
+/// S_eta    = 1 / r^2 * d(r^2 * tau) / dr
+/// S_energy = 1 / r * d(r * v_eta * tau) / dr
+/// ```
+///
+/// where the energy source only applies to adiabatic systems. The stress only depends on the shear
+/// of the angular velocity, so a rigidly rotating disk feels no viscosity, and since the source
+/// of the eta momentum is the divergence of the torque `r^2 * tau` through the cell faces, the
+/// angular momentum is conserved.
+///
+/// These terms are integrated explicitly, so they limit the time step width to the time the
+/// viscosity needs to cross a cell, see [AlphaViscositySource::calc_dt].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlphaViscositySource<const E: usize, const S: usize> {
+    /// The source terms per equation and cell
+    source: Array2<f64>,
+
+    /// The viscous stress through the eastern face of each cell
+    stress: Array1<f64>,
+
+    /// The dimensionless viscosity parameter
+    alpha: f64,
+
+    /// The gravitational constant times the central mass
+    gm: f64,
+
+    /// The softening length of the central mass
+    softening: f64,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for AlphaViscositySource<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for AlphaViscositySource<E, S> {}
+
+impl<const E: usize, const S: usize> AlphaViscositySource<E, S> {
+    /// Constructs a new [AlphaViscositySource] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha_viscosity_config` - Configuration for the alpha viscosity
+    /// * `potential` - The external potential, which needs to be a [Potential::PointMass] that
+    ///   sets the Kepler frequency
+    /// * `units_mode` - The unit system of the simulation, which sets the gravitational constant
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn new(
+        alpha_viscosity_config: &AlphaViscosityConfig,
+        potential: Option<&Potential>,
+        units_mode: &UnitsMode,
+        mesh: &Mesh<S>,
+    ) -> Result<Self> {
+        let Some(&Potential::PointMass { mass, softening }) = potential else {
+            bail!(
+                "The alpha viscosity takes the Kepler frequency from a Potential::PointMass! Got {:?}",
+                potential
+            );
+        };
+        Ok(Self {
+            source: Array2::zeros((E, mesh.n_all)),
+            stress: Array1::zeros(mesh.n_all),
+            alpha: alpha_viscosity_config.alpha,
+            gm: Units::new(units_mode.clone()).gravitational_constant() * mass,
+            softening,
+        })
+    }
+
+    /// Returns the kinematic viscosity in cell `i`
+    #[inline(always)]
+    fn nu<P: Physics<E, S>>(&self, u: &State<P, E, S>, mesh: &Mesh<S>, i: usize) -> f64 {
+        let distance_squared = mesh.xi_cent[i] * mesh.xi_cent[i] + self.softening * self.softening;
+        let omega_kepler = (self.gm / distance_squared.powf(1.5)).sqrt();
+        self.alpha * u.cent.c_sound[i] * u.cent.c_sound[i] / omega_kepler
+    }
+
+    /// Returns the time the viscosity needs to cross a cell, i.e. the minimum of
+    /// `dxi^2 / (2 * nu)`, or `None` if alpha is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn calc_dt<P: Physics<E, S>>(&self, u: &State<P, E, S>, mesh: &Mesh<S>) -> Option<f64> {
+        (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| mesh.cell_width[i] * mesh.cell_width[i] / (2.0 * self.nu(u, mesh, i)))
+            .filter(|dt| dt.is_finite())
+            .reduce(f64::min)
+    }
+}
+
+impl<const E: usize, const S: usize> Source<E, S> for AlphaViscositySource<E, S> {
+    fn apply<P: Physics<E, S>>(
+        &mut self,
+        full_rhs: &mut Array2<f64>,
+        u: &State<P, E, S>,
+        mesh: &Mesh<S>,
+    ) -> Result<()> {
+        let omega = |i: usize| u.cent.prim[[P::JETA, i]] / mesh.xi_cent[i];
+        for i in mesh.ixi_in - 1..=mesh.ixi_out {
+            let rho = 0.5 * (u.cent.prim[[P::JRHO, i]] + u.cent.prim[[P::JRHO, i + 1]]);
+            let nu = 0.5 * (self.nu(u, mesh, i) + self.nu(u, mesh, i + 1));
+            self.stress[i] =
+                rho * nu * mesh.xi_east[i] * (omega(i + 1) - omega(i)) / (mesh.xi_cent[i + 1] - mesh.xi_cent[i]);
+        }
+
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let (torque_west, torque_east) = (
+                mesh.d_area_xi_deta_dphi_west[i] * mesh.xi_west[i] * self.stress[i - 1],
+                mesh.d_area_xi_deta_dphi_east[i] * mesh.xi_east[i] * self.stress[i],
+            );
+            self.source[[P::JETA, i]] = mesh.xi_cent_inv[i] * mesh.deta_dphi_d_volume[i] * (torque_east - torque_west);
+            if P::IS_ADIABATIC {
+                let eta_vel_west = 0.5 * (u.cent.prim[[P::JETA, i - 1]] + u.cent.prim[[P::JETA, i]]);
+                let eta_vel_east = 0.5 * (u.cent.prim[[P::JETA, i]] + u.cent.prim[[P::JETA, i + 1]]);
+                self.source[[P::JPRESSURE, i]] = mesh.deta_dphi_d_volume[i]
+                    * (mesh.d_area_xi_deta_dphi_east[i] * eta_vel_east * self.stress[i]
+                        - mesh.d_area_xi_deta_dphi_west[i] * eta_vel_west * self.stress[i - 1]);
+            }
+        }
+
+        let s = s![.., mesh.ixi_in..=mesh.ixi_out];
+        let mut full_rhs_comp = full_rhs.slice_mut(s);
+        full_rhs_comp -= &self.source.slice(s);
+        if cfg!(feature = "validation") {
+            self.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for AlphaViscositySource<E, S> {
+    fn validate(&self) -> Result<()> {
+        check_finite_arrayd!(self.source);
+        check_finite_arrayd!(self.stress);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use approx::assert_relative_eq;
+    const S: usize = 104;
+    const E: usize = 3;
+    type P = Euler2DIsot<S>;
+
+    fn mesh() -> Mesh<S> {
+        let mut mesh_config = MeshConfig::default_riemann_test();
+        mesh_config.mode = MeshMode::Cylindrical;
+        mesh_config.xi_in = 1.0;
+        mesh_config.xi_out = 2.0;
+        Mesh::new(&mesh_config).unwrap()
+    }
+
+    /// Viscosity around a point mass with `G * M = 1`
+    fn source(alpha: f64) -> AlphaViscositySource<E, S> {
+        let mass = 1.0 / Units::new(UnitsMode::SI).gravitational_constant();
+        AlphaViscositySource::new(
+            &AlphaViscosityConfig { alpha },
+            Some(&Potential::PointMass { mass, softening: 0.0 }),
+            &UnitsMode::SI,
+            &mesh(),
+        )
+        .unwrap()
+    }
+
+    /// Disk with a unit density, and a speed of sound such that `nu` is the same everywhere
+    fn state(mesh: &Mesh<S>, nu: f64, alpha: f64, omega: impl Fn(f64) -> f64) -> State<P, E, S> {
+        let mut u = State::<P, E, S>::new(&PhysicsConfig::default());
+        u.cent.prim.row_mut(P::JRHO).fill(1.0);
+        u.cent.prim.row_mut(P::JXI).fill(0.0);
+        for i in 0..S {
+            u.cent.prim[[P::JETA, i]] = mesh.xi_cent[i] * omega(mesh.xi_cent[i]);
+            u.cent.c_sound[i] = (nu * mesh.xi_cent[i].powf(-1.5) / alpha).sqrt();
+        }
+        u.update_cons();
+        u
+    }
+
+    #[test]
+    fn rigid_rotation_feels_no_viscosity() {
+        let mesh = mesh();
+        let u = state(&mesh, 0.01, 0.1, |_| 0.7);
+        let mut full_rhs = Array2::zeros((E, S));
+        source(0.1).apply(&mut full_rhs, &u, &mesh).unwrap();
+        assert!(full_rhs.iter().all(|x| x.abs() < 1.0e-12));
+    }
+
+    #[test]
+    fn keplerian_shear_matches_analytic_torque() {
+        // with nu * rho = const, 1 / r^2 * d(r^2 * tau) / dr = -0.75 * nu * rho * r^-2.5
+        let (nu, alpha) = (0.01, 0.1);
+        let mesh = mesh();
+        let u = state(&mesh, nu, alpha, |r| r.powf(-1.5));
+        let mut full_rhs = Array2::zeros((E, S));
+        source(alpha).apply(&mut full_rhs, &u, &mesh).unwrap();
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_relative_eq!(
+                full_rhs[[P::JETA, i]],
+                0.75 * nu * mesh.xi_cent[i].powf(-2.5),
+                max_relative = 1.0e-3
+            );
+        }
+        assert!(full_rhs.row(P::JRHO).iter().all(|&x| x == 0.0));
+        assert!(full_rhs.row(P::JXI).iter().all(|&x| x == 0.0));
+    }
+
+    #[test]
+    fn viscosity_limits_the_time_step() {
+        let (nu, alpha) = (0.01, 0.1);
+        let mesh = mesh();
+        let u = state(&mesh, nu, alpha, |r| r.powf(-1.5));
+        let dx = mesh.cell_width[mesh.ixi_in];
+        assert_relative_eq!(
+            source(alpha).calc_dt(&u, &mesh).unwrap(),
+            dx * dx / (2.0 * nu),
+            max_relative = 1.0e-12
+        );
+        assert!(source(0.0).calc_dt(&u, &mesh).is_none());
+    }
+
+    #[test]
+    fn kepler_frequency_needs_a_point_mass() {
+        let alpha_viscosity_config = AlphaViscosityConfig { alpha: 0.1 };
+        let uniform = Potential::Uniform { acceleration: 1.0 };
+        let mesh = mesh();
+        assert!(AlphaViscositySource::<E, S>::new(&alpha_viscosity_config, None, &UnitsMode::SI, &mesh).is_err());
+        assert!(
+            AlphaViscositySource::<E, S>::new(&alpha_viscosity_config, Some(&uniform), &UnitsMode::SI, &mesh).is_err()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [BathymetrySource] struct.
+
+use super::Source;
+use crate::{errorhandling::Validation, mesh::Mesh, state::Physics, State};
+use color_eyre::{eyre::ensure, Result};
+use ndarray::{s, Array2};
+
+/// Calculates the source term that the slope of the bottom exerts on the water in
+/// [ShallowWater](crate::state::ShallowWater) systems.
+///
+/// Let
+///
+/// * `h`: water depth
+/// * `b`: elevation of the bottom
+/// * `g`: gravitational acceleration
+/// * `b_{west,east}`: elevation of the bottom on the west and east cell faces
+/// * `deta_dphi_d_volume`: inverse cell volume of the [Mesh], including the line elements along
+///   eta and Phi
+///
+/// Then the source term for the momentum equation is:
+///
+/// ```text
+/// S_xi = -1.0 * g * (h + b) * (b_east - b_west) * deta_dphi_d_volume
+/// ```
+///
+/// This is the well-balanced counterpart to the pre-balanced physical flux of
+/// [ShallowWater](crate::state::ShallowWater), which already carries `-0.5 * g * b * b`. For a
+/// lake at rest, i.e. a flat surface `h + b` without any velocity, the difference of the
+/// numerical flux through the cell faces and this source term cancel out exactly, as long as the
+/// elevation on the cell faces is set through
+/// [State::apply_bathymetry](crate::state::State::apply_bathymetry). This only holds on cartesian
+/// meshes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BathymetrySource<const E: usize, const S: usize> {
+    /// The source terms per equation and cell
+    source: Array2<f64>,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for BathymetrySource<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for BathymetrySource<E, S> {}
+
+impl<const E: usize, const S: usize> BathymetrySource<E, S> {
+    /// Constructs a new [BathymetrySource] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn new(mesh: &Mesh<S>) -> Self {
+        Self {
+            source: Array2::zeros((E, mesh.n_all)),
+        }
+    }
+}
+
+impl<const E: usize, const S: usize> Source<E, S> for BathymetrySource<E, S> {
+    fn apply<P: Physics<E, S>>(
+        &mut self,
+        full_rhs: &mut Array2<f64>,
+        u: &State<P, E, S>,
+        mesh: &Mesh<S>,
+    ) -> Result<()> {
+        let depth = P::rho_prim(&u.cent);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            self.source[[P::JXI, i]] = -u.cent.systems.shallow_water.gravity
+                * (depth[i] + u.cent.bottom[i])
+                * (u.east.bottom[i] - u.west.bottom[i])
+                * mesh.deta_dphi_d_volume[i];
+        }
+
+        let s = s![.., mesh.ixi_in..=mesh.ixi_out];
+        let mut full_rhs_comp = full_rhs.slice_mut(s);
+        full_rhs_comp -= &self.source.slice(s);
+        if cfg!(feature = "validation") {
+            self.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for BathymetrySource<E, S> {
+    fn validate(&self) -> Result<()> {
+        check_finite_arrayd!(self.source);
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [DragSource] struct.
+
+use super::Source;
+use crate::{errorhandling::Validation, mesh::Mesh, state::Physics, DragConfig, DragUpdate, DustyGas, State};
+use color_eyre::{eyre::ensure, Result};
+use ndarray::{s, Array2};
+
+/// Calculates the drag between gas and dust in [DustyGas] systems.
+///
+/// Let
+///
+/// * `rho_g`, `rho_d`: mass densities of the gas and the dust
+/// * `v_g`, `v_d`: xi velocities of the gas and the dust
+/// * `t_s`: the stopping time
+///
+/// Then the drag force density on the dust is `F = rho_d * (v_g - v_d) / t_s`, and the source
+/// terms are:
+///
+/// ```text
+/// S_xi_gas     = -1.0 * F
+/// S_energy_gas = -1.0 * F * v_d
+/// S_xi_dust    = F
+/// ```
+///
+/// The gas energy loses the work the drag does on the dust, so that the total energy of both
+/// fluids is conserved, while the kinetic energy dissipated by the drag heats the gas.
+///
+/// How these are integrated in time depends on the [DragUpdate] in the [DragConfig]:
+///
+/// * with [DragUpdate::Explicit], the source terms are applied to the right-hand side in
+///   [Source::apply], and the time step width is limited to `t_s / (1 + rho_d / rho_g)`, i.e.
+///   the time scale the relative velocity decays on, see [DragSource::calc_dt].
+/// * with [DragUpdate::Implicit] and [DragUpdate::Exponential], the drag is applied after each
+///   time step in [DragSource::apply_split], without limiting the time step width. Both keep the
+///   centre of mass velocity of each cell, and let the relative velocity `dv = v_g - v_d` decay
+///   by a factor of `1 / (1 + (1 + eps) * dt / t_s)`, or `exp(-1.0 * (1 + eps) * dt / t_s)`
+///   respectively, with the dust to gas ratio `eps = rho_d / rho_g`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DragSource<const E: usize, const S: usize> {
+    /// The source terms per equation and cell
+    source: Array2<f64>,
+
+    /// The time scale on which the dust velocity approaches the gas velocity
+    stopping_time: f64,
+
+    /// How the drag is integrated in time
+    update: DragUpdate,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for DragSource<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for DragSource<E, S> {}
+
+impl<const E: usize, const S: usize> DragSource<E, S> {
+    /// Constructs a new [DragSource] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `drag_config` - Configuration for the drag
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn new(drag_config: &DragConfig, mesh: &Mesh<S>) -> Self {
+        Self {
+            source: Array2::zeros((E, mesh.n_all)),
+            stopping_time: drag_config.stopping_time,
+            update: drag_config.update,
+        }
+    }
+
+    /// Returns the time scale that limits the time step width of an explicitly integrated drag,
+    /// i.e. the shortest time the relative velocity between gas and dust decays on, or `None` if
+    /// the drag is applied after each time step.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn calc_dt<P: Physics<E, S>>(&self, u: &State<P, E, S>, mesh: &Mesh<S>) -> Option<f64> {
+        if self.update != DragUpdate::Explicit {
+            return None;
+        }
+        let dust_to_gas_max = (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.prim[[DustyGas::<S>::JRHO_DUST, i]] / u.cent.prim[[P::JRHO, i]])
+            .fold(0.0f64, f64::max);
+        Some(self.stopping_time / (1.0 + dust_to_gas_max))
+    }
+
+    /// Applies the drag over the time step width `dt` to the conservative variables in `u`, unless
+    /// the drag is integrated explicitly.
+    ///
+    /// Returns whether `u.cent.cons` was changed, in which case the rest of `u` needs to be updated
+    /// from the conservative variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `dt` - the time step width
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn apply_split<P: Physics<E, S>>(&self, u: &mut State<P, E, S>, dt: f64, mesh: &Mesh<S>) -> bool {
+        let j_rho_dust = DustyGas::<S>::JRHO_DUST;
+        let j_xi_dust = DustyGas::<S>::JXI_DUST;
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let (rho_gas, rho_dust) = (u.cent.cons[[P::JRHO, i]], u.cent.cons[[j_rho_dust, i]]);
+            if rho_dust <= 0.0 {
+                // nothing to couple to the gas
+                continue;
+            }
+            let (xi_mom_gas, xi_mom_dust) = (u.cent.cons[[P::JXI, i]], u.cent.cons[[j_xi_dust, i]]);
+            let decay = (1.0 + rho_dust / rho_gas) * dt / self.stopping_time;
+            let factor = match self.update {
+                DragUpdate::Explicit => return false,
+                DragUpdate::Implicit => 1.0 / (1.0 + decay),
+                DragUpdate::Exponential => (-decay).exp(),
+            };
+
+            let rho_total = rho_gas + rho_dust;
+            let xi_vel_centre = (xi_mom_gas + xi_mom_dust) / rho_total;
+            let xi_vel_diff = factor * (xi_mom_gas / rho_gas - xi_mom_dust / rho_dust);
+            let xi_vel_dust = xi_vel_centre - rho_gas / rho_total * xi_vel_diff;
+            u.cent.cons[[P::JXI, i]] = rho_gas * (xi_vel_centre + rho_dust / rho_total * xi_vel_diff);
+            u.cent.cons[[j_xi_dust, i]] = rho_dust * xi_vel_dust;
+
+            // the gas takes up the kinetic energy the dust loses
+            u.cent.cons[[P::JPRESSURE, i]] +=
+                0.5 * (xi_mom_dust * xi_mom_dust / rho_dust - rho_dust * xi_vel_dust * xi_vel_dust);
+        }
+        true
+    }
+}
+
+impl<const E: usize, const S: usize> Source<E, S> for DragSource<E, S> {
+    fn apply<P: Physics<E, S>>(
+        &mut self,
+        full_rhs: &mut Array2<f64>,
+        u: &State<P, E, S>,
+        mesh: &Mesh<S>,
+    ) -> Result<()> {
+        if self.update != DragUpdate::Explicit {
+            return Ok(());
+        }
+        let j_rho_dust = DustyGas::<S>::JRHO_DUST;
+        let j_xi_dust = DustyGas::<S>::JXI_DUST;
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let xi_vel_dust = u.cent.prim[[j_xi_dust, i]];
+            let force = u.cent.prim[[j_rho_dust, i]] * (u.cent.prim[[P::JXI, i]] - xi_vel_dust) / self.stopping_time;
+            self.source[[P::JXI, i]] = -force;
+            self.source[[P::JPRESSURE, i]] = -force * xi_vel_dust;
+            self.source[[j_xi_dust, i]] = force;
+        }
+
+        let s = s![.., mesh.ixi_in..=mesh.ixi_out];
+        let mut full_rhs_comp = full_rhs.slice_mut(s);
+        full_rhs_comp -= &self.source.slice(s);
+        if cfg!(feature = "validation") {
+            self.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for DragSource<E, S> {
+    fn validate(&self) -> Result<()> {
+        check_finite_arrayd!(self.source);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use approx::assert_relative_eq;
+    const S: usize = 8;
+    const E: usize = 5;
+    type P = DustyGas<S>;
+
+    fn mesh() -> Mesh<S> {
+        Mesh::new(&MeshConfig::default_riemann_test()).unwrap()
+    }
+
+    fn state() -> State<P, E, S> {
+        let mut u = State::<P, E, S>::new(&PhysicsConfig::default());
+        u.cent.prim.row_mut(P::JRHO).fill(1.0);
+        u.cent.prim.row_mut(P::JXI).fill(0.5);
+        u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+        u.cent.prim.row_mut(P::JRHO_DUST).fill(3.0);
+        u.cent.prim.row_mut(P::JXI_DUST).fill(-1.0);
+        u.update_cons();
+        u.update_derived_variables();
+        u
+    }
+
+    fn totals(u: &State<P, E, S>, i: usize) -> (f64, f64) {
+        let xi_mom_dust = u.cent.cons[[P::JXI_DUST, i]];
+        (
+            u.cent.cons[[P::JXI, i]] + xi_mom_dust,
+            u.cent.cons[[P::JPRESSURE, i]] + 0.5 * xi_mom_dust * xi_mom_dust / u.cent.cons[[P::JRHO_DUST, i]],
+        )
+    }
+
+    #[test]
+    fn explicit_drag_conserves_momentum_and_energy() {
+        let mesh = mesh();
+        let u = state();
+        let mut drag = DragSource::<E, S>::new(
+            &DragConfig {
+                stopping_time: 0.1,
+                update: DragUpdate::Explicit,
+            },
+            &mesh,
+        );
+        let mut full_rhs = Array2::zeros((E, S));
+        drag.apply(&mut full_rhs, &u, &mesh).unwrap();
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            // the dust is dragged along with the gas, i.e. its momentum grows; keep in mind that
+            // the time derivative of the conservative variables is -1.0 * full_rhs
+            assert_relative_eq!(full_rhs[[P::JXI_DUST, i]], -3.0 * 1.5 / 0.1, max_relative = 1.0e-12);
+            assert_relative_eq!(
+                full_rhs[[P::JXI, i]],
+                -full_rhs[[P::JXI_DUST, i]],
+                max_relative = 1.0e-12
+            );
+            assert_relative_eq!(
+                full_rhs[[P::JPRESSURE, i]],
+                full_rhs[[P::JXI_DUST, i]],
+                max_relative = 1.0e-12
+            );
+        }
+        assert_eq!(drag.calc_dt(&u, &mesh), Some(0.1 / 4.0));
+    }
+
+    #[test]
+    fn split_drag_relaxes_towards_the_centre_of_mass() {
+        let mesh = mesh();
+        for update in [DragUpdate::Implicit, DragUpdate::Exponential] {
+            let drag = DragSource::<E, S>::new(
+                &DragConfig {
+                    stopping_time: 0.1,
+                    update,
+                },
+                &mesh,
+            );
+            let mut u = state();
+            let (xi_mom_total, energy_total) = totals(&u, mesh.ixi_in);
+            assert!(drag.apply_split(&mut u, 1.0e3, &mesh));
+            assert_eq!(drag.calc_dt(&u, &mesh), None);
+            for i in mesh.ixi_in..=mesh.ixi_out {
+                // the stopping time is tiny compared to dt, so both fluids move together, up to
+                // the bit of relative velocity that the implicit update leaves, and the
+                // dissipated kinetic energy heats the gas
+                let xi_vel_centre = xi_mom_total / 4.0;
+                assert_relative_eq!(u.cent.cons[[P::JXI, i]], xi_vel_centre, max_relative = 1.0e-4);
+                assert_relative_eq!(
+                    u.cent.cons[[P::JXI_DUST, i]],
+                    3.0 * xi_vel_centre,
+                    max_relative = 1.0e-4
+                );
+                let (xi_mom, energy) = totals(&u, i);
+                assert_relative_eq!(xi_mom, xi_mom_total, max_relative = 1.0e-12);
+                assert_relative_eq!(energy, energy_total, max_relative = 1.0e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright (c) 2022-2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [GeometricSource] struct.
+
+use super::Source;
+use crate::{errorhandling::Validation, mesh::Mesh, state::Physics, State};
+use color_eyre::{eyre::ensure, Result};
+use ndarray::{s, Array2};
+
+/// Calculates the source terms that arise from the curvature of non-cartesian meshes.
+///
+/// The source terms depend on the system of equations, so they are provided by
+/// [Physics::calc_geometric_source]. Systems that do not provide them, see
+/// [Physics::HAS_GEOMETRIC_SOURCE], are restricted to cartesian meshes by
+/// [CorriesConfig::init_corries](crate::CorriesConfig::init_corries).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeometricSource<const E: usize, const S: usize> {
+    /// The source terms per equation and cell
+    source: Array2<f64>,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for GeometricSource<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for GeometricSource<E, S> {}
+
+impl<const E: usize, const S: usize> GeometricSource<E, S> {
+    /// Constructs a new [GeometricSource] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn new(mesh: &Mesh<S>) -> Self {
+        Self {
+            source: Array2::zeros((E, mesh.n_all)),
+        }
+    }
+}
+
+impl<const E: usize, const S: usize> Source<E, S> for GeometricSource<E, S> {
+    fn apply<P: Physics<E, S>>(
+        &mut self,
+        full_rhs: &mut Array2<f64>,
+        u: &State<P, E, S>,
+        mesh: &Mesh<S>,
+    ) -> Result<()> {
+        P::calc_geometric_source(&u.cent, mesh, &mut self.source);
+
+        let s = s![.., mesh.ixi_in..=mesh.ixi_out];
+        let mut full_rhs_comp = full_rhs.slice_mut(s);
+        full_rhs_comp -= &self.source.slice(s);
+        if cfg!(feature = "validation") {
+            self.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for GeometricSource<E, S> {
+    fn validate(&self) -> Result<()> {
+        check_finite_arrayd!(self.source);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use approx::{assert_abs_diff_eq, assert_relative_eq};
+    const S: usize = 12;
+    fn physics_config() -> PhysicsConfig {
+        PhysicsConfig {
+            units_mode: UnitsMode::SI,
+            adiabatic_index: 1.4,
+            ..Default::default()
+        }
+    }
+
+    fn rhs_at_rest<P: Physics<E, S>, N: NumFlux<E, S>, const E: usize>(
+        mode: &MeshMode,
+        numflux_config: &NumFluxConfig,
+        eta_vel: f64,
+    ) -> (Array2<f64>, Mesh<S>) {
+        let mesh: Mesh<S> = Mesh::new(&MeshConfig {
+            mode: mode.clone(),
+            xi_in: 1.0,
+            xi_out: 3.0,
+            n_gc: 2,
+            n_all: None,
+        })
+        .unwrap();
+        let mut u = State::<P, E, S>::new(&physics_config());
+        u.cent.prim.row_mut(P::JRHO).fill(2.0);
+        if P::JETA < usize::MAX {
+            u.cent.prim.row_mut(P::JETA).fill(eta_vel);
+        }
+        if P::IS_ADIABATIC {
+            u.cent.prim.row_mut(P::JPRESSURE).fill(3.0);
+        } else {
+            u.cent.c_sound.fill(1.5);
+        }
+        u.update_cons();
+        u.update_derived_variables();
+        u.init_west_east();
+
+        let mut numflux = N::new(numflux_config, &mesh).unwrap();
+        let mut geometric = GeometricSource::<E, S>::new(&mesh);
+        let mut full_rhs = Array2::zeros((E, S));
+        numflux.calc_dflux_dxi(&mut full_rhs, &mut u, &mesh).unwrap();
+        geometric.apply(&mut full_rhs, &u, &mesh).unwrap();
+        (full_rhs, mesh)
+    }
+
+    #[test]
+    fn fluid_at_rest_stays_at_rest() {
+        let kt_config = NumFluxConfig::Kt {
+            limiter_mode: LimiterMode::VanLeer,
+        };
+        for mode in [
+            MeshMode::Cylindrical,
+            MeshMode::LogCylindrical,
+            MeshMode::Spherical,
+            MeshMode::LogSpherical,
+            MeshMode::Mapped {
+                geometry: MeshGeometry::Cylindrical,
+                mapping: MeshMapping::Function(|s| s * s),
+            },
+        ] {
+            let (full_rhs, _) = rhs_at_rest::<Euler1DIsot<S>, Hll<2, S>, 2>(&mode, &NumFluxConfig::Hll, 0.0);
+            assert_abs_diff_eq!(full_rhs, Array2::zeros((2, S)), epsilon = 1.0e-12);
+            let (full_rhs, _) = rhs_at_rest::<Euler1DIsot<S>, Kt<2, S>, 2>(&mode, &kt_config, 0.0);
+            assert_abs_diff_eq!(full_rhs, Array2::zeros((2, S)), epsilon = 1.0e-12);
+            let (full_rhs, _) = rhs_at_rest::<Euler1DAdiabatic<S>, Hll<3, S>, 3>(&mode, &NumFluxConfig::Hll, 0.0);
+            assert_abs_diff_eq!(full_rhs, Array2::zeros((3, S)), epsilon = 1.0e-12);
+            let (full_rhs, _) = rhs_at_rest::<Euler1DAdiabatic<S>, Kt<3, S>, 3>(&mode, &kt_config, 0.0);
+            assert_abs_diff_eq!(full_rhs, Array2::zeros((3, S)), epsilon = 1.0e-12);
+            let (full_rhs, _) = rhs_at_rest::<Euler2DIsot<S>, Hll<3, S>, 3>(&mode, &NumFluxConfig::Hll, 0.0);
+            assert_abs_diff_eq!(full_rhs, Array2::zeros((3, S)), epsilon = 1.0e-12);
+            let (full_rhs, _) = rhs_at_rest::<Euler2DAdiabatic<S>, Kt<4, S>, 4>(&mode, &kt_config, 0.0);
+            assert_abs_diff_eq!(full_rhs, Array2::zeros((4, S)), epsilon = 1.0e-12);
+        }
+    }
+
+    #[test]
+    fn rotating_fluid_feels_the_centrifugal_force() {
+        // only the centrifugal force remains, pushing the fluid outwards; keep in mind that the
+        // time derivative of the conservative variables is -1.0 * full_rhs
+        let (full_rhs, mesh) =
+            rhs_at_rest::<Euler2DIsot<S>, Hll<3, S>, 3>(&MeshMode::Cylindrical, &NumFluxConfig::Hll, 0.5);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_abs_diff_eq!(full_rhs[[0, i]], 0.0, epsilon = 1.0e-12);
+            assert_relative_eq!(
+                full_rhs[[1, i]],
+                -2.0 * 0.5 * 0.5 * mesh.cexe[i],
+                max_relative = 1.0e-10
+            );
+            assert_abs_diff_eq!(full_rhs[[2, i]], 0.0, epsilon = 1.0e-12);
+        }
+    }
+}
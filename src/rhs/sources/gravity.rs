@@ -0,0 +1,240 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [GravitySource] struct.
+
+use super::Source;
+use crate::{errorhandling::Validation, mesh::Mesh, state::Physics, DustyGas, Potential, State, Units, UnitsMode};
+use color_eyre::{eyre::ensure, Result};
+use ndarray::{s, Array1, Array2};
+
+/// Calculates the source terms of an external gravitational potential `Phi(xi, t)`, see
+/// [Potential].
+///
+/// Let
+///
+/// * `rho`: mass density
+/// * `v_xi`: xi velocity
+/// * `g = -1.0 * dPhi / dxi`: the gravitational acceleration
+///
+/// Then the source terms are:
+///
+/// ```text
+/// S_xi     = rho * g
+/// S_energy = rho * v_xi * g
+/// ```
+///
+/// where the energy source only applies to adiabatic systems. Systems that carry dust, see
+/// [DustyGas], also get `S_xi_dust = rho_dust * g` for the dust momentum, while the pressureless dust
+/// has no energy equation of its own.
+///
+/// The acceleration in each cell is the difference of the potential on its west and east faces,
+/// divided by its width, i.e. `g = -1.0 * (Phi_east - Phi_west) / (xi_east - xi_west)`. This is
+/// exact for uniform fields, and for a point mass without softening it yields
+/// `g = -1.0 * G * M / (xi_west * xi_east)`. The potential is evaluated at the time set through
+/// [GravitySource::update_time], which the [TimeSolver](crate::time::TimeSolver) does for each of
+/// its stages.
+///
+/// These terms are integrated explicitly, and they limit the time step width to the time the gas
+/// needs to fall across a cell from rest, see [GravitySource::calc_dt].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GravitySource<const E: usize, const S: usize> {
+    /// The source terms per equation and cell
+    source: Array2<f64>,
+
+    /// The gravitational acceleration per cell
+    acceleration: Array1<f64>,
+
+    /// The external potential
+    potential: Potential,
+
+    /// The gravitational constant in the units of the simulation
+    gravitational_constant: f64,
+
+    /// The time the potential is evaluated at
+    t: f64,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for GravitySource<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for GravitySource<E, S> {}
+
+impl<const E: usize, const S: usize> GravitySource<E, S> {
+    /// Constructs a new [GravitySource] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `potential` - the external gravitational potential
+    /// * `units_mode` - The unit system of the simulation, which sets the gravitational constant
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn new(potential: &Potential, units_mode: &UnitsMode, mesh: &Mesh<S>) -> Self {
+        Self {
+            source: Array2::zeros((E, mesh.n_all)),
+            acceleration: Array1::zeros(mesh.n_all),
+            potential: potential.clone(),
+            gravitational_constant: Units::new(units_mode.clone()).gravitational_constant(),
+            t: 0.0,
+        }
+    }
+
+    /// Sets the time the potential is evaluated at.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - the time
+    pub fn update_time(&mut self, t: f64) {
+        self.t = t;
+    }
+
+    /// Returns the gravitational acceleration in cell `i`
+    #[inline(always)]
+    fn calc_acceleration(&self, mesh: &Mesh<S>, i: usize) -> f64 {
+        let phi_west = self.potential.phi(mesh.xi_west[i], self.t, self.gravitational_constant);
+        let phi_east = self.potential.phi(mesh.xi_east[i], self.t, self.gravitational_constant);
+        -(phi_east - phi_west) * mesh.cell_width_inv[i]
+    }
+
+    /// Returns the time the gas needs to fall across a cell, i.e. the minimum of
+    /// `sqrt(dxi / |g|)`, or `None` if the acceleration vanishes everywhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn calc_dt(&self, mesh: &Mesh<S>) -> Option<f64> {
+        (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| (mesh.cell_width[i] / self.calc_acceleration(mesh, i).abs()).sqrt())
+            .filter(|dt| dt.is_finite())
+            .reduce(f64::min)
+    }
+}
+
+impl<const E: usize, const S: usize> Source<E, S> for GravitySource<E, S> {
+    fn apply<P: Physics<E, S>>(
+        &mut self,
+        full_rhs: &mut Array2<f64>,
+        u: &State<P, E, S>,
+        mesh: &Mesh<S>,
+    ) -> Result<()> {
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            self.acceleration[i] = self.calc_acceleration(mesh, i);
+            let force = u.cent.prim[[P::JRHO, i]] * self.acceleration[i];
+            self.source[[P::JXI, i]] = force;
+            if P::IS_ADIABATIC {
+                self.source[[P::JPRESSURE, i]] = force * u.cent.prim[[P::JXI, i]];
+            }
+            if P::HAS_DUST {
+                self.source[[DustyGas::<S>::JXI_DUST, i]] =
+                    u.cent.prim[[DustyGas::<S>::JRHO_DUST, i]] * self.acceleration[i];
+            }
+        }
+
+        let s = s![.., mesh.ixi_in..=mesh.ixi_out];
+        let mut full_rhs_comp = full_rhs.slice_mut(s);
+        full_rhs_comp -= &self.source.slice(s);
+        if cfg!(feature = "validation") {
+            self.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for GravitySource<E, S> {
+    fn validate(&self) -> Result<()> {
+        check_finite_arrayd!(self.source);
+        check_finite_arrayd!(self.acceleration);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use approx::assert_relative_eq;
+    const S: usize = 12;
+    const E: usize = 3;
+    type P = Euler1DAdiabatic<S>;
+
+    fn state() -> State<P, E, S> {
+        let mut u = State::<P, E, S>::new(&PhysicsConfig::default());
+        u.cent.prim.row_mut(P::JRHO).fill(2.0);
+        u.cent.prim.row_mut(P::JXI).fill(0.5);
+        u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+        u.update_cons();
+        u.update_derived_variables();
+        u
+    }
+
+    #[test]
+    fn uniform_field_accelerates_the_gas() {
+        let mesh = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
+        let u = state();
+        let mut gravity = GravitySource::<E, S>::new(&Potential::Uniform { acceleration: -3.0 }, &UnitsMode::SI, &mesh);
+        let mut full_rhs = Array2::zeros((E, S));
+        gravity.apply(&mut full_rhs, &u, &mesh).unwrap();
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_relative_eq!(full_rhs[[P::JXI, i]], 6.0, max_relative = 1.0e-12);
+            assert_relative_eq!(full_rhs[[P::JPRESSURE, i]], 3.0, max_relative = 1.0e-12);
+        }
+        assert!(full_rhs.row(P::JRHO).iter().all(|&x| x == 0.0));
+        let dx = mesh.cell_width[mesh.ixi_in];
+        assert_relative_eq!(
+            gravity.calc_dt(&mesh).unwrap(),
+            (dx / 3.0).sqrt(),
+            max_relative = 1.0e-12
+        );
+    }
+
+    #[test]
+    fn uniform_field_accelerates_the_dust() {
+        const E: usize = 5;
+        type P = DustyGas<S>;
+        let mesh = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
+        let mut u = State::<P, E, S>::new(&PhysicsConfig::default());
+        u.cent.prim.row_mut(P::JRHO).fill(2.0);
+        u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+        u.cent.prim.row_mut(P::JRHO_DUST).fill(0.5);
+        u.cent.prim.row_mut(P::JXI_DUST).fill(1.0);
+        u.update_cons();
+        u.update_derived_variables();
+        let mut gravity = GravitySource::<E, S>::new(&Potential::Uniform { acceleration: -3.0 }, &UnitsMode::SI, &mesh);
+        let mut full_rhs = Array2::zeros((E, S));
+        gravity.apply(&mut full_rhs, &u, &mesh).unwrap();
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_relative_eq!(full_rhs[[P::JXI, i]], 6.0, max_relative = 1.0e-12);
+            assert_relative_eq!(full_rhs[[P::JXI_DUST, i]], 1.5, max_relative = 1.0e-12);
+            assert_eq!(full_rhs[[P::JRHO_DUST, i]], 0.0);
+        }
+    }
+
+    #[test]
+    fn point_mass_pulls_towards_the_origin() {
+        let mut mesh_config = MeshConfig::default_riemann_test();
+        mesh_config.mode = MeshMode::Cylindrical;
+        mesh_config.xi_in = 1.0;
+        mesh_config.xi_out = 2.0;
+        let mesh = Mesh::<S>::new(&mesh_config).unwrap();
+        let mass = 1.0 / Units::new(UnitsMode::SI).gravitational_constant();
+        let point_mass =
+            GravitySource::<E, S>::new(&Potential::PointMass { mass, softening: 0.0 }, &UnitsMode::SI, &mesh);
+        let softened =
+            GravitySource::<E, S>::new(&Potential::PointMass { mass, softening: 0.5 }, &UnitsMode::SI, &mesh);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let g = point_mass.calc_acceleration(&mesh, i);
+            assert_relative_eq!(g, -1.0 / (mesh.xi_west[i] * mesh.xi_east[i]), max_relative = 1.0e-12);
+            assert!(softened.calc_acceleration(&mesh, i).abs() < g.abs());
+        }
+    }
+
+    #[test]
+    fn function_potential_follows_the_time() {
+        let mesh = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
+        let mut gravity = GravitySource::<E, S>::new(&Potential::Function(|xi, t| t * xi), &UnitsMode::SI, &mesh);
+        assert!(gravity.calc_dt(&mesh).is_none());
+        gravity.update_time(2.0);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_relative_eq!(gravity.calc_acceleration(&mesh, i), -2.0, max_relative = 1.0e-12);
+        }
+    }
+}
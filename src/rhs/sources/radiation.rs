@@ -0,0 +1,326 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [RadiationSource] struct.
+
+use crate::{
+    errorhandling::Validation, mesh::Mesh, state::Physics, Opacity, RadiationConfig, RadiationHydro, State, Units,
+    UnitsMode,
+};
+use color_eyre::{eyre::ensure, Result};
+use ndarray::Array1;
+
+/// Integrates the radiation of [RadiationHydro] systems in the flux-limited diffusion (FLD)
+/// approximation.
+///
+/// Let
+///
+/// * `E_r`: the radiation energy density
+/// * `e`: the internal energy density of the gas
+/// * `T`: the gas temperature, i.e. `T = mu * m_u * p / (rho * k_B)` with the mean molecular weight
+///   `mu`
+/// * `c`, `a`: the speed of light and the radiation constant
+/// * `kappa_R`, `kappa_P`: the Rosseland and Planck mean opacities, see [Opacity]
+///
+/// Then the radiation diffuses with the flux `F = -1.0 * D * grad(E_r)`, where the diffusion
+/// coefficient `D = c * lambda / (kappa_R * rho)` uses the Levermore-Pomraning flux limiter
+/// `lambda = (2 + R) / (6 + 3 * R + R^2)` with `R = |grad(E_r)| / (kappa_R * rho * E_r)`. This
+/// recovers the diffusion limit `lambda = 1 / 3` in optically thick regions, and limits the flux
+/// to `|F| <= c * E_r` in optically thin ones. Gas and radiation exchange energy with:
+///
+/// ```text
+/// dE_r / dt = -1.0 * div(F) + c * kappa_P * rho * (a * T^4 - E_r)
+/// de / dt   = -1.0 * c * kappa_P * rho * (a * T^4 - E_r)
+/// ```
+///
+/// Both processes are far too stiff for the explicit time integration, so they are integrated
+/// implicitly after each time step in [RadiationSource::apply_split], without limiting the time
+/// step width. The diffusion coefficients and the opacities are taken from the beginning of the
+/// step, and the emission `a * T^4` is linearized in the internal energy, which leaves a
+/// tridiagonal linear system for the new radiation energy densities of the computational cells.
+/// The ghost cells enter that system as fixed values, so the boundary conditions of the radiation
+/// energy density carry over to the diffusion. The gas energy changes by exactly the energy the
+/// radiation takes up in each cell, so the total energy is only changed by the fluxes through the
+/// boundaries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadiationSource<const E: usize, const S: usize> {
+    /// The speed of light
+    c_light: f64,
+
+    /// The radiation constant
+    a_rad: f64,
+
+    /// Converts the specific pressure `p / rho` into the temperature, i.e. `mu * m_u / k_B`
+    temperature_factor: f64,
+
+    /// The Rosseland mean opacity
+    opacity_rosseland: Opacity,
+
+    /// The Planck mean opacity
+    opacity_planck: Opacity,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for RadiationSource<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for RadiationSource<E, S> {}
+
+impl<const E: usize, const S: usize> RadiationSource<E, S> {
+    /// Constructs a new [RadiationSource] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `radiation_config` - Configuration for the radiation
+    /// * `units_mode` - The unit system of the simulation, which sets the physical constants
+    pub fn new(radiation_config: &RadiationConfig, units_mode: &UnitsMode) -> Self {
+        let units = Units::new(units_mode.clone());
+        Self {
+            c_light: units.speed_of_light(),
+            a_rad: units.radiation_constant(),
+            temperature_factor: radiation_config.mean_molecular_weight * units.atomic_mass_unit()
+                / units.boltzmann_constant(),
+            opacity_rosseland: radiation_config.opacity_rosseland,
+            opacity_planck: radiation_config.opacity_planck,
+        }
+    }
+
+    /// Returns the gas temperature for mass density `rho` and `pressure`
+    #[inline(always)]
+    pub fn temperature(&self, rho: f64, pressure: f64) -> f64 {
+        self.temperature_factor * pressure / rho
+    }
+
+    /// Diffuses the radiation and exchanges energy between gas and radiation over the time step
+    /// width `dt`, updating the gas energy and the radiation energy density in `u.cent.cons`.
+    ///
+    /// Returns whether `u.cent.cons` was changed, in which case the rest of `u` needs to be updated
+    /// from the conservative variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `dt` - the time step width
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn apply_split<P: Physics<E, S>>(&self, u: &mut State<P, E, S>, dt: f64, mesh: &Mesh<S>) -> Result<bool> {
+        let j_rad = RadiationHydro::<S>::JRAD;
+        let (i_west, i_east) = (mesh.ixi_in - 1, mesh.ixi_out + 1);
+
+        // diffusion coefficients times face area over distance between neighbouring cells, so that
+        // the flux through the eastern face of cell i is -1.0 * weight[i] * (E_r[i + 1] - E_r[i])
+        let mut weight = Array1::<f64>::zeros(mesh.n_all);
+        for i in i_west..i_east {
+            let distance = mesh.xi_cent[i + 1] - mesh.xi_cent[i];
+            let e_rad_face = 0.5 * (u.cent.prim[[j_rad, i]] + u.cent.prim[[j_rad, i + 1]]);
+            let grad_abs = ((u.cent.prim[[j_rad, i + 1]] - u.cent.prim[[j_rad, i]]) / distance).abs();
+            let chi_face = 0.5 * (self.chi_rosseland(u, i) + self.chi_rosseland(u, i + 1));
+            weight[i] = mesh.d_area_xi_deta_dphi_east[i] * self.diffusion_coefficient(chi_face, e_rad_face, grad_abs)
+                / distance;
+        }
+
+        let mut lower = Array1::<f64>::zeros(mesh.n_all);
+        let mut diag = Array1::<f64>::zeros(mesh.n_all);
+        let mut upper = Array1::<f64>::zeros(mesh.n_all);
+        let mut rhs = Array1::<f64>::zeros(mesh.n_all);
+        let mut eta = Array1::<f64>::zeros(mesh.n_all);
+        let mut emission = Array1::<f64>::zeros(mesh.n_all);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let (rho, pressure) = (u.cent.prim[[P::JRHO, i]], u.cent.prim[[P::JPRESSURE, i]]);
+            let temperature = self.temperature(rho, pressure);
+            let e_int = pressure / (u.cent.gamma - 1.0);
+            emission[i] = self.a_rad * temperature.powi(4);
+
+            // the emission linearized in the internal energy, i.e. a * T^4 + beta * (e_new - e), lets
+            // the gas take up or give off energy implicitly
+            let rate = self.c_light * self.opacity_planck.kappa(rho, temperature) * rho;
+            let beta = 4.0 * emission[i] / e_int;
+            eta[i] = rate / (1.0 + dt * rate * beta);
+
+            let (weight_west, weight_east) = (
+                dt * mesh.deta_dphi_d_volume[i] * weight[i - 1],
+                dt * mesh.deta_dphi_d_volume[i] * weight[i],
+            );
+            lower[i] = -weight_west;
+            upper[i] = -weight_east;
+            diag[i] = 1.0 + dt * eta[i] + weight_west + weight_east;
+            rhs[i] = u.cent.cons[[j_rad, i]] + dt * eta[i] * emission[i];
+        }
+        // the ghost cells are held fixed
+        rhs[mesh.ixi_in] -= lower[mesh.ixi_in] * u.cent.cons[[j_rad, i_west]];
+        rhs[mesh.ixi_out] -= upper[mesh.ixi_out] * u.cent.cons[[j_rad, i_east]];
+
+        let e_rad = solve_tridiagonal(&lower, &diag, &upper, &rhs, mesh.ixi_in, mesh.ixi_out);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            u.cent.cons[[P::JPRESSURE, i]] -= dt * eta[i] * (emission[i] - e_rad[i]);
+            u.cent.cons[[j_rad, i]] = e_rad[i];
+        }
+        ensure!(
+            (mesh.ixi_in..=mesh.ixi_out).all(|i| u.cent.cons[[j_rad, i]].is_finite()),
+            "RadiationSource::apply_split produced non-finite radiation energy densities! Got {}",
+            u.cent.cons.row(j_rad)
+        );
+        Ok(true)
+    }
+
+    /// Returns the Rosseland mean extinction coefficient `kappa_R * rho` in cell `i`
+    #[inline(always)]
+    fn chi_rosseland<P: Physics<E, S>>(&self, u: &State<P, E, S>, i: usize) -> f64 {
+        let (rho, pressure) = (u.cent.prim[[P::JRHO, i]], u.cent.prim[[P::JPRESSURE, i]]);
+        self.opacity_rosseland.kappa(rho, self.temperature(rho, pressure)) * rho
+    }
+
+    /// Returns the flux-limited diffusion coefficient `c * lambda / chi` for the extinction
+    /// coefficient `chi`, the radiation energy density `e_rad`, and the absolute value of its
+    /// gradient `grad_abs`.
+    ///
+    /// The limiter is expanded by `(chi * e_rad)^2`, which keeps the coefficient finite in
+    /// transparent regions, where it approaches the free streaming limit `c * e_rad / grad_abs`.
+    #[inline(always)]
+    fn diffusion_coefficient(&self, chi: f64, e_rad: f64, grad_abs: f64) -> f64 {
+        let chi_e = chi * e_rad;
+        let denominator = 6.0 * chi_e * chi_e + 3.0 * chi_e * grad_abs + grad_abs * grad_abs;
+        if denominator > 0.0 {
+            self.c_light * e_rad * (2.0 * chi_e + grad_abs) / denominator
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Solves the tridiagonal linear system with the sub-diagonal `lower`, the diagonal `diag`, and the
+/// super-diagonal `upper` for the right-hand side `rhs` in the rows `i_min..=i_max` with the
+/// Thomas algorithm, which is stable for the diagonally dominant systems of the diffusion.
+fn solve_tridiagonal(
+    lower: &Array1<f64>,
+    diag: &Array1<f64>,
+    upper: &Array1<f64>,
+    rhs: &Array1<f64>,
+    i_min: usize,
+    i_max: usize,
+) -> Array1<f64> {
+    let mut upper_reduced = Array1::<f64>::zeros(rhs.len());
+    let mut solution = Array1::<f64>::zeros(rhs.len());
+    upper_reduced[i_min] = upper[i_min] / diag[i_min];
+    solution[i_min] = rhs[i_min] / diag[i_min];
+    for i in i_min + 1..=i_max {
+        let pivot = diag[i] - lower[i] * upper_reduced[i - 1];
+        upper_reduced[i] = upper[i] / pivot;
+        solution[i] = (rhs[i] - lower[i] * solution[i - 1]) / pivot;
+    }
+    for i in (i_min..i_max).rev() {
+        solution[i] -= upper_reduced[i] * solution[i + 1];
+    }
+    solution
+}
+
+impl<const E: usize, const S: usize> Validation for RadiationSource<E, S> {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.c_light > 0.0 && self.a_rad > 0.0 && self.temperature_factor > 0.0,
+            "RadiationSource needs positive physical constants! Got c = {}, a = {}, mu * m_u / k_B = {}",
+            self.c_light,
+            self.a_rad,
+            self.temperature_factor
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+    const S: usize = 12;
+    const E: usize = 4;
+    type P = RadiationHydro<S>;
+
+    fn state(physics_config: &PhysicsConfig) -> State<P, E, S> {
+        let mut u = State::<P, E, S>::new(physics_config);
+        u.cent.prim.row_mut(P::JRHO).fill(1.0);
+        u.cent.prim.row_mut(P::JXI).fill(0.0);
+        u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+        for i in 0..S {
+            u.cent.prim[[P::JRAD, i]] = 1.0 + i as f64;
+        }
+        u.update_cons();
+        u.update_derived_variables();
+        u
+    }
+
+    fn physics_config(opacity_rosseland: f64, opacity_planck: f64) -> PhysicsConfig {
+        PhysicsConfig {
+            adiabatic_index: 5.0 / 3.0,
+            systems: SystemsConfig {
+                radiation: Some(RadiationConfig {
+                    mean_molecular_weight: 1.0,
+                    opacity_rosseland: Opacity::Constant(opacity_rosseland),
+                    opacity_planck: Opacity::Constant(opacity_planck),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tridiagonal_solver_inverts_the_matrix() {
+        let lower = ndarray::arr1(&[0.0, -1.0, -2.0, -1.0, 0.0]);
+        let diag = ndarray::arr1(&[0.0, 4.0, 5.0, 3.0, 0.0]);
+        let upper = ndarray::arr1(&[0.0, -1.0, -0.5, 0.0, 0.0]);
+        let x = ndarray::arr1(&[0.0, 1.0, 2.0, 3.0, 0.0]);
+        let rhs = ndarray::arr1(&[
+            0.0,
+            diag[1] * x[1] + upper[1] * x[2],
+            lower[2] * x[1] + diag[2] * x[2] + upper[2] * x[3],
+            lower[3] * x[2] + diag[3] * x[3],
+            0.0,
+        ]);
+        let solution = solve_tridiagonal(&lower, &diag, &upper, &rhs, 1, 3);
+        for i in 1..=3 {
+            assert_relative_eq!(solution[i], x[i], max_relative = 1.0e-14);
+        }
+    }
+
+    #[test]
+    fn diffusion_without_coupling_conserves_radiation_energy() {
+        let physics_config = physics_config(1.0e8, 0.0);
+        let mesh = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
+        let source = RadiationSource::<E, S>::new(physics_config.systems.radiation.as_ref().unwrap(), &UnitsMode::SI);
+        let mut u = state(&physics_config);
+        // without gradients at the boundaries, no radiation leaves the computational cells
+        u.cent.cons[[P::JRAD, mesh.ixi_in - 1]] = u.cent.cons[[P::JRAD, mesh.ixi_in]];
+        u.cent.cons[[P::JRAD, mesh.ixi_out + 1]] = u.cent.cons[[P::JRAD, mesh.ixi_out]];
+        let total = |u: &State<P, E, S>| {
+            (mesh.ixi_in..=mesh.ixi_out)
+                .map(|i| u.cent.cons[[P::JRAD, i]])
+                .sum::<f64>()
+        };
+        let (total_initial, energy_initial) = (total(&u), u.cent.cons.row(P::JPRESSURE).to_owned());
+        assert!(source.apply_split(&mut u, 1.0e-3, &mesh).unwrap());
+        assert_relative_eq!(total(&u), total_initial, max_relative = 1.0e-12);
+        assert_eq!(u.cent.cons.row(P::JPRESSURE), energy_initial);
+
+        // the profile flattens out
+        let (e_in, e_out) = (
+            u.cent.cons[[P::JRAD, mesh.ixi_in]],
+            u.cent.cons[[P::JRAD, mesh.ixi_out]],
+        );
+        assert!(e_in > 1.0 + mesh.ixi_in as f64);
+        assert!(e_out < 1.0 + mesh.ixi_out as f64);
+    }
+
+    #[test]
+    fn coupling_conserves_energy_per_cell() {
+        let physics_config = physics_config(0.0, 1.0e-8);
+        let mesh = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
+        let source = RadiationSource::<E, S>::new(physics_config.systems.radiation.as_ref().unwrap(), &UnitsMode::SI);
+        let mut u = state(&physics_config);
+        u.cent.cons.row_mut(P::JRAD).fill(0.0);
+        let total = |u: &State<P, E, S>, i: usize| u.cent.cons[[P::JPRESSURE, i]] + u.cent.cons[[P::JRAD, i]];
+        let u_initial = u.clone();
+        assert!(source.apply_split(&mut u, 1.0e-3, &mesh).unwrap());
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_relative_eq!(total(&u, i), total(&u_initial, i), max_relative = 1.0e-14);
+            assert!(u.cent.cons[[P::JRAD, i]] > 0.0);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [ReactionSource] struct.
+
+use crate::{errorhandling::Validation, mesh::Mesh, state::Physics, State};
+use color_eyre::{eyre::Context, Result};
+use ndarray::s;
+
+/// Integrates the reactions between the species of [ReactingFlow](crate::state::ReactingFlow)
+/// systems.
+///
+/// Reaction networks, like the ionization and recombination of hydrogen, tend to be far too stiff
+/// for the explicit time integration, so they do not contribute to the right-hand side. Instead,
+/// the network is integrated implicitly in each cell after each time step in
+/// [ReactionSource::apply_split], see [ReactionNetwork::react](crate::state::ReactionNetwork::react),
+/// without limiting the time step width. The mass density and the energy of each cell are left
+/// untouched, only the partial densities of the species change.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReactionSource<const E: usize, const S: usize>;
+
+unsafe impl<const E: usize, const S: usize> Send for ReactionSource<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for ReactionSource<E, S> {}
+
+impl<const E: usize, const S: usize> ReactionSource<E, S> {
+    /// Constructs a new [ReactionSource] object.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Integrates the reaction network over the time step width `dt`, updating the partial
+    /// densities of the species in `u.cent.cons`.
+    ///
+    /// Returns whether `u.cent.cons` was changed, in which case the rest of `u` needs to be updated
+    /// from the conservative variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `dt` - the time step width
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn apply_split<P: Physics<E, S>>(&self, u: &mut State<P, E, S>, dt: f64, mesh: &Mesh<S>) -> Result<bool> {
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let rho = u.cent.cons[[P::JRHO, i]];
+            let xi_mom = u.cent.cons[[P::JXI, i]];
+            let e_int = u.cent.cons[[P::JPRESSURE, i]] - 0.5 * xi_mom * xi_mom / rho;
+            let mut mass_fractions = u.cent.cons.slice(s![P::JSCALARS.., i]).mapv(|x| x / rho);
+            u.cent
+                .network
+                .react(rho, e_int, &mut mass_fractions, dt)
+                .with_context(|| format!("Calling ReactionNetwork::react in cell {}", i))?;
+            u.cent
+                .cons
+                .slice_mut(s![P::JSCALARS.., i])
+                .assign(&(rho * &mass_fractions));
+        }
+        Ok(true)
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for ReactionSource<E, S> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Transport] struct.
+
+use crate::{
+    errorhandling::Validation, mesh::Mesh, state::Physics, State, TransportConfig, Units, UnitsMode, Viscosity,
+};
+use color_eyre::{eyre::ensure, Result};
+use ndarray::{s, Array2};
+
+/// Calculates the parabolic fluxes of the physical viscosity and the heat conduction, which turn
+/// the Euler equations into the Navier-Stokes equations.
+///
+/// Let
+///
+/// * `v`, `w`: the xi and the eta velocity
+/// * `T`: the gas temperature, i.e. `T = mu_mol * m_u * p / (rho * k_B)` with the mean molecular
+///   weight `mu_mol`, where isothermal systems use `p / rho = c_s^2`
+/// * `mu`: the dynamic viscosity, see [Viscosity]
+/// * `K`: the thermal conductivity
+///
+/// Then the viscous stresses and the heat flux through a cell face along xi are:
+///
+/// ```text
+/// tau_xi  = 4 / 3 * mu * dv / dxi
+/// tau_eta = mu * dw / dxi
+/// q       = -1.0 * K * dT / dxi
+/// ```
+///
+/// and they enter the momentum and energy equations like an additional physical flux:
+///
+/// ```text
+/// F_xi     = -1.0 * tau_xi
+/// F_eta    = -1.0 * tau_eta
+/// F_energy = -1.0 * (tau_xi * v + tau_eta * w) + q
+/// ```
+///
+/// The gradients are taken between the centres of neighbouring cells, and all other quantities are
+/// averaged onto the face between them, which makes these fluxes second order accurate. Like the
+/// numerical flux, they are multiplied with the area of the cell faces before their divergence is
+/// added to the right-hand side, so the momentum and the energy stay conserved.
+///
+/// Since these terms are integrated explicitly, they limit the time step width to the time their
+/// diffusivities need to cross a cell, see [Transport::calc_dt].
+///
+/// The viscosity only models the stress tensor of cartesian meshes, while the heat conduction works
+/// on any mesh. Both the temperature and the heat capacity follow the ideal gas law, so adiabatic
+/// systems with any other [EosConfig](crate::EosConfig) only get a constant viscosity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transport<const E: usize, const S: usize> {
+    /// The parabolic flux through the eastern face of each cell, multiplied with its area
+    flux: Array2<f64>,
+
+    /// The dynamic viscosity
+    viscosity: Viscosity,
+
+    /// The thermal conductivity
+    thermal_conductivity: f64,
+
+    /// Converts the specific pressure `p / rho` into the temperature, i.e. `mu_mol * m_u / k_B`
+    temperature_factor: f64,
+}
+
+unsafe impl<const E: usize, const S: usize> Send for Transport<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for Transport<E, S> {}
+
+impl<const E: usize, const S: usize> Transport<E, S> {
+    /// Constructs a new [Transport] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `transport_config` - Configuration for the viscosity and the heat conduction
+    /// * `units_mode` - The unit system of the simulation, which sets the physical constants
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn new(transport_config: &TransportConfig, units_mode: &UnitsMode, mesh: &Mesh<S>) -> Self {
+        let units = Units::new(units_mode.clone());
+        Self {
+            flux: Array2::zeros((E, mesh.n_all)),
+            viscosity: transport_config.viscosity,
+            thermal_conductivity: transport_config.thermal_conductivity,
+            temperature_factor: transport_config.mean_molecular_weight * units.atomic_mass_unit()
+                / units.boltzmann_constant(),
+        }
+    }
+
+    /// Returns the gas temperature in cell `i`
+    #[inline(always)]
+    fn temperature<P: Physics<E, S>>(&self, u: &State<P, E, S>, i: usize) -> f64 {
+        let specific_pressure = if P::IS_ADIABATIC {
+            u.cent.prim[[P::JPRESSURE, i]] / u.cent.prim[[P::JRHO, i]]
+        } else {
+            u.cent.c_sound[i] * u.cent.c_sound[i]
+        };
+        self.temperature_factor * specific_pressure
+    }
+
+    /// Adds the divergence of the parabolic fluxes to `full_rhs`.
+    ///
+    /// Just like the numerical flux, the divergence is added to `full_rhs` as is, since the time
+    /// integration subtracts it from the conservative variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `full_rhs` - the right-hand side the fluxes are added to
+    /// * `u` - current [State] of the simulation
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn apply<P: Physics<E, S>>(
+        &mut self,
+        full_rhs: &mut Array2<f64>,
+        u: &State<P, E, S>,
+        mesh: &Mesh<S>,
+    ) -> Result<()> {
+        for i in mesh.ixi_in - 1..=mesh.ixi_out {
+            let distance = mesh.xi_cent[i + 1] - mesh.xi_cent[i];
+            let area = mesh.d_area_xi_deta_dphi_east[i];
+            let (temperature_west, temperature_east) = (self.temperature(u, i), self.temperature(u, i + 1));
+            let mu = self.viscosity.mu(0.5 * (temperature_west + temperature_east));
+
+            let (xi_vel_west, xi_vel_east) = (u.cent.prim[[P::JXI, i]], u.cent.prim[[P::JXI, i + 1]]);
+            let tau_xi = 4.0 / 3.0 * mu * (xi_vel_east - xi_vel_west) / distance;
+            self.flux[[P::JXI, i]] = -area * tau_xi;
+            let mut work = tau_xi * 0.5 * (xi_vel_west + xi_vel_east);
+            if P::JETA != usize::MAX {
+                let (eta_vel_west, eta_vel_east) = (u.cent.prim[[P::JETA, i]], u.cent.prim[[P::JETA, i + 1]]);
+                let tau_eta = mu * (eta_vel_east - eta_vel_west) / distance;
+                self.flux[[P::JETA, i]] = -area * tau_eta;
+                work += tau_eta * 0.5 * (eta_vel_west + eta_vel_east);
+            }
+            if P::IS_ADIABATIC {
+                let heat_flux = -self.thermal_conductivity * (temperature_east - temperature_west) / distance;
+                self.flux[[P::JPRESSURE, i]] = area * (heat_flux - work);
+            }
+        }
+
+        let s = s![mesh.ixi_in..=mesh.ixi_out];
+        let sm1 = s![mesh.ixi_in - 1..=mesh.ixi_out - 1];
+        for j in 0..E {
+            full_rhs.row_mut(j).slice_mut(s).scaled_add(
+                1.0,
+                &(&mesh.deta_dphi_d_volume.slice(s) * (&self.flux.row(j).slice(s) - &self.flux.row(j).slice(sm1))),
+            );
+        }
+        if cfg!(feature = "validation") {
+            self.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the parabolic flux through the eastern face of each cell from the last call to
+    /// [Transport::apply], multiplied with the area of the face.
+    pub fn flux(&self) -> &Array2<f64> {
+        &self.flux
+    }
+
+    /// Returns the time the fastest of the diffusivities of the viscosity and the heat conduction
+    /// needs to cross a cell, i.e. the minimum of `dxi^2 / (2 * D)`.
+    ///
+    /// The kinematic viscosity is `D = 4 / 3 * mu / rho`, and the thermal diffusivity is
+    /// `D = K / (rho * c_v)` with the specific heat capacity at constant volume `c_v`.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - current [State] of the simulation
+    /// * `mesh` - the [Mesh] this simulation runs on
+    pub fn calc_dt<P: Physics<E, S>>(&self, u: &State<P, E, S>, mesh: &Mesh<S>) -> Option<f64> {
+        (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| {
+                let rho = u.cent.prim[[P::JRHO, i]];
+                let mut diffusivity = 4.0 / 3.0 * self.viscosity.mu(self.temperature(u, i)) / rho;
+                if P::IS_ADIABATIC {
+                    let c_v = 1.0 / ((u.cent.gamma - 1.0) * self.temperature_factor);
+                    diffusivity = diffusivity.max(self.thermal_conductivity / (rho * c_v));
+                }
+                mesh.cell_width[i] * mesh.cell_width[i] / (2.0 * diffusivity)
+            })
+            .filter(|dt| dt.is_finite())
+            .reduce(f64::min)
+    }
+}
+
+impl<const E: usize, const S: usize> Validation for Transport<E, S> {
+    fn validate(&self) -> Result<()> {
+        check_finite_arrayd!(self.flux);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+    const S: usize = 12;
+
+    fn transport_config(mu: f64, thermal_conductivity: f64) -> TransportConfig {
+        TransportConfig {
+            viscosity: Viscosity::Constant(mu),
+            thermal_conductivity,
+            mean_molecular_weight: 1.0,
+        }
+    }
+
+    #[test]
+    fn sutherland_viscosity_matches_reference() {
+        // air, with the usual reference values
+        let viscosity = Viscosity::Sutherland {
+            mu_ref: 1.716e-5,
+            temperature_ref: 273.15,
+            sutherland_temperature: 110.4,
+        };
+        assert_relative_eq!(viscosity.mu(273.15), 1.716e-5, max_relative = 1.0e-14);
+        assert!(viscosity.mu(373.15) > viscosity.mu(273.15));
+        assert!(!viscosity.is_zero());
+        assert!(Viscosity::Constant(0.0).is_zero());
+    }
+
+    #[test]
+    fn linear_velocity_profile_has_no_net_stress() {
+        type P = Euler2DAdiabatic<S>;
+        const E: usize = 4;
+        let mesh = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
+        let mut u = State::<P, E, S>::new(&PhysicsConfig::default());
+        u.cent.prim.row_mut(P::JRHO).fill(1.0);
+        u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+        for i in 0..S {
+            u.cent.prim[[P::JXI, i]] = 0.0;
+            u.cent.prim[[P::JETA, i]] = mesh.xi_cent[i];
+        }
+        u.update_cons();
+        u.update_derived_variables();
+
+        let mut transport = Transport::<E, S>::new(&transport_config(0.1, 0.0), &UnitsMode::SI, &mesh);
+        let mut full_rhs = Array2::zeros((E, S));
+        transport.apply(&mut full_rhs, &u, &mesh).unwrap();
+        // plane Couette flow carries a constant shear stress, which only heats the gas
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            assert_relative_eq!(full_rhs[[P::JETA, i]], 0.0, epsilon = 1.0e-12);
+            assert_relative_eq!(full_rhs[[P::JPRESSURE, i]], -0.1, max_relative = 1.0e-10);
+        }
+        assert!(full_rhs.row(P::JRHO).iter().all(|&x| x == 0.0));
+    }
+
+    #[test]
+    fn diffusion_limits_the_time_step() {
+        type P = Euler1DAdiabatic<S>;
+        const E: usize = 3;
+        let mesh = Mesh::<S>::new(&MeshConfig::default_riemann_test()).unwrap();
+        let mut u = State::<P, E, S>::new(&PhysicsConfig::default());
+        u.cent.prim.row_mut(P::JRHO).fill(2.0);
+        u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+        u.update_cons();
+        u.update_derived_variables();
+
+        let dx = mesh.cell_width[mesh.ixi_in];
+        let viscous = Transport::<E, S>::new(&transport_config(0.3, 0.0), &UnitsMode::SI, &mesh);
+        assert_relative_eq!(
+            viscous.calc_dt(&u, &mesh).unwrap(),
+            dx * dx / (2.0 * 4.0 / 3.0 * 0.3 / 2.0),
+            max_relative = 1.0e-12
+        );
+        let inviscid = Transport::<E, S>::new(&transport_config(0.0, 0.0), &UnitsMode::SI, &mesh);
+        assert!(inviscid.calc_dt(&u, &mesh).is_none());
+    }
+}
@@ -4,16 +4,21 @@
 
 //! Exports the [Solver] struct, responsible for generating new solutions for [State] objects
 
-use crate::{timestep::TimeStep, CorriesConfig, Mesh, NumFlux, Physics, Rhs, State, TimeSolver};
+use crate::{timestep::TimeStep, CorriesConfig, Mesh, MeshMotion, NumFlux, Physics, Rhs, State, TimeSolver};
 use color_eyre::{
     eyre::{bail, Context},
     Result,
 };
+use ndarray::Array2;
 use std::marker::PhantomData;
 
 /// Struct that is responsible for generating new solutions for [State] objects.
 ///
 /// Acts as a bundler struct for [TimeStep], [Rhs], and [TimeSolver].
+///
+/// If the config sets [MeshMotion::Lagrangian], the [Solver] also moves the cell faces of the
+/// [Mesh] with the fluid after each time step, and optionally remaps the [State] back onto the
+/// initial [Mesh].
 pub struct Solver<P: Physics<E, S>, N: NumFlux<E, S>, T: TimeSolver<P, E, S>, const E: usize, const S: usize> {
     /// Keeps track of the time coordinate and related data
     pub timestep: TimeStep,
@@ -24,6 +29,12 @@ pub struct Solver<P: Physics<E, S>, N: NumFlux<E, S>, T: TimeSolver<P, E, S>, co
     /// Solves the right-hand side
     pub rhs: Rhs<N, E, S>,
 
+    /// Whether the cell faces move with the fluid
+    mesh_motion: MeshMotion,
+
+    /// The initial mesh, that a Lagrangian mesh is remapped onto
+    mesh_eulerian: Option<Mesh<S>>,
+
     phantom_type: PhantomData<P>,
 }
 
@@ -78,12 +89,27 @@ impl<P: Physics<E, S>, N: NumFlux<E, S>, T: TimeSolver<P, E, S>, const E: usize,
             timestep: TimeStep::new(&config.numerics_config, config.output_counter_max),
             time_solver: T::new(config)?,
             rhs: Rhs::<N, E, S>::new(config, mesh)?,
+            mesh_motion: config.numerics_config.mesh_motion,
+            mesh_eulerian: match config.numerics_config.mesh_motion {
+                MeshMotion::Lagrangian {
+                    remap_interval: Some(_),
+                } => Some(mesh.clone()),
+                _ => None,
+            },
             phantom_type: PhantomData,
         })
     }
 
     /// Calculates the next state for the [State] object `u`.
     ///
+    /// Source terms that are split off from the time integration, like a drag that is not
+    /// integrated explicitly, are applied right after the [TimeSolver] step, see
+    /// [Rhs::apply_split].
+    ///
+    /// For a [MeshMotion::Lagrangian] mesh, the cell faces of `mesh` are moved afterwards, and the
+    /// conservative variables are scaled such that each cell keeps its content. If a remap is due,
+    /// `u` is remapped onto the initial mesh, which then replaces `mesh`.
+    ///
     /// # Arguments
     ///
     /// * `u` - the [State] being modified to transition between current and next state
@@ -108,14 +134,29 @@ impl<P: Physics<E, S>, N: NumFlux<E, S>, T: TimeSolver<P, E, S>, const E: usize,
     ///
     /// // define the config instance
     /// let config = CorriesConfig::default_riemann_test::<N, E, S>(t_end, folder_name, file_name);
-    /// let (mut u, mut solver, mesh, _) = config.init_corries::<P, N, T, E, S>(|_,_,_| Ok(())).unwrap();
+    /// let (mut u, mut solver, mut mesh, _) = config.init_corries::<P, N, T, E, S>(|_,_,_| Ok(())).unwrap();
     ///
-    /// solver.next_solution(&mut u, &mesh).unwrap();
+    /// solver.next_solution(&mut u, &mut mesh).unwrap();
     /// ```
-    pub fn next_solution(&mut self, u: &mut State<P, E, S>, mesh: &Mesh<S>) -> Result<()> {
+    pub fn next_solution(&mut self, u: &mut State<P, E, S>, mesh: &mut Mesh<S>) -> Result<()> {
+        if let MeshMotion::Lagrangian { .. } = self.mesh_motion {
+            // the faces at the edges of the computational area stay in place
+            let xi_vel = P::xi_vel(&u.cent);
+            mesh.face_velocity_east.fill(0.0);
+            for i in mesh.ixi_in..mesh.ixi_out {
+                mesh.face_velocity_east[i] = 0.5 * (xi_vel[i] + xi_vel[i + 1]);
+            }
+        }
         self.time_solver
             .next_solution::<N>(&mut self.timestep, u, &mut self.rhs, mesh)
             .context("Calling TimeIntegration::solver.next_solution in Solver::next_solution")?;
+        self.rhs
+            .apply_split(u, self.timestep.dt, mesh)
+            .context("Calling Rhs::apply_split in Solver::next_solution")?;
+        if let MeshMotion::Lagrangian { remap_interval } = self.mesh_motion {
+            self.move_mesh(u, mesh, remap_interval)
+                .context("Calling Solver::move_mesh in Solver::next_solution")?;
+        }
         if self.timestep.iter >= self.timestep.iter_max {
             bail!(
                 "time.iter reached time.iter_max! time.iter = {}, time.iter_max = {}",
@@ -125,4 +166,35 @@ impl<P: Physics<E, S>, N: NumFlux<E, S>, T: TimeSolver<P, E, S>, const E: usize,
         }
         Ok(())
     }
+
+    /// Returns the flux through the eastern cell faces, integrated over the last call to
+    /// [Solver::next_solution], see [TimeSolver::integrated_flux].
+    pub fn integrated_flux(&self) -> &Array2<f64> {
+        self.time_solver.integrated_flux()
+    }
+
+    /// Moves the cell faces of `mesh` over the last time step, and remaps `u` onto the initial
+    /// mesh if that is due.
+    fn move_mesh(&mut self, u: &mut State<P, E, S>, mesh: &mut Mesh<S>, remap_interval: Option<usize>) -> Result<()> {
+        let volume_old = mesh.volume.clone();
+        mesh.move_faces(self.timestep.dt)?;
+        u.apply_c_sound_profile(mesh)?;
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let ratio = volume_old[i] / mesh.volume[i];
+            u.cent.cons.column_mut(i).mapv_inplace(|c| c * ratio);
+        }
+        u.update_vars_from_cons(&mut self.rhs.boundary_west, &mut self.rhs.boundary_east, mesh)?;
+
+        if let (Some(n), Some(mesh_eulerian)) = (remap_interval, &self.mesh_eulerian) {
+            if self.timestep.iter.is_multiple_of(n) {
+                let cons = u.cent.cons.clone();
+                mesh.remap(&cons, mesh_eulerian, &mut u.cent.cons);
+                *mesh = mesh_eulerian.clone();
+                u.apply_c_sound_profile(mesh)?;
+                u.update_vars_from_cons(&mut self.rhs.boundary_west, &mut self.rhs.boundary_east, mesh)?;
+            }
+        }
+        self.rhs.update_mesh(mesh);
+        Ok(())
+    }
 }
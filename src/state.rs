@@ -5,13 +5,24 @@
 //! Exports the [State] struct
 
 use self::variables::Variables;
-pub use self::{physics::Physics, systems::*};
+pub use self::{
+    eos::{EosParams, EosTable, EquationOfState, IdealGas, Polytropic, StiffenedGas, Tabulated},
+    network::ReactionNetwork,
+    physics::Physics,
+    systems::*,
+};
 use crate::{
     boundaryconditions::BoundaryCondition, directions::Direction, errorhandling::Validation, Mesh, PhysicsConfig,
+    SoundSpeedProfile,
+};
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
 };
-use color_eyre::Result;
 use std::marker::PhantomData;
 
+pub mod eos;
+pub mod network;
 pub mod physics;
 pub mod systems;
 pub mod variables;
@@ -48,10 +59,18 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> Default for State<P, E, S
 impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
     /// Construct a new [State] object
     pub fn new(physics_config: &PhysicsConfig) -> Self {
+        Self::with_size(physics_config, S)
+    }
+
+    /// Construct a new [State] object with `n_all` cells, which is how runtime-sized states, with
+    /// `S =` [RUNTIME_SIZE](crate::mesh::RUNTIME_SIZE), are built.
+    pub fn with_size(physics_config: &PhysicsConfig, n_all: usize) -> Self {
+        let mut vars = Variables::with_size(physics_config, n_all);
+        vars.n_scalars = P::NUM_SCALARS;
         Self {
-            cent: Variables::new(physics_config),
-            west: Variables::new(physics_config),
-            east: Variables::new(physics_config),
+            cent: vars.clone(),
+            west: vars.clone(),
+            east: vars,
             methods: PhantomData,
         }
     }
@@ -65,9 +84,85 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
     ///
     /// If your simulation uses that scheme, you should call this method on the [State] object
     /// after applying your initial conditions to the mesh central variables.
+    ///
+    /// The bottom and a speed of sound set by a [SoundSpeedProfile] are
+    /// evaluated on the cell faces themselves, see [State::apply_bathymetry] and
+    /// [State::apply_c_sound_profile], so they are not copied from the cell centres.
     pub fn init_west_east(&mut self) {
-        self.west.assign(&self.cent);
-        self.east.assign(&self.cent);
+        let copies_c_sound = self.cent.c_sound_profile == SoundSpeedProfile::Global;
+        for face in [&mut self.west, &mut self.east] {
+            face.prim.assign(&self.cent.prim);
+            face.cons.assign(&self.cent.cons);
+            if copies_c_sound {
+                face.c_sound.assign(&self.cent.c_sound);
+            }
+        }
+    }
+
+    /// Applies [PhysicsConfig::c_sound_profile] of locally isothermal systems, evaluating it at
+    /// the cell centres for `self.cent`, and at the west and east cell faces for `self.west` and
+    /// `self.east`.
+    ///
+    /// This is a no-op for adiabatic systems and [SoundSpeedProfile::Global]. Call this after
+    /// setting the initial conditions, and whenever the cell faces of the mesh move.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - The mesh to evaluate the profile on
+    pub fn apply_c_sound_profile(&mut self, mesh: &Mesh<S>) -> Result<()> {
+        if !P::IS_ADIABATIC {
+            self.cent
+                .apply_c_sound_profile(&mesh.xi_cent)
+                .context("Applying the speed of sound profile to the cell centres")?;
+            self.west
+                .apply_c_sound_profile(&mesh.xi_west)
+                .context("Applying the speed of sound profile to the west cell faces")?;
+            self.east
+                .apply_c_sound_profile(&mesh.xi_east)
+                .context("Applying the speed of sound profile to the east cell faces")?;
+        }
+        Ok(())
+    }
+
+    /// Applies [ShallowWaterConfig::bathymetry](crate::ShallowWaterConfig::bathymetry), evaluating
+    /// it at the cell centres.
+    ///
+    /// The elevation on the west and east cell faces is set to the average of the two cell
+    /// centres next to that face, so that both sides of a face see the same elevation, and so that
+    /// the flux through that face matches the [BathymetrySource](crate::rhs::BathymetrySource)
+    /// for a lake at rest; only the outermost faces are evaluated directly. Since the conversions
+    /// between primitive and conservative variables depend on the bottom, call this before
+    /// setting the initial conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - The mesh to evaluate the bathymetry on
+    pub fn apply_bathymetry(&mut self, mesh: &Mesh<S>) -> Result<()> {
+        let n_all = mesh.n_all;
+        for i in 0..n_all {
+            let bottom = self.cent.systems.shallow_water.bathymetry.elevation(mesh.xi_cent[i]);
+            ensure!(
+                bottom.is_finite(),
+                "The bathymetry needs to be finite! Got {} at xi = {} in cell {}",
+                bottom,
+                mesh.xi_cent[i],
+                i
+            );
+            self.cent.bottom[i] = bottom;
+        }
+        for i in 0..n_all {
+            self.west.bottom[i] = if i > 0 {
+                0.5 * (self.cent.bottom[i - 1] + self.cent.bottom[i])
+            } else {
+                self.west.systems.shallow_water.bathymetry.elevation(mesh.xi_west[i])
+            };
+            self.east.bottom[i] = if i < n_all - 1 {
+                0.5 * (self.cent.bottom[i] + self.cent.bottom[i + 1])
+            } else {
+                self.east.systems.shallow_water.bathymetry.elevation(mesh.xi_east[i])
+            };
+        }
+        Ok(())
     }
 
     /// Returns whether this [State] is handled adiabatically or isothermally
@@ -109,16 +204,16 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
     /// let mut u: State<P, E, S> = State::default();
     ///
     /// // Updates the primitive variables in the cell centres
-    /// u.update_prim_d::<{Direction::Cent as u8}>();
+    /// u.update_prim_d::<{Direction::Cent as u8}>().unwrap();
     ///
     /// // Updates the primitive variables at west cell faces
-    /// u.update_prim_d::<{Direction::West as u8}>();
+    /// u.update_prim_d::<{Direction::West as u8}>().unwrap();
     ///
     /// // Updates the primitive variables at east cell faces
-    /// u.update_prim_d::<{Direction::East as u8}>();
+    /// u.update_prim_d::<{Direction::East as u8}>().unwrap();
     /// ```
-    pub fn update_prim_d<const D: u8>(&mut self) {
-        P::update_prim(self.get_vars_mut::<D>());
+    pub fn update_prim_d<const D: u8>(&mut self) -> Result<()> {
+        P::update_prim(self.get_vars_mut::<D>())
     }
 
     /// Update the primitive variables at the cell centres
@@ -136,10 +231,10 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
     /// let mut u: State<P, E, S> = State::default();
     ///
     /// // Updates the primitive variables in the cell centres
-    /// u.update_prim();
+    /// u.update_prim().unwrap();
     /// ```
-    pub fn update_prim(&mut self) {
-        self.update_prim_d::<{ Direction::Cent as u8 }>();
+    pub fn update_prim(&mut self) -> Result<()> {
+        self.update_prim_d::<{ Direction::Cent as u8 }>()
     }
 
     /// Update the conservative variables at a certain part of each mesh cell
@@ -511,8 +606,8 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
         boundary_west: &mut Box<dyn BoundaryCondition<E, S>>,
         boundary_east: &mut Box<dyn BoundaryCondition<E, S>>,
         mesh: &Mesh<S>,
-    ) {
-        P::update_vars_from_cons(self.get_vars_mut::<D>(), boundary_west, boundary_east, mesh);
+    ) -> Result<()> {
+        P::update_vars_from_cons(self.get_vars_mut::<D>(), boundary_west, boundary_east, mesh)
     }
 
     /// Updates all variables at cell centres, assuming that conservative variables are up to date
@@ -525,8 +620,8 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
         boundary_west: &mut Box<dyn BoundaryCondition<E, S>>,
         boundary_east: &mut Box<dyn BoundaryCondition<E, S>>,
         mesh: &Mesh<S>,
-    ) {
-        self.update_vars_from_cons_d::<{ Direction::Cent as u8 }>(boundary_west, boundary_east, mesh);
+    ) -> Result<()> {
+        self.update_vars_from_cons_d::<{ Direction::Cent as u8 }>(boundary_west, boundary_east, mesh)
     }
 
     /// Assigns the fields of the argument to `self`.
@@ -573,7 +668,7 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
     /// use corries::prelude::*;
     /// set_Physics_and_E!(Euler1DIsot);
     /// const S: usize = 10;
-    /// let physics_config = PhysicsConfig { adiabatic_index: 1.66, units_mode: UnitsMode::SI };
+    /// let physics_config = PhysicsConfig { adiabatic_index: 1.66, units_mode: UnitsMode::SI, ..Default::default() };
     /// let mut u1: State<P, E, S> = State::default();
     /// let mut u2: State<P, E, S> = State::default();
     ///
@@ -615,7 +710,7 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
     /// use corries::prelude::*;
     /// set_Physics_and_E!(Euler1DIsot);
     /// const S: usize = 10;
-    /// let physics_config = PhysicsConfig { adiabatic_index: 1.66, units_mode: UnitsMode::SI };
+    /// let physics_config = PhysicsConfig { adiabatic_index: 1.66, units_mode: UnitsMode::SI, ..Default::default() };
     /// let mut u1: State<P, E, S> = State::default();
     /// let mut u2: State<P, E, S> = State::default();
     ///
@@ -647,21 +742,25 @@ impl<P: Physics<E, S>, const E: usize, const S: usize> State<P, E, S> {
 impl<P: Physics<E, S>, const E: usize, const S: usize> Validation for State<P, E, S> {
     fn validate(&self) -> Result<()> {
         self.cent.validate()?;
+        P::validate(&self.cent)?;
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{PhysicsConfig, UnitsMode};
+    use crate::{AdvectionConfig, EosConfig, MhdConfig, PhysicsConfig, SystemsConfig, UnitsMode};
 
     use super::*;
     use proptest::prelude::*;
     const S: usize = 2;
-    const PHYSICS_CONFIG: PhysicsConfig = PhysicsConfig {
-        adiabatic_index: 1.4,
-        units_mode: UnitsMode::SI,
-    };
+    fn physics_config() -> PhysicsConfig {
+        PhysicsConfig {
+            adiabatic_index: 1.4,
+            units_mode: UnitsMode::SI,
+            ..Default::default()
+        }
+    }
 
     mod euler1dadiabatic {
         use super::*;
@@ -671,16 +770,16 @@ mod tests {
             #[test]
             fn conversion(p0 in 0.1f64..10.0, p1 in -10.0f64..10.0, p2 in 0.1f64..10.0) {
                 // converting to cons and back to prim should be idempotent
-                let mut u0 = State::<P, E, S>::new(&PHYSICS_CONFIG);
+                let mut u0 = State::<P, E, S>::new(&physics_config());
                 u0.cent.prim.row_mut(0).fill(p0);
                 u0.cent.prim.row_mut(1).fill(p1);
                 u0.cent.prim.row_mut(2).fill(p2);
-                let mut u = State::<P,E,S>::new(&PHYSICS_CONFIG);
+                let mut u = State::<P,E,S>::new(&physics_config());
                 u.cent.prim.row_mut(0).fill(p0);
                 u.cent.prim.row_mut(1).fill(p1);
                 u.cent.prim.row_mut(2).fill(p2);
                 u.update_cons();
-                u.update_prim();
+                u.update_prim().unwrap();
                 assert_relative_eq!(u.cent.prim.row(0), u0.cent.prim.row(0), max_relative = 1.0e-12);
                 assert_relative_eq!(u.cent.prim.row(1), u0.cent.prim.row(1), max_relative = 1.0e-12);
                 assert_relative_eq!(u.cent.prim.row(2), u0.cent.prim.row(2), max_relative = 1.0e-8);
@@ -695,16 +794,340 @@ mod tests {
             #[test]
             fn conversion(p0 in 0.1f64..100_000.0, p1 in -100_000.0f64..100_000.0) {
                 // converting to cons and back to prim should be idempotent
-                let mut u0 = State::<P, E, S>::new(&PHYSICS_CONFIG);
+                let mut u0 = State::<P, E, S>::new(&physics_config());
                 u0.cent.prim.row_mut(0).fill(p0);
                 u0.cent.prim.row_mut(1).fill(p1);
-                let mut u = State::<P, E, S>::new(&PHYSICS_CONFIG);
+                let mut u = State::<P, E, S>::new(&physics_config());
                 u.cent.prim.row_mut(0).fill(p0);
                 u.cent.prim.row_mut(1).fill(p1);
                 u.update_cons();
-                u.update_prim();
+                u.update_prim().unwrap();
                 assert_relative_eq!(u.cent.prim, u0.cent.prim, max_relative = 1.0e-12);
             }
         }
     }
+    mod euler1dadiabatic_stiffened {
+        use super::*;
+        use approx::assert_relative_eq;
+        const E: usize = 3;
+        type P = Euler1DAdiabatic<S, StiffenedGas>;
+        fn stiffened_config() -> PhysicsConfig {
+            PhysicsConfig {
+                adiabatic_index: 4.4,
+                units_mode: UnitsMode::SI,
+                eos: EosConfig::StiffenedGas { p_inf: 6.0e8 },
+                ..Default::default()
+            }
+        }
+        proptest! {
+            #[test]
+            fn conversion(p0 in 500.0f64..1500.0, p1 in -100.0f64..100.0, p2 in 1.0e5f64..1.0e9) {
+                // converting to cons and back to prim should be idempotent
+                let mut u = State::<P, E, S>::new(&stiffened_config());
+                for (j, p) in [p0, p1, p2].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                u.update_prim().unwrap();
+                u.update_c_sound();
+                assert_relative_eq!(u.cent.prim, u0_prim, max_relative = 1.0e-9);
+                let c_sound_expect = (4.4 * (p2 + 6.0e8) / p0).sqrt();
+                assert_relative_eq!(u.cent.c_sound[0], c_sound_expect, max_relative = 1.0e-9);
+            }
+        }
+    }
+
+    mod euler2dadiabatic {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(Euler2DAdiabatic);
+        proptest! {
+            #[test]
+            fn conversion(
+                p0 in 0.1f64..10.0, p1 in -10.0f64..10.0, p2 in -10.0f64..10.0, p3 in 0.1f64..10.0
+            ) {
+                // converting to cons and back to prim should be idempotent
+                let mut u = State::<P, E, S>::new(&physics_config());
+                for (j, p) in [p0, p1, p2, p3].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                u.update_prim().unwrap();
+                assert_relative_eq!(u.cent.prim.row(0), u0_prim.row(0), max_relative = 1.0e-12);
+                assert_relative_eq!(u.cent.prim.row(1), u0_prim.row(1), max_relative = 1.0e-12);
+                assert_relative_eq!(u.cent.prim.row(2), u0_prim.row(2), max_relative = 1.0e-12);
+                assert_relative_eq!(u.cent.prim.row(3), u0_prim.row(3), max_relative = 1.0e-8);
+            }
+        }
+    }
+    mod euler2disot {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(Euler2DIsot);
+        proptest! {
+            #[test]
+            fn conversion(
+                p0 in 0.1f64..100_000.0, p1 in -100_000.0f64..100_000.0, p2 in -100_000.0f64..100_000.0
+            ) {
+                // converting to cons and back to prim should be idempotent
+                let mut u = State::<P, E, S>::new(&physics_config());
+                for (j, p) in [p0, p1, p2].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                u.update_prim().unwrap();
+                assert_relative_eq!(u.cent.prim, u0_prim, max_relative = 1.0e-12);
+            }
+        }
+    }
+
+    mod euler1dadiabaticscalars {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(Euler1DAdiabatic, 2);
+        proptest! {
+            #[test]
+            fn conversion(
+                p0 in 0.1f64..10.0, p1 in -10.0f64..10.0, p2 in 0.1f64..10.0, x0 in 0.0f64..1.0, x1 in 0.0f64..1.0
+            ) {
+                // converting to cons and back to prim should be idempotent
+                let mut u = State::<P, E, S>::new(&physics_config());
+                for (j, p) in [p0, p1, p2, x0, x1].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                assert_relative_eq!(u.cent.cons.row(P::JSCALARS + 1), u.cent.cons.row(P::JRHO).mapv(|rho| rho * x1), max_relative = 1.0e-12);
+                u.update_prim().unwrap();
+                assert_relative_eq!(u.cent.prim, u0_prim, max_relative = 1.0e-12);
+                assert_relative_eq!(P::scalar(&u.cent, 0), u0_prim.row(3), max_relative = 1.0e-12);
+            }
+        }
+    }
+
+    mod euler1disotscalars {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(Euler1DIsot, 1);
+        proptest! {
+            #[test]
+            fn conversion(p0 in 0.1f64..100_000.0, p1 in -100_000.0f64..100_000.0, x0 in 0.0f64..1.0) {
+                // converting to cons and back to prim should be idempotent
+                let mut u = State::<P, E, S>::new(&physics_config());
+                for (j, p) in [p0, p1, x0].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                u.update_prim().unwrap();
+                assert_relative_eq!(u.cent.prim, u0_prim, max_relative = 1.0e-12);
+                assert_eq!(u.cent.n_scalars, 1);
+            }
+        }
+    }
+
+    mod idealmhd {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(IdealMhd);
+        fn mhd_config() -> PhysicsConfig {
+            PhysicsConfig {
+                adiabatic_index: 2.0,
+                units_mode: UnitsMode::SI,
+                systems: SystemsConfig {
+                    mhd: MhdConfig { b_xi: 0.75 },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }
+        proptest! {
+            #[test]
+            fn conversion(
+                p0 in 0.1f64..10.0, p1 in -10.0f64..10.0, p2 in -10.0f64..10.0, p3 in -10.0f64..10.0,
+                p4 in -10.0f64..10.0, p5 in -10.0f64..10.0, p6 in 0.1f64..10.0
+            ) {
+                // converting to cons and back to prim should be idempotent
+                let mut u = State::<P, E, S>::new(&mhd_config());
+                for (j, p) in [p0, p1, p2, p3, p4, p5, p6].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                u.update_prim().unwrap();
+                for j in 0..P::JPRESSURE {
+                    assert_relative_eq!(u.cent.prim.row(j), u0_prim.row(j), max_relative = 1.0e-12);
+                }
+                assert_relative_eq!(u.cent.prim.row(P::JPRESSURE), u0_prim.row(P::JPRESSURE), max_relative = 1.0e-8);
+
+                // the magnetosonic waves enclose the Alfven waves, and the fast waves are faster
+                // than sound
+                u.update_derived_variables();
+                for j in 0..E - 1 {
+                    assert!(u.cent.eigen_vals[[j, 0]] <= u.cent.eigen_vals[[j + 1, 0]]);
+                }
+                assert!(u.cent.eigen_max()[0] - p1 >= u.cent.c_sound[0]);
+            }
+        }
+    }
+
+    mod srhd {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(Srhd);
+        proptest! {
+            #[test]
+            fn conversion(p0 in 0.1f64..10.0, p1 in -0.99f64..0.99, p2 in 0.01f64..100.0) {
+                // converting to cons and back to prim should be idempotent, even when starting
+                // the recovery far away from the actual pressure
+                let mut u = State::<P, E, S>::new(&physics_config());
+                for (j, p) in [p0, p1, p2].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                u.cent.prim[[P::JPRESSURE, 0]] = 1.0e-3 * p2;
+                u.cent.prim[[P::JPRESSURE, 1]] = 1.0e3 * p2;
+                u.update_prim().unwrap();
+                assert_relative_eq!(u.cent.prim, u0_prim, max_relative = 1.0e-8, epsilon = 1.0e-12);
+            }
+        }
+
+        #[test]
+        fn failed_recovery_is_an_error() {
+            // more momentum than energy does not belong to any physical state
+            let mut u = State::<P, E, S>::new(&physics_config());
+            u.cent.cons.row_mut(P::JRHO).fill(1.0);
+            u.cent.cons.row_mut(P::JXI).fill(10.0);
+            u.cent.cons.row_mut(P::JPRESSURE).fill(0.1);
+            let err = u.update_prim().unwrap_err();
+            assert!(format!("{:?}", err).contains("Recovering the primitive variables in cell 0"));
+        }
+    }
+    mod shallowwater {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(ShallowWater);
+        proptest! {
+            #[test]
+            fn conversion(p0 in 0.1f64..100.0, p1 in -100.0f64..100.0, b in -10.0f64..10.0) {
+                // converting to cons and back to prim should be idempotent on top of any bottom
+                let mut u = State::<P, E, S>::new(&physics_config());
+                u.cent.prim.row_mut(0).fill(p0);
+                u.cent.prim.row_mut(1).fill(p1);
+                u.cent.bottom.fill(b);
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                assert_relative_eq!(u.cent.cons.row(0), u0_prim.row(0).mapv(|h| h + b), max_relative = 1.0e-12);
+                u.update_prim().unwrap();
+                assert_relative_eq!(u.cent.prim, u0_prim, max_relative = 1.0e-10, epsilon = 1.0e-12);
+            }
+        }
+
+        #[test]
+        fn dry_cells_are_at_rest() {
+            // the surface may dip below the bottom a bit, which has to be clipped instead of
+            // turning into a negative depth
+            let mut u = State::<P, E, S>::new(&physics_config());
+            u.cent.bottom.fill(1.0);
+            u.cent.cons[[P::JRHO, 0]] = 1.0 - 1.0e-14;
+            u.cent.cons[[P::JRHO, 1]] = 1.0 + 1.0e-14;
+            u.cent.cons.row_mut(P::JXI).fill(1.0e-3);
+            u.update_prim().unwrap();
+            assert_eq!(u.cent.prim[[P::JRHO, 0]], 0.0);
+            assert!(u.cent.prim.row(P::JXI).iter().all(|&v| v == 0.0));
+        }
+    }
+
+    mod linearadvection {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(LinearAdvection);
+        proptest! {
+            #[test]
+            fn flux_and_eigen_vals(q in -100.0f64..100.0, a in -10.0f64..10.0) {
+                // the quantity is carried with the advection velocity, which is also the only wave
+                // speed
+                let mut u = State::<P, E, S>::new(&PhysicsConfig {
+                    systems: SystemsConfig { advection: AdvectionConfig { velocity: a }, ..Default::default() },
+                    ..physics_config()
+                });
+                u.cent.prim.fill(q);
+                u.update_cons();
+                assert_relative_eq!(u.cent.cons, u.cent.prim);
+                u.update_derived_variables();
+                u.update_flux();
+                assert!(u.cent.eigen_vals.iter().all(|&ev| ev == a));
+                assert_relative_eq!(u.cent.flux.row(0), u.cent.cons.row(0).mapv(|q| a * q), max_relative = 1.0e-12);
+            }
+        }
+    }
+
+    mod burgers {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(Burgers);
+        proptest! {
+            #[test]
+            fn flux_and_eigen_vals(v in -100.0f64..100.0) {
+                // the velocity carries itself, so it is also the only wave speed
+                let mut u = State::<P, E, S>::new(&physics_config());
+                u.cent.prim.fill(v);
+                u.update_cons();
+                assert_relative_eq!(u.cent.cons, u.cent.prim);
+                u.update_derived_variables();
+                u.update_flux();
+                assert!(u.cent.eigen_vals.iter().all(|&ev| ev == v));
+                assert_relative_eq!(u.cent.flux.row(0), u.cent.cons.row(0).mapv(|v| 0.5 * v * v), max_relative = 1.0e-12);
+            }
+        }
+    }
+
+    mod dustygas {
+        use super::*;
+        use approx::assert_relative_eq;
+        set_Physics_and_E!(DustyGas);
+        proptest! {
+            #[test]
+            fn conversion(
+                p0 in 0.1f64..10.0,
+                p1 in -10.0f64..10.0,
+                p2 in 0.1f64..10.0,
+                p3 in 0.1f64..10.0,
+                p4 in -10.0f64..10.0
+            ) {
+                // converting to cons and back to prim should be idempotent
+                let mut u = State::<P, E, S>::new(&physics_config());
+                for (j, p) in [p0, p1, p2, p3, p4].into_iter().enumerate() {
+                    u.cent.prim.row_mut(j).fill(p);
+                }
+                let u0_prim = u.cent.prim.clone();
+                u.update_cons();
+                u.update_prim().unwrap();
+                assert_relative_eq!(u.cent.prim, u0_prim, max_relative = 1.0e-8);
+            }
+
+            #[test]
+            fn eigen_vals_are_sorted(xi_vel in -10.0f64..10.0, xi_vel_dust in -10.0f64..10.0) {
+                // the extremal eigen values may come from either fluid
+                let mut u = State::<P, E, S>::new(&physics_config());
+                u.cent.prim.fill(1.0);
+                u.cent.prim.row_mut(P::JXI).fill(xi_vel);
+                u.cent.prim.row_mut(P::JXI_DUST).fill(xi_vel_dust);
+                u.update_cons();
+                u.update_derived_variables();
+                let c_sound = u.cent.c_sound[0];
+                for i in 0..S {
+                    let eigen_vals = u.cent.eigen_vals.column(i);
+                    assert!(eigen_vals.windows(2).into_iter().all(|w| w[0] <= w[1]));
+                    assert_eq!(eigen_vals[0], (xi_vel - c_sound).min(xi_vel_dust));
+                    assert_eq!(eigen_vals[E - 1], (xi_vel + c_sound).max(xi_vel_dust));
+                    assert!(eigen_vals.iter().filter(|&&ev| ev == xi_vel_dust).count() >= 2);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,321 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [EquationOfState] trait, its implementers, and the [EosParams] they are fed with.
+
+use crate::{
+    config::physicsconfig::{EosConfig, EosInterpolation},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::{
+    eyre::{bail, Context},
+    Result,
+};
+use std::fmt::Debug;
+
+pub use tabulated::{EosTable, Tabulated};
+
+pub mod tabulated;
+
+/// The parameters of the equation of state, taken from the [PhysicsConfig] and carried by
+/// [Variables].
+///
+/// Parameters that the configured [EosConfig] does not use are set to 0.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EosParams {
+    /// Ratio of specific heats
+    pub gamma: f64,
+
+    /// The polytropic constant `K` for [Polytropic]
+    pub polytropic_constant: f64,
+
+    /// The stiffening pressure `p_inf` for [StiffenedGas]
+    pub p_inf: f64,
+
+    /// The table for [Tabulated]
+    pub table: Option<EosTable>,
+
+    /// How [Tabulated] interpolates in its table
+    pub interpolation: EosInterpolation,
+}
+
+unsafe impl Send for EosParams {}
+unsafe impl Sync for EosParams {}
+
+impl EosParams {
+    /// Constructs a new [EosParams] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `physics_config` - Contains the adiabatic index and the [EosConfig]
+    pub fn new(physics_config: &PhysicsConfig) -> Self {
+        let mut eos = Self {
+            gamma: physics_config.adiabatic_index,
+            ..Default::default()
+        };
+        match &physics_config.eos {
+            EosConfig::IdealGas => (),
+            EosConfig::Polytropic { polytropic_constant } => eos.polytropic_constant = *polytropic_constant,
+            EosConfig::StiffenedGas { p_inf } => eos.p_inf = *p_inf,
+            EosConfig::Tabulated { table, interpolation } => {
+                eos.table = Some(table.clone());
+                eos.interpolation = *interpolation;
+            },
+        };
+        eos
+    }
+}
+
+/// Trait for equations of state, i.e. closing the adiabatic Euler equations by relating the pressure
+/// to the mass density and the internal energy.
+///
+/// Adiabatic [Physics](crate::state::Physics) types, like
+/// [Euler1DAdiabatic](crate::state::Euler1DAdiabatic), are generic over this trait, and default to
+/// [IdealGas].
+///
+/// Note that all internal energies here are per unit volume, i.e. the energy density minus the
+/// kinetic energy density.
+pub trait EquationOfState: Debug + Clone + Default + PartialEq + 'static {
+    /// Returns the name of the equation of state
+    fn name() -> String;
+
+    /// Checks that `eos_config` is the variant of [EosConfig] that configures this type
+    fn validate_config(eos_config: &EosConfig) -> Result<()>;
+
+    /// Returns the pressure for mass density `rho` and internal energy `e_int`
+    fn pressure(rho: f64, e_int: f64, eos: &EosParams) -> f64;
+
+    /// Returns the internal energy for mass density `rho` and `pressure`
+    fn internal_energy(rho: f64, pressure: f64, eos: &EosParams) -> f64;
+
+    /// Returns the speed of sound for mass density `rho`, internal energy `e_int`, and `pressure`
+    fn c_sound(rho: f64, e_int: f64, pressure: f64, eos: &EosParams) -> f64;
+
+    /// Checks whether this equation of state is valid for mass density `rho` and internal energy
+    /// `e_int`
+    fn validate_state(_: f64, _: f64, _: &EosParams) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Ideal gas, i.e. `p = (gamma - 1) * e_int` and `c_sound = sqrt(gamma * p / rho)`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdealGas;
+
+unsafe impl Send for IdealGas {}
+unsafe impl Sync for IdealGas {}
+
+impl EquationOfState for IdealGas {
+    fn name() -> String {
+        "IdealGas".to_string()
+    }
+
+    fn validate_config(eos_config: &EosConfig) -> Result<()> {
+        match eos_config {
+            EosConfig::IdealGas => Ok(()),
+            x => bail!("IdealGas needs to be configured with EosConfig::IdealGas! Got {:?}", x),
+        }
+    }
+
+    #[inline(always)]
+    fn pressure(_: f64, e_int: f64, eos: &EosParams) -> f64 {
+        (eos.gamma - 1.0) * e_int
+    }
+
+    #[inline(always)]
+    fn internal_energy(_: f64, pressure: f64, eos: &EosParams) -> f64 {
+        pressure * (eos.gamma - 1.0).recip()
+    }
+
+    #[inline(always)]
+    fn c_sound(rho: f64, _: f64, pressure: f64, eos: &EosParams) -> f64 {
+        (eos.gamma * pressure / rho).sqrt()
+    }
+}
+
+/// Polytropic gas, i.e. `p = K * rho^gamma` and `c_sound = sqrt(gamma * p / rho)`.
+///
+/// The pressure only depends on the mass density, so the energy equation does not act back on the
+/// flow. The internal energy is set to `p / (gamma - 1)` when converting primitive to conservative
+/// variables, so that the total energy stays meaningful.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polytropic;
+
+unsafe impl Send for Polytropic {}
+unsafe impl Sync for Polytropic {}
+
+impl EquationOfState for Polytropic {
+    fn name() -> String {
+        "Polytropic".to_string()
+    }
+
+    fn validate_config(eos_config: &EosConfig) -> Result<()> {
+        match eos_config {
+            EosConfig::Polytropic { .. } => Ok(()),
+            x => bail!(
+                "Polytropic needs to be configured with EosConfig::Polytropic! Got {:?}",
+                x
+            ),
+        }
+    }
+
+    #[inline(always)]
+    fn pressure(rho: f64, _: f64, eos: &EosParams) -> f64 {
+        eos.polytropic_constant * rho.powf(eos.gamma)
+    }
+
+    #[inline(always)]
+    fn internal_energy(_: f64, pressure: f64, eos: &EosParams) -> f64 {
+        pressure * (eos.gamma - 1.0).recip()
+    }
+
+    #[inline(always)]
+    fn c_sound(rho: f64, _: f64, pressure: f64, eos: &EosParams) -> f64 {
+        (eos.gamma * pressure / rho).sqrt()
+    }
+}
+
+/// Stiffened gas, i.e. `p = (gamma - 1) * e_int - gamma * p_inf` and
+/// `c_sound = sqrt(gamma * (p + p_inf) / rho)`.
+///
+/// This models liquids, like water with `gamma = 4.4` and `p_inf = 6e8 Pa`, and reduces to
+/// [IdealGas] for `p_inf = 0`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StiffenedGas;
+
+unsafe impl Send for StiffenedGas {}
+unsafe impl Sync for StiffenedGas {}
+
+impl EquationOfState for StiffenedGas {
+    fn name() -> String {
+        "StiffenedGas".to_string()
+    }
+
+    fn validate_config(eos_config: &EosConfig) -> Result<()> {
+        match eos_config {
+            EosConfig::StiffenedGas { .. } => Ok(()),
+            x => bail!(
+                "StiffenedGas needs to be configured with EosConfig::StiffenedGas! Got {:?}",
+                x
+            ),
+        }
+    }
+
+    #[inline(always)]
+    fn pressure(_: f64, e_int: f64, eos: &EosParams) -> f64 {
+        (eos.gamma - 1.0) * e_int - eos.gamma * eos.p_inf
+    }
+
+    #[inline(always)]
+    fn internal_energy(_: f64, pressure: f64, eos: &EosParams) -> f64 {
+        (pressure + eos.gamma * eos.p_inf) * (eos.gamma - 1.0).recip()
+    }
+
+    #[inline(always)]
+    fn c_sound(rho: f64, _: f64, pressure: f64, eos: &EosParams) -> f64 {
+        (eos.gamma * (pressure + eos.p_inf) / rho).sqrt()
+    }
+}
+
+/// Updates the speed of sound in `vars` through the equation of state `Q`, assuming that both
+/// `vars.prim` and `vars.cons` are up to date.
+///
+/// # Arguments
+///
+/// * `vars` - The [Variables] to update
+/// * `j_rho` - The row index of the mass density
+/// * `j_pressure` - The row index of the pressure and the energy
+/// * `j_vels` - The row indexes of all velocities, needed to get the internal from the total energy
+#[inline(always)]
+pub fn update_c_sound<Q: EquationOfState, const E: usize, const S: usize>(
+    vars: &mut Variables<E, S>,
+    j_rho: usize,
+    j_pressure: usize,
+    j_vels: &[usize],
+) {
+    for i in 0..vars.n_all() {
+        vars.c_sound[i] = Q::c_sound(
+            vars.prim[[j_rho, i]],
+            internal_energy(vars, i, j_pressure, j_vels),
+            vars.prim[[j_pressure, i]],
+            &vars.eos,
+        );
+    }
+}
+
+/// Checks whether the equation of state `Q` is valid for every cell in `vars`, see
+/// [EquationOfState::validate_state]; takes the same arguments as [update_c_sound].
+pub fn validate<Q: EquationOfState, const E: usize, const S: usize>(
+    vars: &Variables<E, S>,
+    j_rho: usize,
+    j_pressure: usize,
+    j_vels: &[usize],
+) -> Result<()> {
+    for i in 0..vars.n_all() {
+        Q::validate_state(
+            vars.prim[[j_rho, i]],
+            internal_energy(vars, i, j_pressure, j_vels),
+            &vars.eos,
+        )
+        .with_context(|| format!("Validating the equation of state {} in cell {}", Q::name(), i))?;
+    }
+    Ok(())
+}
+
+/// Returns the internal energy in cell `i`, i.e. the total minus the kinetic energy
+#[inline(always)]
+fn internal_energy<const E: usize, const S: usize>(
+    vars: &Variables<E, S>,
+    i: usize,
+    j_pressure: usize,
+    j_vels: &[usize],
+) -> f64 {
+    let e_kin: f64 = j_vels.iter().map(|&j| vars.cons[[j, i]] * vars.prim[[j, i]]).sum();
+    vars.cons[[j_pressure, i]] - 0.5 * e_kin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use proptest::prelude::*;
+
+    const EOS: EosParams = EosParams {
+        gamma: 4.4,
+        polytropic_constant: 2.0,
+        p_inf: 6.0e8,
+        table: None,
+        interpolation: EosInterpolation::Bilinear,
+    };
+
+    proptest! {
+        #[test]
+        fn pressure_and_internal_energy_are_inverse(rho in 0.1f64..1000.0, p in 1.0e5f64..1.0e9) {
+            let e_ideal = IdealGas::internal_energy(rho, p, &EOS);
+            assert_relative_eq!(IdealGas::pressure(rho, e_ideal, &EOS), p, max_relative = 1.0e-12);
+            let e_stiff = StiffenedGas::internal_energy(rho, p, &EOS);
+            assert_relative_eq!(StiffenedGas::pressure(rho, e_stiff, &EOS), p, max_relative = 1.0e-6);
+        }
+    }
+
+    #[test]
+    fn stiffened_gas_without_stiffening_is_an_ideal_gas() {
+        let eos = EosParams { p_inf: 0.0, ..EOS };
+        let (rho, e_int) = (2.0, 3.0);
+        let p = IdealGas::pressure(rho, e_int, &eos);
+        assert_eq!(StiffenedGas::pressure(rho, e_int, &eos), p);
+        assert_eq!(
+            StiffenedGas::c_sound(rho, e_int, p, &eos),
+            IdealGas::c_sound(rho, e_int, p, &eos)
+        );
+    }
+
+    #[test]
+    fn polytropic_pressure_ignores_the_internal_energy() {
+        let p = Polytropic::pressure(2.0, 1.0, &EOS);
+        assert_eq!(Polytropic::pressure(2.0, 100.0, &EOS), p);
+        assert_relative_eq!(p, 2.0 * 2.0f64.powf(4.4), max_relative = 1.0e-14);
+    }
+}
@@ -0,0 +1,496 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports [EosTable] and the [Tabulated] equation of state, which interpolates in it.
+
+use super::{EosParams, EquationOfState};
+use crate::config::physicsconfig::{EosConfig, EosInterpolation};
+use color_eyre::{
+    eyre::{bail, ensure, eyre, Context},
+    Result,
+};
+use ndarray::Array2;
+use serde::Serialize;
+use std::{fs, sync::Arc};
+
+/// The first bytes of a binary [EosTable] file
+const MAGIC: &[u8; 8] = b"CORRIEOS";
+
+/// A rectilinear table of the pressure and the speed of sound over the mass density and the
+/// specific internal energy, i.e. the internal energy per unit mass.
+///
+/// # File formats
+///
+/// [EosTable::from_file] reads both text and binary files, and tells them apart by the first
+/// bytes of the file.
+///
+/// Text files start with a line containing the number of mass densities `n_rho` and energies
+/// `n_e`, followed by `n_rho * n_e` lines containing `rho e pressure c_sound`, where `e` varies
+/// fastest. Empty lines and lines starting with `#` are ignored:
+///
+/// ```text
+/// # rho e pressure c_sound
+/// 2 2
+/// 0.1 1.0 0.04 0.75
+/// 0.1 2.0 0.08 1.06
+/// 1.0 1.0 0.4  0.75
+/// 1.0 2.0 0.8  1.06
+/// ```
+///
+/// Binary files start with the bytes `CORRIEOS`, followed by `n_rho` and `n_e` as little endian
+/// `u64`, followed by the same `n_rho * n_e` records of four little endian `f64` as the text
+/// format. [EosTable::write_binary] writes these files.
+///
+/// Both axes need to have at least 2 entries, and have to be strictly increasing.
+///
+/// Only the path to the file is serialised into the metadata of the output, not the table itself.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EosTable {
+    /// The path of the file this table was read from
+    pub path: String,
+
+    /// The contents of the table, shared between all the clones of this table
+    #[serde(skip)]
+    data: Arc<EosTableData>,
+}
+
+/// The contents of an [EosTable]
+#[derive(Debug, Default, PartialEq)]
+struct EosTableData {
+    /// The mass densities along the first axis
+    rho: Vec<f64>,
+
+    /// The specific internal energies along the second axis
+    e: Vec<f64>,
+
+    /// The pressure at each pair of `rho` and `e`
+    pressure: Array2<f64>,
+
+    /// The speed of sound at each pair of `rho` and `e`
+    c_sound: Array2<f64>,
+}
+
+unsafe impl Send for EosTable {}
+unsafe impl Sync for EosTable {}
+
+impl EosTable {
+    /// Reads an [EosTable] from the text or binary file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file holding the table
+    pub fn from_file(path: &str) -> Result<Self> {
+        let bytes = fs::read(path).with_context(|| format!("Reading the EOS table {}", path))?;
+        let records = if bytes.starts_with(MAGIC) {
+            read_binary(&bytes)
+        } else {
+            read_text(&String::from_utf8(bytes).context("Text EOS tables need to be valid UTF-8")?)
+        }
+        .with_context(|| format!("Parsing the EOS table {}", path))?;
+        Self::from_records(path, records).with_context(|| format!("Building the EOS table {}", path))
+    }
+
+    /// Writes this table to `path` in the binary format.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write to
+    pub fn write_binary(&self, path: &str) -> Result<()> {
+        let (n_rho, n_e) = (self.data.rho.len(), self.data.e.len());
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend((n_rho as u64).to_le_bytes());
+        bytes.extend((n_e as u64).to_le_bytes());
+        for k in 0..n_rho {
+            for l in 0..n_e {
+                for x in [
+                    self.data.rho[k],
+                    self.data.e[l],
+                    self.data.pressure[[k, l]],
+                    self.data.c_sound[[k, l]],
+                ] {
+                    bytes.extend(x.to_le_bytes());
+                }
+            }
+        }
+        fs::write(path, bytes).with_context(|| format!("Writing the EOS table {}", path))
+    }
+
+    /// Returns the covered ranges of the mass density and the specific internal energy
+    pub fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let (rho, e) = (&self.data.rho, &self.data.e);
+        ((rho[0], rho[rho.len() - 1]), (e[0], e[e.len() - 1]))
+    }
+
+    /// Interpolates the pressure at mass density `rho` and specific internal energy `e`.
+    ///
+    /// Queries outside of the table are clamped onto its edges.
+    pub fn pressure(&self, rho: f64, e: f64, interpolation: EosInterpolation) -> f64 {
+        self.interpolate(&self.data.pressure, rho, e, interpolation)
+    }
+
+    /// Interpolates the speed of sound at mass density `rho` and specific internal energy `e`.
+    ///
+    /// Queries outside of the table are clamped onto its edges.
+    pub fn c_sound(&self, rho: f64, e: f64, interpolation: EosInterpolation) -> f64 {
+        self.interpolate(&self.data.c_sound, rho, e, interpolation)
+    }
+
+    /// Finds the specific internal energy at which the pressure takes the value `pressure` for the
+    /// mass density `rho` through bisection, assuming that the pressure grows with the energy.
+    ///
+    /// The result is clamped onto the edges of the table.
+    pub fn specific_internal_energy(&self, rho: f64, pressure: f64, interpolation: EosInterpolation) -> f64 {
+        let (_, (mut e_low, mut e_high)) = self.bounds();
+        for _ in 0..100 {
+            let e_mid = 0.5 * (e_low + e_high);
+            if self.pressure(rho, e_mid, interpolation) < pressure {
+                e_low = e_mid;
+            } else {
+                e_high = e_mid;
+            }
+            if e_high - e_low <= f64::EPSILON * e_high.abs() {
+                break;
+            }
+        }
+        0.5 * (e_low + e_high)
+    }
+
+    /// Checks whether `rho` and `e` lie within the table
+    pub fn validate_query(&self, rho: f64, e: f64) -> Result<()> {
+        let ((rho_min, rho_max), (e_min, e_max)) = self.bounds();
+        ensure!(
+            (rho_min..=rho_max).contains(&rho) && (e_min..=e_max).contains(&e),
+            "Queried the EOS table {} outside of its range! Got rho = {} and e = {}, but the table covers \
+            {} <= rho <= {} and {} <= e <= {}",
+            self.path,
+            rho,
+            e,
+            rho_min,
+            rho_max,
+            e_min,
+            e_max
+        );
+        Ok(())
+    }
+
+    fn from_records(path: &str, records: Vec<[f64; 4]>) -> Result<Self> {
+        ensure!(!records.is_empty(), "The table does not contain any records!");
+        let n_rho = records.iter().filter(|r| r[1] == records[0][1]).count();
+        ensure!(
+            n_rho >= 2 && records.len().is_multiple_of(n_rho) && records.len() / n_rho >= 2,
+            "The table needs at least 2 entries along both axes, and n_rho * n_e records! Got {} records",
+            records.len()
+        );
+        let n_e = records.len() / n_rho;
+        let rho: Vec<f64> = (0..n_rho).map(|k| records[k * n_e][0]).collect();
+        let e: Vec<f64> = (0..n_e).map(|l| records[l][1]).collect();
+        for (name, axis) in [("rho", &rho), ("e", &e)] {
+            ensure!(
+                axis.windows(2).all(|w| w[0] < w[1]),
+                "The {} axis of the table needs to be strictly increasing! Got {:?}",
+                name,
+                axis
+            );
+        }
+        let mut pressure = Array2::zeros((n_rho, n_e));
+        let mut c_sound = Array2::zeros((n_rho, n_e));
+        for (n, record) in records.iter().enumerate() {
+            let (k, l) = (n / n_e, n % n_e);
+            ensure!(
+                record[0] == rho[k] && record[1] == e[l],
+                "Record {} does not lie on a rectilinear grid, expected rho = {} and e = {}! Got {:?}",
+                n,
+                rho[k],
+                e[l],
+                record
+            );
+            ensure!(
+                record.iter().all(|x| x.is_finite()),
+                "Record {} contains non-finite values! Got {:?}",
+                n,
+                record
+            );
+            pressure[[k, l]] = record[2];
+            c_sound[[k, l]] = record[3];
+        }
+        Ok(Self {
+            path: path.to_string(),
+            data: Arc::new(EosTableData {
+                rho,
+                e,
+                pressure,
+                c_sound,
+            }),
+        })
+    }
+
+    fn interpolate(&self, values: &Array2<f64>, rho: f64, e: f64, interpolation: EosInterpolation) -> f64 {
+        let (rho_axis, e_axis) = (&self.data.rho, &self.data.e);
+        let ((rho_min, rho_max), (e_min, e_max)) = self.bounds();
+        let (rho, e) = (rho.clamp(rho_min, rho_max), e.clamp(e_min, e_max));
+        let (k, l) = (interval(rho_axis, rho), interval(e_axis, e));
+        match interpolation {
+            EosInterpolation::Bilinear => {
+                let t_rho = (rho - rho_axis[k]) / (rho_axis[k + 1] - rho_axis[k]);
+                let t_e = (e - e_axis[l]) / (e_axis[l + 1] - e_axis[l]);
+                (1.0 - t_rho) * ((1.0 - t_e) * values[[k, l]] + t_e * values[[k, l + 1]])
+                    + t_rho * ((1.0 - t_e) * values[[k + 1, l]] + t_e * values[[k + 1, l + 1]])
+            },
+            EosInterpolation::Bicubic => {
+                // interpolate along e in the rows needed for the derivatives along rho, and then
+                // along rho
+                let along_e = |kk: usize| hermite(e_axis, |ll| values[[kk, ll]], l, e);
+                let rows_needed = k.saturating_sub(1)..(k + 3).min(rho_axis.len());
+                let mut rows = [0.0; 4];
+                for kk in rows_needed.clone() {
+                    rows[kk + 1 - k] = along_e(kk);
+                }
+                hermite(rho_axis, |kk| rows[kk + 1 - k], k, rho)
+            },
+        }
+    }
+}
+
+/// Returns the index `k` of the interval `[axis[k], axis[k + 1]]` containing `x`
+fn interval(axis: &[f64], x: f64) -> usize {
+    axis.partition_point(|&a| a <= x).clamp(1, axis.len() - 1) - 1
+}
+
+/// Cubic Hermite interpolation at `x` in the interval `[axis[k], axis[k + 1]]`, with derivatives
+/// from finite differences of the values `f`
+fn hermite(axis: &[f64], f: impl Fn(usize) -> f64, k: usize, x: f64) -> f64 {
+    let n = axis.len();
+    let derivative = |m: usize| {
+        let (west, east) = (m.saturating_sub(1), (m + 1).min(n - 1));
+        (f(east) - f(west)) / (axis[east] - axis[west])
+    };
+    let h = axis[k + 1] - axis[k];
+    let t = (x - axis[k]) / h;
+    let (t2, t3) = (t * t, t * t * t);
+    (2.0 * t3 - 3.0 * t2 + 1.0) * f(k)
+        + (t3 - 2.0 * t2 + t) * h * derivative(k)
+        + (-2.0 * t3 + 3.0 * t2) * f(k + 1)
+        + (t3 - t2) * h * derivative(k + 1)
+}
+
+fn read_text(text: &str) -> Result<Vec<[f64; 4]>> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(n, line)| (n + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    let (n_header, header) = lines.next().ok_or_else(|| eyre!("The table is empty!"))?;
+    let header: Vec<usize> = header
+        .split_whitespace()
+        .map(|x| x.parse())
+        .collect::<std::result::Result<_, _>>()
+        .with_context(|| format!("Parsing the header in line {}", n_header))?;
+    if header.len() != 2 {
+        bail!(
+            "The header in line {} needs to contain n_rho and n_e! Got {:?}",
+            n_header,
+            header
+        );
+    }
+    let records = lines
+        .map(|(n, line)| {
+            let record: Vec<f64> = line
+                .split_whitespace()
+                .map(|x| x.parse())
+                .collect::<std::result::Result<_, _>>()
+                .with_context(|| format!("Parsing line {}", n))?;
+            <[f64; 4]>::try_from(record).map_err(|record| {
+                eyre!(
+                    "Line {} needs to contain rho, e, pressure, c_sound! Got {:?}",
+                    n,
+                    record
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(
+        records.len() == header[0] * header[1],
+        "The header announces {} x {} records, but the table contains {}!",
+        header[0],
+        header[1],
+        records.len()
+    );
+    Ok(records)
+}
+
+fn read_binary(bytes: &[u8]) -> Result<Vec<[f64; 4]>> {
+    let word = |n: usize| -> Result<[u8; 8]> {
+        bytes
+            .get(MAGIC.len() + 8 * n..MAGIC.len() + 8 * (n + 1))
+            .map(|w| w.try_into().unwrap())
+            .ok_or_else(|| eyre!("The binary table ends after {} bytes!", bytes.len()))
+    };
+    let (n_rho, n_e) = (
+        u64::from_le_bytes(word(0)?) as usize,
+        u64::from_le_bytes(word(1)?) as usize,
+    );
+    let n_records = n_rho
+        .checked_mul(n_e)
+        .ok_or_else(|| eyre!("The header announces too many records!"))?;
+    ensure!(
+        bytes.len() == MAGIC.len() + 16 + 32 * n_records,
+        "The header announces {} x {} records, which needs {} bytes, but the table has {} bytes!",
+        n_rho,
+        n_e,
+        MAGIC.len() + 16 + 32 * n_records,
+        bytes.len()
+    );
+    (0..n_records)
+        .map(|n| {
+            let mut record = [0.0; 4];
+            for (m, x) in record.iter_mut().enumerate() {
+                *x = f64::from_le_bytes(word(2 + 4 * n + m)?);
+            }
+            Ok(record)
+        })
+        .collect()
+}
+
+/// Tabulated equation of state, which interpolates the pressure and the speed of sound in an
+/// [EosTable] over the mass density and the specific internal energy `e_int / rho`.
+///
+/// The internal energy is found by inverting the tabulated pressure, assuming that it grows with
+/// the energy. Queries outside of the table are clamped onto its edges, and reported when
+/// validating the [State](crate::state::State).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Tabulated;
+
+unsafe impl Send for Tabulated {}
+unsafe impl Sync for Tabulated {}
+
+impl EquationOfState for Tabulated {
+    fn name() -> String {
+        "Tabulated".to_string()
+    }
+
+    fn validate_config(eos_config: &EosConfig) -> Result<()> {
+        match eos_config {
+            EosConfig::Tabulated { .. } => Ok(()),
+            x => bail!(
+                "Tabulated needs to be configured with EosConfig::Tabulated! Got {:?}",
+                x
+            ),
+        }
+    }
+
+    #[inline(always)]
+    fn pressure(rho: f64, e_int: f64, eos: &EosParams) -> f64 {
+        table(eos).pressure(rho, e_int / rho, eos.interpolation)
+    }
+
+    #[inline(always)]
+    fn internal_energy(rho: f64, pressure: f64, eos: &EosParams) -> f64 {
+        rho * table(eos).specific_internal_energy(rho, pressure, eos.interpolation)
+    }
+
+    #[inline(always)]
+    fn c_sound(rho: f64, e_int: f64, _: f64, eos: &EosParams) -> f64 {
+        table(eos).c_sound(rho, e_int / rho, eos.interpolation)
+    }
+
+    fn validate_state(rho: f64, e_int: f64, eos: &EosParams) -> Result<()> {
+        table(eos).validate_query(rho, e_int / rho)
+    }
+}
+
+#[inline(always)]
+fn table(eos: &EosParams) -> &EosTable {
+    eos.table
+        .as_ref()
+        .expect("Tabulated needs EosParams::table, which is set through EosConfig::Tabulated!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::env::temp_dir;
+
+    const GAMMA: f64 = 1.4;
+
+    /// Writes a text table for an ideal gas, and returns its path
+    fn write_ideal_gas_table(name: &str, n_rho: usize, n_e: usize) -> String {
+        let mut text = format!("# ideal gas with gamma = {}\n{} {}\n", GAMMA, n_rho, n_e);
+        for k in 0..n_rho {
+            for l in 0..n_e {
+                let (rho, e) = (0.1 + k as f64 * 0.1, 1.0 + l as f64 * 0.25);
+                let pressure = (GAMMA - 1.0) * rho * e;
+                text += &format!("{} {} {} {}\n", rho, e, pressure, (GAMMA * pressure / rho).sqrt());
+            }
+        }
+        let path = temp_dir().join(name).to_str().unwrap().to_string();
+        fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn ideal_gas_table_is_interpolated() {
+        let table = EosTable::from_file(&write_ideal_gas_table("corries_eos_ideal.txt", 10, 13)).unwrap();
+        assert_eq!(table.bounds(), ((0.1, 1.0), (1.0, 4.0)));
+        let (rho, e) = (0.537, 2.345);
+        for interpolation in [EosInterpolation::Bilinear, EosInterpolation::Bicubic] {
+            // the pressure is bilinear in rho and e, so both interpolations reproduce it
+            let pressure = table.pressure(rho, e, interpolation);
+            assert_relative_eq!(pressure, (GAMMA - 1.0) * rho * e, max_relative = 1.0e-12);
+            assert_relative_eq!(
+                table.specific_internal_energy(rho, pressure, interpolation),
+                e,
+                max_relative = 1.0e-12
+            );
+        }
+        // the speed of sound is not, but bicubic interpolation gets closer
+        let c_sound = (GAMMA * (GAMMA - 1.0) * e).sqrt();
+        let error_bilinear = (table.c_sound(rho, e, EosInterpolation::Bilinear) - c_sound).abs();
+        let error_bicubic = (table.c_sound(rho, e, EosInterpolation::Bicubic) - c_sound).abs();
+        assert!(error_bicubic < error_bilinear);
+        assert!(error_bicubic < 1.0e-4 * c_sound);
+    }
+
+    #[test]
+    fn binary_tables_match_text_tables() {
+        let table = EosTable::from_file(&write_ideal_gas_table("corries_eos_binary.txt", 3, 4)).unwrap();
+        let path = temp_dir().join("corries_eos_binary.bin").to_str().unwrap().to_string();
+        table.write_binary(&path).unwrap();
+        let table_binary = EosTable::from_file(&path).unwrap();
+        assert_eq!(table_binary.data, table.data);
+    }
+
+    #[test]
+    fn broken_tables_are_rejected() {
+        let path = temp_dir().join("corries_eos_broken.txt").to_str().unwrap().to_string();
+        for text in [
+            // wrong number of records
+            "2 2\n0.1 1.0 1.0 1.0\n0.1 2.0 1.0 1.0\n0.2 1.0 1.0 1.0\n",
+            // not rectilinear
+            "2 2\n0.1 1.0 1.0 1.0\n0.1 2.0 1.0 1.0\n0.2 1.0 1.0 1.0\n0.2 3.0 1.0 1.0\n",
+            // decreasing axis
+            "2 2\n0.2 1.0 1.0 1.0\n0.2 2.0 1.0 1.0\n0.1 1.0 1.0 1.0\n0.1 2.0 1.0 1.0\n",
+            // too few columns
+            "2 2\n0.1 1.0 1.0\n0.1 2.0 1.0 1.0\n0.2 1.0 1.0 1.0\n0.2 2.0 1.0 1.0\n",
+        ] {
+            fs::write(&path, text).unwrap();
+            assert!(EosTable::from_file(&path).is_err());
+        }
+        assert!(EosTable::from_file("this/file/does/not/exist").is_err());
+    }
+
+    #[test]
+    fn queries_outside_of_the_table_are_reported() {
+        let table = EosTable::from_file(&write_ideal_gas_table("corries_eos_outside.txt", 3, 4)).unwrap();
+        assert!(table.validate_query(0.2, 1.5).is_ok());
+        assert!(table.validate_query(0.05, 1.5).is_err());
+        assert!(table.validate_query(0.2, 5.0).is_err());
+        // but interpolation still clamps onto the edges
+        assert_relative_eq!(
+            table.pressure(0.2, 5.0, EosInterpolation::Bilinear),
+            table.pressure(0.2, 1.75, EosInterpolation::Bilinear),
+            max_relative = 1.0e-12
+        );
+    }
+}
@@ -0,0 +1,404 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [ReactionNetwork] struct, which evolves the mass fractions of the species of
+//! [ReactingFlow](crate::state::ReactingFlow) systems.
+
+use crate::{units::Units, PhysicsConfig};
+use color_eyre::{eyre::ensure, Result};
+use ndarray::{Array1, Array2, ArrayView1};
+
+/// Maximum number of Newton iterations for a single backward Euler step
+const MAX_NEWTON_ITER: usize = 50;
+
+/// Smallest fraction of the time step width the network is sub-cycled with before giving up
+const MIN_SUBSTEP_FRACTION: f64 = 1.0e-9;
+
+/// Relative step width for the finite difference Jacobian
+const JACOBIAN_STEP: f64 = 1.0e-7;
+
+/// Smallest absolute step width for the finite difference Jacobian
+const JACOBIAN_STEP_MIN: f64 = 1.0e-12;
+
+/// A single reaction, see [ReactionConfig](crate::ReactionConfig)
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Reaction {
+    /// Species indexes of the reactants
+    reactants: Vec<usize>,
+
+    /// Species indexes of the products
+    products: Vec<usize>,
+
+    /// The prefactor of the rate coefficient
+    prefactor: f64,
+
+    /// The exponent of the temperature in the rate coefficient
+    temperature_exponent: f64,
+
+    /// The temperature scale of the exponential in the rate coefficient
+    activation_temperature: f64,
+}
+
+impl Reaction {
+    /// Returns the modified Arrhenius rate coefficient at `temperature`
+    #[inline(always)]
+    fn rate_coefficient(&self, temperature: f64) -> f64 {
+        self.prefactor
+            * temperature.powf(self.temperature_exponent)
+            * (-self.activation_temperature / temperature).exp()
+    }
+}
+
+/// The reaction network of [ReactingFlow](crate::state::ReactingFlow) systems, built from
+/// [SystemsConfig::network](crate::SystemsConfig::network), and carried by
+/// [Variables](crate::variables::Variables).
+///
+/// Besides integrating the reactions, this relates the composition of the gas to its
+/// thermodynamics. The mean molecular weight `mu` in atomic mass units `m_u` is
+/// `1 / mu = sum_k Y_k / m_k`, for the mass fractions `Y_k` and the particle masses `m_k` in
+/// atomic mass units, so that the temperature is `T = mu * m_u * p / (rho * k_B)`. The formation
+/// energies of the species make up the chemical energy density `rho * sum_k Y_k * q_k`, with the
+/// formation energies per unit mass `q_k`, which is part of the internal energy, but does not
+/// contribute to the pressure.
+///
+/// If [SystemsConfig::network](crate::SystemsConfig::network) is `None`, this network has
+/// neither species nor reactions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReactionNetwork {
+    /// Particle masses of the species in the units of the simulation
+    masses: Array1<f64>,
+
+    /// Particle masses of the species in atomic mass units
+    masses_amu: Array1<f64>,
+
+    /// Formation energies of the species per unit mass
+    specific_formation_energies: Array1<f64>,
+
+    /// The reactions between the species
+    reactions: Vec<Reaction>,
+
+    /// Adiabatic index
+    gamma: f64,
+
+    /// Boltzmann constant in the units of the simulation
+    boltzmann_constant: f64,
+
+    /// Atomic mass unit in the units of the simulation
+    atomic_mass_unit: f64,
+
+    /// Convergence criterium for the Newton iterations
+    tolerance: f64,
+}
+
+unsafe impl Send for ReactionNetwork {}
+unsafe impl Sync for ReactionNetwork {}
+
+impl ReactionNetwork {
+    /// Constructs a new [ReactionNetwork] object.
+    ///
+    /// # Arguments
+    ///
+    /// * `physics_config` - Contains the [NetworkConfig](crate::NetworkConfig), the adiabatic index
+    ///   and the units
+    pub fn new(physics_config: &PhysicsConfig) -> Self {
+        let units = Units::new(physics_config.units_mode.clone());
+        let mut network = Self {
+            gamma: physics_config.adiabatic_index,
+            boltzmann_constant: units.boltzmann_constant(),
+            atomic_mass_unit: units.atomic_mass_unit(),
+            ..Default::default()
+        };
+        if let Some(network_config) = &physics_config.systems.network {
+            network.masses_amu = network_config.species.iter().map(|species| species.mass).collect();
+            network.masses = network.masses_amu.mapv(|m| m * network.atomic_mass_unit);
+            network.specific_formation_energies = network_config
+                .species
+                .iter()
+                .zip(network.masses.iter())
+                .map(|(species, m)| species.formation_energy / m)
+                .collect();
+            network.reactions = network_config
+                .reactions
+                .iter()
+                .map(|reaction| Reaction {
+                    reactants: reaction.reactants.clone(),
+                    products: reaction.products.clone(),
+                    prefactor: reaction.prefactor,
+                    temperature_exponent: reaction.temperature_exponent,
+                    activation_temperature: reaction.activation_temperature,
+                })
+                .collect();
+            network.tolerance = network_config.tolerance;
+        }
+        network
+    }
+
+    /// Returns the number of species in the network
+    pub fn num_species(&self) -> usize {
+        self.masses.len()
+    }
+
+    /// Returns the chemical energy density for the mass density `rho` and the `mass_fractions`
+    #[inline(always)]
+    pub fn chemical_energy(&self, rho: f64, mass_fractions: ArrayView1<f64>) -> f64 {
+        rho * (&mass_fractions * &self.specific_formation_energies).sum()
+    }
+
+    /// Returns the mean molecular weight in atomic mass units for the `mass_fractions`
+    #[inline(always)]
+    pub fn mean_molecular_weight(&self, mass_fractions: ArrayView1<f64>) -> f64 {
+        (&mass_fractions / &self.masses_amu).sum().recip()
+    }
+
+    /// Returns the temperature for the mass density `rho`, the `pressure`, and the `mass_fractions`
+    pub fn temperature(&self, rho: f64, pressure: f64, mass_fractions: ArrayView1<f64>) -> f64 {
+        self.mean_molecular_weight(mass_fractions) * self.atomic_mass_unit * pressure / (rho * self.boltzmann_constant)
+    }
+
+    /// Returns the pressure for the mass density `rho`, the `temperature`, and the `mass_fractions`;
+    /// the inverse of [ReactionNetwork::temperature], which comes in handy for initial conditions
+    pub fn pressure(&self, rho: f64, temperature: f64, mass_fractions: ArrayView1<f64>) -> f64 {
+        rho * self.boltzmann_constant * temperature
+            / (self.mean_molecular_weight(mass_fractions) * self.atomic_mass_unit)
+    }
+
+    /// Integrates the reactions in a single cell over the time step width `dt`, updating
+    /// `mass_fractions` in place.
+    ///
+    /// Since the reactions only move energy between its chemical and its thermal part, the mass
+    /// density `rho` and the internal energy density `e_int`, including the chemical energy, stay
+    /// fixed, while the temperature follows the composition.
+    ///
+    /// The network is integrated with backward Euler steps, solved with Newton iterations with a
+    /// finite difference Jacobian, which is stable for arbitrarily stiff reactions. If the Newton
+    /// iterations do not converge, the step is split in half until they do, and later sub-steps
+    /// grow again.
+    ///
+    /// # Arguments
+    ///
+    /// * `rho` - The mass density
+    /// * `e_int` - The internal energy density, including the chemical energy
+    /// * `mass_fractions` - The mass fractions of each species
+    /// * `dt` - The time step width
+    pub fn react(&self, rho: f64, e_int: f64, mass_fractions: &mut Array1<f64>, dt: f64) -> Result<()> {
+        if self.reactions.is_empty() {
+            return Ok(());
+        }
+        let (mut t, mut dt_sub) = (0.0, dt);
+        while t < dt {
+            dt_sub = dt_sub.min(dt - t);
+            match self.backward_euler(rho, e_int, mass_fractions.view(), dt_sub) {
+                Some(next) => {
+                    mass_fractions.assign(&next);
+                    t += dt_sub;
+                    dt_sub *= 2.0;
+                },
+                None => {
+                    dt_sub *= 0.5;
+                    ensure!(
+                        dt_sub >= MIN_SUBSTEP_FRACTION * dt,
+                        "The reaction network did not converge, even when sub-cycled with dt_sub = {}! Got rho = {}, \
+                         e_int = {}, mass_fractions = {}",
+                        dt_sub,
+                        rho,
+                        e_int,
+                        mass_fractions
+                    );
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the mass fractions after a backward Euler step of width `dt` starting at
+    /// `mass_fractions`, or `None` if the Newton iterations do not converge
+    fn backward_euler(&self, rho: f64, e_int: f64, mass_fractions: ArrayView1<f64>, dt: f64) -> Option<Array1<f64>> {
+        let n = self.num_species();
+        let mut next = mass_fractions.to_owned();
+        for _ in 0..MAX_NEWTON_ITER {
+            let rates = self.rates(rho, e_int, next.view())?;
+            let residual = &next - &mass_fractions - dt * &rates;
+            let mut jacobian = Array2::eye(n);
+            for k in 0..n {
+                let step = (JACOBIAN_STEP * next[k]).max(JACOBIAN_STEP_MIN);
+                let mut shifted = next.clone();
+                shifted[k] += step;
+                let rates_shifted = self.rates(rho, e_int, shifted.view())?;
+                jacobian
+                    .column_mut(k)
+                    .scaled_add(-dt / step, &(&rates_shifted - &rates));
+            }
+            let delta = solve_linear(jacobian, -residual)?;
+            next += &delta;
+            next.mapv_inplace(|y| y.max(0.0));
+            if delta.iter().all(|d| d.abs() <= self.tolerance) {
+                return Some(next);
+            }
+        }
+        None
+    }
+
+    /// Returns the time derivatives of the `mass_fractions` for the mass density `rho` and the
+    /// internal energy density `e_int`, or `None` if there is no thermal energy left
+    fn rates(&self, rho: f64, e_int: f64, mass_fractions: ArrayView1<f64>) -> Option<Array1<f64>> {
+        let pressure = (self.gamma - 1.0) * (e_int - self.chemical_energy(rho, mass_fractions));
+        let temperature = self.temperature(rho, pressure, mass_fractions);
+        if !(temperature.is_finite() && temperature > 0.0) {
+            return None;
+        }
+        let number_densities = rho * &mass_fractions / &self.masses;
+        let mut rates = Array1::zeros(self.num_species());
+        for reaction in self.reactions.iter() {
+            let rate = reaction
+                .reactants
+                .iter()
+                .fold(reaction.rate_coefficient(temperature), |acc, &k| {
+                    acc * number_densities[k]
+                });
+            for &k in reaction.reactants.iter() {
+                rates[k] -= rate * self.masses[k] / rho;
+            }
+            for &k in reaction.products.iter() {
+                rates[k] += rate * self.masses[k] / rho;
+            }
+        }
+        Some(rates)
+    }
+}
+
+/// Solves the linear system `a * x = b` with Gaussian elimination with partial pivoting, returning
+/// `None` if `a` is singular
+fn solve_linear(mut a: Array2<f64>, mut b: Array1<f64>) -> Option<Array1<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&r1, &r2| a[[r1, col]].abs().total_cmp(&a[[r2, col]].abs()))?;
+        if !(a[[pivot, col]].is_finite() && a[[pivot, col]] != 0.0) {
+            return None;
+        }
+        if pivot != col {
+            for j in 0..n {
+                a.swap([pivot, j], [col, j]);
+            }
+            b.swap(pivot, col);
+        }
+        for row in col + 1..n {
+            let factor = a[[row, col]] / a[[col, col]];
+            for j in col..n {
+                a[[row, j]] -= factor * a[[col, j]];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|j| a[[row, j]] * b[j]).sum();
+        b[row] = (b[row] - sum) / a[[row, row]];
+    }
+    b.iter().all(|x| x.is_finite()).then_some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NetworkConfig, ReactionConfig, SpeciesConfig, SystemsConfig, UnitsMode};
+    use approx::assert_relative_eq;
+
+    /// Network with a single decay `A -> B` at the constant rate `1 / t_decay`, where forming `B`
+    /// costs the energy `formation_energy`
+    fn decay_config(t_decay: f64, formation_energy: f64) -> PhysicsConfig {
+        let species = |name: &str, formation_energy| SpeciesConfig {
+            name: name.to_string(),
+            mass: 1.0,
+            formation_energy,
+        };
+        PhysicsConfig {
+            systems: SystemsConfig {
+                network: Some(NetworkConfig {
+                    species: vec![species("A", 0.0), species("B", formation_energy)],
+                    reactions: vec![ReactionConfig {
+                        reactants: vec![0],
+                        products: vec![1],
+                        prefactor: 1.0 / t_decay,
+                        temperature_exponent: 0.0,
+                        activation_temperature: 0.0,
+                    }],
+                    tolerance: 1.0e-13,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn linear_decay_takes_a_single_backward_euler_step() {
+        let network = ReactionNetwork::new(&decay_config(2.0, 0.0));
+        let mut mass_fractions = Array1::from(vec![1.0, 0.0]);
+        network.react(1.0, 1.0, &mut mass_fractions, 0.5).unwrap();
+        assert_relative_eq!(mass_fractions[0], 1.0 / (1.0 + 0.5 / 2.0), max_relative = 1.0e-10);
+        assert_relative_eq!(mass_fractions.sum(), 1.0, max_relative = 1.0e-12);
+    }
+
+    #[test]
+    fn endothermic_reactions_cool_the_gas() {
+        let k_b = Units::new(UnitsMode::SI).boltzmann_constant();
+        let physics_config = decay_config(1.0e-3, 0.5 * k_b);
+        let network = ReactionNetwork::new(&physics_config);
+        let (rho, pressure) = (1.0, 1.0e5);
+        let mut mass_fractions = Array1::from(vec![1.0, 0.0]);
+        let e_int = pressure / (physics_config.adiabatic_index - 1.0);
+        let temperature_initial = network.temperature(rho, pressure, mass_fractions.view());
+
+        // every reaction costs a tiny bit of energy, which is taken from the pressure, while a
+        // single backward Euler step decays nearly all of A
+        network.react(rho, e_int, &mut mass_fractions, 1.0).unwrap();
+        assert_relative_eq!(mass_fractions[0], 1.0 / (1.0 + 1.0e3), max_relative = 1.0e-10);
+        let pressure_after =
+            (physics_config.adiabatic_index - 1.0) * (e_int - network.chemical_energy(rho, mass_fractions.view()));
+        assert!(pressure_after < pressure);
+        assert!(network.temperature(rho, pressure_after, mass_fractions.view()) < temperature_initial);
+    }
+
+    #[test]
+    fn temperature_and_pressure_are_inverse() {
+        let network = ReactionNetwork::new(&PhysicsConfig {
+            systems: SystemsConfig {
+                network: Some(NetworkConfig::hydrogen_ionization(&UnitsMode::CGS)),
+                ..Default::default()
+            },
+            units_mode: UnitsMode::CGS,
+            ..Default::default()
+        });
+        // fully ionized hydrogen has half the mean molecular weight of atomic hydrogen
+        let neutral = Array1::from(vec![1.0, 0.0, 0.0]);
+        let mass_fraction_electrons = network.masses_amu[2] / network.masses_amu[0];
+        let ionized = Array1::from(vec![0.0, 1.0 - mass_fraction_electrons, mass_fraction_electrons]);
+        assert_relative_eq!(
+            network.mean_molecular_weight(neutral.view()),
+            1.00783,
+            max_relative = 1.0e-5
+        );
+        assert_relative_eq!(
+            network.mean_molecular_weight(ionized.view()),
+            0.5 * network.mean_molecular_weight(neutral.view()),
+            max_relative = 1.0e-12
+        );
+        let pressure = network.pressure(1.0e-10, 1.0e4, ionized.view());
+        assert_relative_eq!(
+            network.temperature(1.0e-10, pressure, ionized.view()),
+            1.0e4,
+            max_relative = 1.0e-12
+        );
+    }
+
+    #[test]
+    fn singular_systems_are_rejected() {
+        let a = Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+        assert_eq!(solve_linear(a, Array1::from(vec![1.0, 1.0])), None);
+        let a = Array2::from_shape_vec((2, 2), vec![0.0, 2.0, 1.0, 1.0]).unwrap();
+        assert_eq!(
+            solve_linear(a, Array1::from(vec![2.0, 3.0])),
+            Some(Array1::from(vec![2.0, 1.0]))
+        );
+    }
+}
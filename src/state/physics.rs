@@ -5,13 +5,18 @@
 //! Exports the [Physics] trait which defines the behavior embedded in objects
 
 use color_eyre::{eyre::bail, Result};
-use ndarray::{ArrayView1, Zip};
+use ndarray::{Array2, ArrayView1, Zip};
 
-use crate::{boundaryconditions::BoundaryCondition, variables::Variables, Mesh};
+use crate::{boundaryconditions::BoundaryCondition, variables::Variables, Mesh, PhysicsConfig};
 
 /// Trait for objects that define sets of differential equations that describe the evolution of a
 /// classical hydrodynamics system.
 pub trait Physics<const E: usize, const S: usize> {
+    /// The implementer at [RUNTIME_SIZE](crate::mesh::RUNTIME_SIZE), which tells apart systems
+    /// independent of their mesh size, e.g. when comparing the physics of refined patches to the
+    /// coarse one
+    type Unsized: 'static;
+
     /// The number of equations in the systme of differential equations.
     const NUM_EQ: usize;
 
@@ -37,6 +42,38 @@ pub trait Physics<const E: usize, const S: usize> {
     /// usize::MAX if the implementer does not carry the density in either of those arrays.
     const JPRESSURE: usize;
 
+    /// The number of passive scalars carried by the implementer
+    const NUM_SCALARS: usize = 0;
+
+    /// The row index of the first passive scalar in Variables::prim or Variables::cons; the scalars
+    /// occupy the last [NUM_SCALARS](Physics::NUM_SCALARS) rows. Set to usize::MAX if the
+    /// implementer does not carry passive scalars.
+    const JSCALARS: usize = usize::MAX;
+
+    /// Whether the implementer is a newtonian gas with a mass density and a xi velocity, which
+    /// physical viscosity, heat conduction, and external potentials act on
+    const IS_NEWTONIAN_GAS: bool = Self::JRHO != usize::MAX && Self::JXI != usize::MAX;
+
+    /// Whether the implementer flows over a bottom, set by
+    /// [ShallowWaterConfig::bathymetry](crate::ShallowWaterConfig::bathymetry)
+    const HAS_BOTTOM: bool = false;
+
+    /// Whether the implementer carries dust, which is coupled to the gas through
+    /// [SystemsConfig::drag](crate::SystemsConfig::drag)
+    const HAS_DUST: bool = false;
+
+    /// Whether the implementer carries species that react through
+    /// [SystemsConfig::network](crate::SystemsConfig::network)
+    const HAS_REACTIONS: bool = false;
+
+    /// Whether the implementer carries radiation configured by
+    /// [SystemsConfig::radiation](crate::SystemsConfig::radiation)
+    const HAS_RADIATION: bool = false;
+
+    /// Whether [Physics::calc_geometric_source] covers the implementer; systems without geometric
+    /// source terms can only run on cartesian meshes
+    const HAS_GEOMETRIC_SOURCE: bool = true;
+
     /// Construct a new [Physics] object
     fn new() -> Self;
 
@@ -141,8 +178,70 @@ pub trait Physics<const E: usize, const S: usize> {
         }
     }
 
+    /// Checks that `physics_config` fits this type, e.g. that its [EosConfig](crate::EosConfig)
+    /// matches the equation of state of an adiabatic system.
+    fn validate_physics_config(_: &PhysicsConfig) -> Result<()> {
+        Ok(())
+    }
+
+    /// Accessor to the `k`-th passive scalar, i.e. its mass fraction, derived from the `vars`
+    /// argument.
+    ///
+    /// Returns a view to a vector of zeros if the implementer does not carry that many scalars.
+    fn scalar(vars: &Variables<E, S>, k: usize) -> ArrayView1<'_, f64> {
+        if k < Self::NUM_SCALARS {
+            vars.prim.row(Self::JSCALARS + k)
+        } else {
+            vars.zero_vec()
+        }
+    }
+
+    /// Calculates the source terms that arise from the curvature of non-cartesian meshes in the
+    /// computational area, and writes them into `source`; see
+    /// [GeometricSource](crate::rhs::GeometricSource).
+    ///
+    /// The default covers hydrodynamic systems. Let
+    ///
+    /// * `rho`: mass density
+    /// * `P`: pressure; for isothermal systems this is `rho * cs * cs`
+    /// * `v_xi`, `v_eta`: velocities along xi and eta
+    /// * `m_eta`: momentum along eta
+    /// * `cexe`, `cpxp`: commutator coefficients of the [Mesh]
+    ///
+    /// Then the source terms for the momentum equations are:
+    ///
+    /// ```text
+    /// S_xi  = (m_eta * v_eta + P) * cexe + P * cpxp
+    /// S_eta = -1.0 * m_eta * v_xi * cexe
+    /// ```
+    ///
+    /// The source terms of equations that the implementer does not carry are skipped, and all
+    /// other equations do not receive geometric source terms.
+    fn calc_geometric_source(vars: &Variables<E, S>, mesh: &Mesh<S>, source: &mut Array2<f64>) {
+        let rho = Self::rho_prim(vars);
+        let pressure = Self::pressure(vars);
+        let xi_vel = Self::xi_vel(vars);
+        let eta_vel = Self::eta_vel(vars);
+        let eta_mom = Self::eta_mom(vars);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let p = if Self::IS_ADIABATIC {
+                pressure[i]
+            } else {
+                rho[i] * vars.c_sound[i] * vars.c_sound[i]
+            };
+            if Self::JXI < usize::MAX {
+                source[[Self::JXI, i]] = (eta_mom[i] * eta_vel[i] + p) * mesh.cexe[i] + p * mesh.cpxp[i];
+            }
+            if Self::JETA < usize::MAX {
+                source[[Self::JETA, i]] = mesh.minus_cexe[i] * eta_mom[i] * xi_vel[i];
+            }
+        }
+    }
+
     /// Updates primitive variables in the `vars` argument.
-    fn update_prim(vars: &mut Variables<E, S>);
+    ///
+    /// Returns an error if the primitive variables cannot be recovered from the conservative ones.
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()>;
 
     /// Updates conservative variables in the `vars` argument.
     fn update_cons(vars: &mut Variables<E, S>);
@@ -163,7 +262,7 @@ pub trait Physics<const E: usize, const S: usize> {
             // - raw index loop (~10% faster than the alternatives)
             // - ndarray's `assign`
             // - ndarray's `Zip`
-            for i in 0..S {
+            for i in 0..vars.n_all() {
                 vars.c_sound[i] = (vars.gamma * Self::pressure(vars)[i] / Self::rho_prim(vars)[i]).sqrt();
             }
         }
@@ -214,9 +313,10 @@ pub trait Physics<const E: usize, const S: usize> {
         boundary_west: &mut Box<dyn BoundaryCondition<E, S>>,
         boundary_east: &mut Box<dyn BoundaryCondition<E, S>>,
         mesh: &Mesh<S>,
-    ) {
-        Self::update_prim(vars);
+    ) -> Result<()> {
+        Self::update_prim(vars)?;
         Self::update_vars_from_prim(vars, boundary_west, boundary_east, mesh);
+        Ok(())
     }
 
     /// Updates the physical flux in the `vars` argument.
@@ -4,8 +4,32 @@
 
 //! Exports the modules and contents for implementers of `Physics`
 
+pub use burgers::Burgers;
+pub use dustygas::DustyGas;
 pub use euler1dadiabatic::*;
+pub use euler1dadiabaticscalars::Euler1DAdiabaticScalars;
 pub use euler1disot::*;
+pub use euler1disotscalars::Euler1DIsotScalars;
+pub use euler2dadiabatic::Euler2DAdiabatic;
+pub use euler2disot::Euler2DIsot;
+pub use idealmhd::IdealMhd;
+pub use linearadvection::LinearAdvection;
+pub use radiationhydro::RadiationHydro;
+pub use reactingflow::ReactingFlow;
+pub use shallowwater::ShallowWater;
+pub use srhd::Srhd;
 
+pub mod burgers;
+pub mod dustygas;
 pub mod euler1dadiabatic;
+pub mod euler1dadiabaticscalars;
 pub mod euler1disot;
+pub mod euler1disotscalars;
+pub mod euler2dadiabatic;
+pub mod euler2disot;
+pub mod idealmhd;
+pub mod linearadvection;
+pub mod radiationhydro;
+pub mod reactingflow;
+pub mod shallowwater;
+pub mod srhd;
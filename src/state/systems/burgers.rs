@@ -0,0 +1,109 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Burgers] struct, which is an implementer for [Physics] for the 1-dimensional
+//! inviscid Burgers equation.
+
+use crate::{mesh::RUNTIME_SIZE, state::Physics, variables::Variables};
+use color_eyre::{eyre::ensure, Result};
+
+const E: usize = 1;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for the 1-dimensional
+/// inviscid Burgers equation, i.e. a velocity `u` that carries itself:
+///
+/// ```text
+/// du/dt + d(0.5 * u * u)/dxi = 0
+/// ```
+///
+/// This is the simplest non-linear model equation, which is useful for testing how
+/// [NumFlux](crate::rhs::NumFlux) implementations and their limiters handle shocks and
+/// rarefactions, without the overhead of a full hydrodynamics system. Smooth initial conditions
+/// steepen into shocks wherever the velocity falls off along `xi`.
+///
+/// # Variables
+///
+/// There is only one equation, and the primitive and the conservative variable are the same:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | xi velocity        | xi velocity
+///
+/// Since the variable is a velocity, [JXI](Physics::JXI) points to it, while
+/// [JRHO](Physics::JRHO), [JETA](Physics::JETA), and [JPRESSURE](Physics::JPRESSURE) are set to
+/// [usize::MAX]. The velocity is also the only eigen value of this system. The speed of sound is
+/// not used.
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_0`: the primitive variable
+/// * `uc_0`: the conservative variable
+/// * `Fp_0`: the physical flux
+///
+/// Then
+///
+/// ```text
+/// uc_0 = up_0
+/// Fp_0 = 0.5 * uc_0 * uc_0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Burgers<const S: usize>;
+
+unsafe impl<const S: usize> Send for Burgers<S> {}
+unsafe impl<const S: usize> Sync for Burgers<S> {}
+
+impl<const S: usize> Physics<E, S> for Burgers<S> {
+    type Unsized = Burgers<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = false;
+    const IS_ISOTHERMAL: bool = false;
+    const JRHO: usize = usize::MAX;
+    const JXI: usize = 0;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = usize::MAX;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "Burgers".to_string()
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        vars.prim.assign(&vars.cons);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        vars.cons.assign(&vars.prim);
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_min(vars: &mut Variables<E, S>) {
+        vars.eigen_vals.row_mut(0).assign(&vars.prim.row(Self::JXI));
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_max(vars: &mut Variables<E, S>) {
+        vars.eigen_vals.row_mut(E - 1).assign(&vars.prim.row(Self::JXI));
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.flux[[Self::JXI, i]] = 0.5 * vars.cons[[Self::JXI, i]] * vars.cons[[Self::JXI, i]];
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        check_finite_arrayd!(vars.prim, vars.cons);
+        Ok(())
+    }
+}
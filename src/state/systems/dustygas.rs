@@ -0,0 +1,234 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [DustyGas] struct, which is an implementer for [Physics] for 1-dimensional
+//! adiabatic gas coupled to pressureless dust.
+
+use crate::{
+    mesh::RUNTIME_SIZE,
+    state::{eos, EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::{eyre::ensure, Result};
+
+const E: usize = 5;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for 1-dimensional
+/// adiabatic Euler equations of a gas, together with a pressureless dust fluid, as used for
+/// planet formation.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | gas mass density   | gas mass density
+/// 1              | gas xi velocity    | gas xi momentum
+/// 2              | gas pressure       | gas energy
+/// 3              | dust mass density  | dust mass density
+/// 4              | dust xi velocity   | dust xi momentum
+///
+/// The equation indexes [JRHO](Physics::JRHO), [JXI](Physics::JXI), and
+/// [JPRESSURE](Physics::JPRESSURE) point to the gas, while the dust rows are found through
+/// [DustyGas::JRHO_DUST] and [DustyGas::JXI_DUST]. The gas is closed by an [IdealGas].
+///
+/// Since the dust does not have any pressure, its only eigen value is its velocity, which it
+/// carries twice. Together with the gas eigen values `v_gas - c`, `v_gas`, and `v_gas + c`, these
+/// five eigen values are sorted per cell, so that the first and the last row of the eigen values
+/// hold the extremal wave speeds of both fluids.
+///
+/// Both fluids only interact through the drag set in
+/// [SystemsConfig::drag](crate::SystemsConfig::drag), which is applied by the
+/// [DragSource](crate::rhs::DragSource).
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `gamma`: the adiabatic index
+///
+/// The gas rows are converted like those of [Euler1DAdiabatic](crate::state::Euler1DAdiabatic),
+/// while the dust rows are converted with:
+///
+/// ```text
+/// uc_3 = up_3
+/// uc_4 = up_3 * up_4
+/// up_3 = uc_3
+/// up_4 = uc_4 / uc_3
+/// ```
+///
+/// where cells without any dust, i.e. `uc_3 = 0`, get `up_4 = 0`.
+///
+/// The physical flux of the dust is:
+///
+/// ```text
+/// Fp_3 = uc_4
+/// Fp_4 = up_4 * uc_4
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DustyGas<const S: usize>;
+
+unsafe impl<const S: usize> Send for DustyGas<S> {}
+unsafe impl<const S: usize> Sync for DustyGas<S> {}
+
+impl<const S: usize> DustyGas<S> {
+    /// The row index for the dust mass density
+    pub const JRHO_DUST: usize = 3;
+
+    /// The row index for the dust xi velocity, or the dust xi momentum respectively
+    pub const JXI_DUST: usize = 4;
+}
+
+impl<const S: usize> Physics<E, S> for DustyGas<S> {
+    type Unsized = DustyGas<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = true;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = 2;
+    const HAS_DUST: bool = true;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "DustyGas".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        IdealGas::validate_config(&physics_config.eos)
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::cons_to_prim::<IdealGas>(
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+            (vars.prim[[Self::JRHO_DUST, i]], vars.prim[[Self::JXI_DUST, i]]) =
+                cons_to_prim_dust(vars.cons[[Self::JRHO_DUST, i]], vars.cons[[Self::JXI_DUST, i]]);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::prim_to_cons::<IdealGas>(
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+            (vars.cons[[Self::JRHO_DUST, i]], vars.cons[[Self::JXI_DUST, i]]) =
+                super::euler1disot::prim_to_cons(vars.prim[[Self::JRHO_DUST, i]], vars.prim[[Self::JXI_DUST, i]]);
+        }
+    }
+
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        eos::update_c_sound::<IdealGas, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI]);
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            let (xi_vel, c_sound, xi_vel_dust) = (
+                vars.prim[[Self::JXI, i]],
+                vars.c_sound[i],
+                vars.prim[[Self::JXI_DUST, i]],
+            );
+            let mut eigen_vals = [xi_vel - c_sound, xi_vel, xi_vel + c_sound, xi_vel_dust, xi_vel_dust];
+            eigen_vals.sort_by(f64::total_cmp);
+            for (j, eigen_val) in eigen_vals.into_iter().enumerate() {
+                vars.eigen_vals[[j, i]] = eigen_val;
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_min(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.eigen_vals[[0, i]] = (vars.prim[[Self::JXI, i]] - vars.c_sound[i]).min(vars.prim[[Self::JXI_DUST, i]]);
+        }
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_max(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.eigen_vals[[E - 1, i]] =
+                (vars.prim[[Self::JXI, i]] + vars.c_sound[i]).max(vars.prim[[Self::JXI_DUST, i]]);
+        }
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.flux[[Self::JRHO, i]],
+                vars.flux[[Self::JXI, i]],
+                vars.flux[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::calc_flux(
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            );
+            (vars.flux[[Self::JRHO_DUST, i]], vars.flux[[Self::JXI_DUST, i]]) =
+                calc_flux_dust(vars.prim[[Self::JXI_DUST, i]], vars.cons[[Self::JXI_DUST, i]]);
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)?;
+        super::euler1dadiabatic::validate(vars, Self::JPRESSURE)?;
+        validate_dust(vars, Self::JRHO_DUST)?;
+        eos::validate::<IdealGas, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI])
+    }
+}
+
+/// Converts the conservative to the primitive variables of the dust, where cells without dust are at
+/// rest
+#[inline(always)]
+pub fn cons_to_prim_dust(rho_dust: f64, xi_mom_dust: f64) -> (f64, f64) {
+    (rho_dust, if rho_dust > 0.0 { xi_mom_dust / rho_dust } else { 0.0 })
+}
+
+/// Checks that the dust mass density is finite and non-negative, since some cells may hold no dust
+#[inline(always)]
+pub fn validate_dust<const E: usize, const S: usize>(vars: &Variables<E, S>, j_rho_dust: usize) -> Result<()> {
+    let rho_dust = vars.prim.row(j_rho_dust);
+    ensure!(
+        rho_dust.iter().all(|&x| x.is_finite() && x >= 0.0),
+        "The dust mass density turned negative or non-finite! Got: {}",
+        rho_dust
+    );
+    Ok(())
+}
+
+/// Updates the physical flux of the pressureless dust
+#[inline(always)]
+pub fn calc_flux_dust(xi_vel_dust: f64, xi_mom_dust: f64) -> (f64, f64) {
+    (xi_mom_dust, xi_vel_dust * xi_mom_dust)
+}
@@ -5,13 +5,19 @@
 //! Exports the [Euler1DAdiabatic] struct, which is an implementer for [Physics] for 1-dimensional
 //! adiabatic Euler equations
 
-use crate::{state::Physics, variables::Variables};
+use crate::{
+    mesh::RUNTIME_SIZE,
+    state::{eos, EosParams, EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
 use color_eyre::{eyre::ensure, Result};
+use std::marker::PhantomData;
 
 const E: usize = 3;
 
 /// Implenter for [Physics] that manipulates [State](crate::state::State) for 1-dimensional
-/// adiabatic Euler equations.
+/// adiabatic Euler equations, closed by the [EquationOfState] `Q`, which defaults to [IdealGas].
 ///
 /// # Variables
 ///
@@ -39,7 +45,8 @@ const E: usize = 3;
 /// * `up_j`: primitive variables at equation index `j`
 /// * `uc_j`: conservative variables at equation index `j`
 /// * `Fp_j`: physical flux at equation index `j`
-/// * `gamma`: the adiabatic index
+/// * `p(rho, e)`: the pressure from [EquationOfState::pressure]
+/// * `e(rho, p)`: the internal energy from [EquationOfState::internal_energy]
 ///
 /// In combination with the table up to this means that, for example, `up_0` corresponds the
 /// primitive mass density, and `uc_1` corresponds to the momentum along the xi direction.
@@ -49,7 +56,7 @@ const E: usize = 3;
 /// ```text
 /// uc_0 = up_0
 /// uc_1 = up_0 * up_1
-/// uc_2 = up_2 / (gamma - 1.0) + 0.5 * up_0 * up_1 * up_1
+/// uc_2 = e(up_0, up_2) + 0.5 * up_0 * up_1 * up_1
 /// ```
 ///
 /// They are converted back with:
@@ -57,7 +64,7 @@ const E: usize = 3;
 /// ```text
 /// up_0 = uc_0
 /// up_1 = uc_1 / uc_0
-/// up_2 = (gamma - 1.0) * (uc_2 - 0.5 / uc_0 * uc_1 * uc_1)
+/// up_2 = p(uc_0, uc_2 - 0.5 / uc_0 * uc_1 * uc_1)
 /// ```
 ///
 /// The physical flux is calculated with:
@@ -67,13 +74,18 @@ const E: usize = 3;
 /// Fp_1 = up_1 * uc_1 + up_2
 /// Fp_2 = (uc_2 + up_2) * up_1
 /// ```
+///
+/// For an [IdealGas], these boil down to `e(rho, p) = p / (gamma - 1)` and
+/// `p(rho, e) = (gamma - 1) * e`, where `gamma` is the adiabatic index. The speed of sound is
+/// calculated through [EquationOfState::c_sound].
 #[derive(Debug, Clone, Default, PartialEq)]
-pub struct Euler1DAdiabatic<const S: usize>;
+pub struct Euler1DAdiabatic<const S: usize, Q: EquationOfState = IdealGas>(PhantomData<Q>);
 
-unsafe impl<const S: usize> Send for Euler1DAdiabatic<S> {}
-unsafe impl<const S: usize> Sync for Euler1DAdiabatic<S> {}
+unsafe impl<const S: usize, Q: EquationOfState> Send for Euler1DAdiabatic<S, Q> {}
+unsafe impl<const S: usize, Q: EquationOfState> Sync for Euler1DAdiabatic<S, Q> {}
 
-impl<const S: usize> Physics<E, S> for Euler1DAdiabatic<S> {
+impl<const S: usize, Q: EquationOfState> Physics<E, S> for Euler1DAdiabatic<S, Q> {
+    type Unsized = Euler1DAdiabatic<RUNTIME_SIZE, Q>;
     const NUM_EQ: usize = E;
     const IS_ADIABATIC: bool = true;
     const JRHO: usize = 0;
@@ -82,32 +94,37 @@ impl<const S: usize> Physics<E, S> for Euler1DAdiabatic<S> {
     const JPRESSURE: usize = 2;
 
     fn new() -> Self {
-        Self
+        Self(PhantomData)
     }
 
     fn name() -> String {
-        "Euler1DAdiabatic".to_string()
+        format!("Euler1DAdiabatic<{}>", Q::name())
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        Q::validate_config(&physics_config.eos)
     }
 
     #[inline(always)]
-    fn update_prim(vars: &mut Variables<E, S>) {
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
         // PERF: This was benchmarked between the following options:
         // - raw index loop over columns calculating the tuple of the primitive values per column
         // (~20% speed up over the zip)
         // - ndarray::Zip!, where each row is calculated in a separate zip
 
-        for i in 0..S {
+        for i in 0..vars.n_all() {
             (
                 vars.prim[[Self::JRHO, i]],
                 vars.prim[[Self::JXI, i]],
                 vars.prim[[Self::JPRESSURE, i]],
-            ) = cons_to_prim(
+            ) = cons_to_prim::<Q>(
                 vars.cons[[Self::JRHO, i]],
                 vars.cons[[Self::JXI, i]],
                 vars.cons[[Self::JPRESSURE, i]],
-                vars.gamma,
+                &vars.eos,
             );
         }
+        Ok(())
     }
 
     #[inline(always)]
@@ -117,23 +134,28 @@ impl<const S: usize> Physics<E, S> for Euler1DAdiabatic<S> {
         // (~20% speed up over the zip)
         // - ndarray::Zip!, where each row is calculated in a separate zip
 
-        for i in 0..S {
+        for i in 0..vars.n_all() {
             (
                 vars.cons[[Self::JRHO, i]],
                 vars.cons[[Self::JXI, i]],
                 vars.cons[[Self::JPRESSURE, i]],
-            ) = prim_to_cons(
+            ) = prim_to_cons::<Q>(
                 vars.prim[[Self::JRHO, i]],
                 vars.prim[[Self::JXI, i]],
                 vars.prim[[Self::JPRESSURE, i]],
-                vars.gamma,
+                &vars.eos,
             );
         }
     }
 
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        eos::update_c_sound::<Q, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI]);
+    }
+
     #[inline(always)]
     fn update_flux(vars: &mut Variables<E, S>) {
-        for i in 0..S {
+        for i in 0..vars.n_all() {
             (
                 vars.flux[[Self::JRHO, i]],
                 vars.flux[[Self::JXI, i]],
@@ -150,7 +172,8 @@ impl<const S: usize> Physics<E, S> for Euler1DAdiabatic<S> {
     #[inline(always)]
     fn validate(vars: &Variables<E, S>) -> Result<()> {
         super::euler1disot::validate(vars, Self::JRHO)?;
-        validate(vars, Self::JPRESSURE)
+        validate(vars, Self::JPRESSURE)?;
+        eos::validate::<Q, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI])
     }
 }
 
@@ -160,25 +183,25 @@ pub fn calc_flux(xi_vel: f64, xi_mom: f64, pressure: f64, energy: f64) -> (f64,
     (xi_mom, xi_vel * xi_mom + pressure, (energy + pressure) * xi_vel)
 }
 
-/// Converts conservative to primitive variables
+/// Converts conservative to primitive variables using the equation of state `Q`
 #[inline(always)]
-pub fn cons_to_prim(rho_cons: f64, xi_mom: f64, energy: f64, gamma: f64) -> (f64, f64, f64) {
+pub fn cons_to_prim<Q: EquationOfState>(rho_cons: f64, xi_mom: f64, energy: f64, eos: &EosParams) -> (f64, f64, f64) {
     let (rho_prim, xi_vel) = super::euler1disot::cons_to_prim(rho_cons, xi_mom);
     (
         rho_prim,
         xi_vel,
-        (gamma - 1.0) * (energy - 0.5 / rho_cons * xi_mom * xi_mom),
+        Q::pressure(rho_cons, energy - 0.5 / rho_cons * xi_mom * xi_mom, eos),
     )
 }
 
-/// Converts primitive to conservative variables
+/// Converts primitive to conservative variables using the equation of state `Q`
 #[inline(always)]
-pub fn prim_to_cons(rho_prim: f64, xi_vel: f64, pressure: f64, gamma: f64) -> (f64, f64, f64) {
+pub fn prim_to_cons<Q: EquationOfState>(rho_prim: f64, xi_vel: f64, pressure: f64, eos: &EosParams) -> (f64, f64, f64) {
     let (rho_cons, xi_mom) = super::euler1disot::prim_to_cons(rho_prim, xi_vel);
     (
         rho_cons,
         xi_mom,
-        pressure * (gamma - 1.0).recip() + 0.5 * rho_prim * xi_vel * xi_vel,
+        Q::internal_energy(rho_prim, pressure, eos) + 0.5 * rho_prim * xi_vel * xi_vel,
     )
 }
 
@@ -0,0 +1,174 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Euler1DAdiabaticScalars] struct, which is an implementer for [Physics] for
+//! adiabatic Euler equations that also advect passive scalars.
+
+use super::euler1disotscalars::{calc_scalar_flux, scalars_to_cons, scalars_to_prim};
+use crate::{
+    mesh::RUNTIME_SIZE,
+    state::{eos, EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::Result;
+use std::marker::PhantomData;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for adiabatic Euler
+/// equations in 1D, which also carry `E - 3` passive scalars. Like
+/// [Euler1DAdiabatic](super::Euler1DAdiabatic), it is closed by the [EquationOfState] `Q`, which
+/// defaults to [IdealGas].
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable          | conservative variable
+/// ---            | ---                         | ---
+/// 0              | mass density                | mass density
+/// 1              | xi velocity                 | xi momentum
+/// 2              | pressure                    | energy
+/// 3 + k          | mass fraction of scalar `k` | partial density of scalar `k`
+///
+/// Passive scalars, like dye or the abundance of a chemical species, are carried along with the
+/// flow without acting back on it. Thus, the hydrodynamic rows behave exactly like
+/// [Euler1DAdiabatic](super::Euler1DAdiabatic).
+///
+/// [Euler1DAdiabaticScalars] does not model the eta velocity. Accessors to it will return a vector
+/// of zeroes, whereas its equation index will return [usize::MAX].
+///
+/// Each scalar is treated like any other equation when reconstructing and calculating numerical
+/// fluxes, and it gets its own row in the boundary conditions, so that it can be handled separately
+/// with [BoundaryMode::Custom](crate::BoundaryMode::Custom).
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `p(rho, e)`: the pressure from [EquationOfState::pressure]
+/// * `e(rho, p)`: the internal energy from [EquationOfState::internal_energy]
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0     = up_0
+/// uc_1     = up_0 * up_1
+/// uc_2     = e(up_0, up_2) + 0.5 * up_0 * up_1 * up_1
+/// uc_{3+k} = up_0 * up_{3+k}
+/// ```
+///
+/// They are converted back with:
+///
+/// ```text
+/// up_0     = uc_0
+/// up_1     = uc_1 / uc_0
+/// up_2     = p(uc_0, uc_2 - 0.5 / uc_0 * uc_1 * uc_1)
+/// up_{3+k} = uc_{3+k} / uc_0
+/// ```
+///
+/// The physical flux is calculated with:
+///
+/// ```text
+/// Fp_0     = uc_1
+/// Fp_1     = up_1 * uc_1 + up_2
+/// Fp_2     = (uc_2 + up_2) * up_1
+/// Fp_{3+k} = up_{3+k} * Fp_0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Euler1DAdiabaticScalars<const E: usize, const S: usize, Q: EquationOfState = IdealGas>(PhantomData<Q>);
+
+unsafe impl<const E: usize, const S: usize, Q: EquationOfState> Send for Euler1DAdiabaticScalars<E, S, Q> {}
+unsafe impl<const E: usize, const S: usize, Q: EquationOfState> Sync for Euler1DAdiabaticScalars<E, S, Q> {}
+
+impl<const E: usize, const S: usize, Q: EquationOfState> Physics<E, S> for Euler1DAdiabaticScalars<E, S, Q> {
+    type Unsized = Euler1DAdiabaticScalars<E, RUNTIME_SIZE, Q>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = true;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = 2;
+    const NUM_SCALARS: usize = E - 3;
+    const JSCALARS: usize = 3;
+
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    fn name() -> String {
+        "Euler1DAdiabaticScalars".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        Q::validate_config(&physics_config.eos)
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::cons_to_prim::<Q>(
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+        }
+        scalars_to_prim(vars, Self::JRHO, Self::JSCALARS);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::prim_to_cons::<Q>(
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+        }
+        scalars_to_cons(vars, Self::JRHO, Self::JSCALARS);
+    }
+
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        eos::update_c_sound::<Q, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI]);
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.flux[[Self::JRHO, i]],
+                vars.flux[[Self::JXI, i]],
+                vars.flux[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::calc_flux(
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            );
+        }
+        calc_scalar_flux(vars, Self::JRHO, Self::JSCALARS);
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)?;
+        super::euler1dadiabatic::validate(vars, Self::JPRESSURE)?;
+        eos::validate::<Q, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI])
+    }
+}
@@ -5,7 +5,7 @@
 //! Exports the [Euler1DIsot] struct, which is an implementer for [Physics] for 1-dimensional
 //! isothermal Euler equations.
 
-use crate::{state::Physics, variables::Variables};
+use crate::{mesh::RUNTIME_SIZE, state::Physics, variables::Variables};
 use color_eyre::{eyre::ensure, Result};
 
 const E: usize = 2;
@@ -73,6 +73,7 @@ unsafe impl<const S: usize> Send for Euler1DIsot<S> {}
 unsafe impl<const S: usize> Sync for Euler1DIsot<S> {}
 
 impl<const S: usize> Physics<E, S> for Euler1DIsot<S> {
+    type Unsized = Euler1DIsot<RUNTIME_SIZE>;
     const NUM_EQ: usize = E;
     const IS_ADIABATIC: bool = false;
     const IS_ISOTHERMAL: bool = !Self::IS_ADIABATIC;
@@ -90,15 +91,16 @@ impl<const S: usize> Physics<E, S> for Euler1DIsot<S> {
     }
 
     #[inline(always)]
-    fn update_prim(vars: &mut Variables<E, S>) {
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
         // PERF: This was benchmarked between the following options:
         // - raw index loop over columns calculating the tuple of the primitive values per column
         // (~20% speed up over the zip)
         // - ndarray::Zip!, where each row is calculated in a separate zip
-        for i in 0..S {
+        for i in 0..vars.n_all() {
             (vars.prim[[Self::JRHO, i]], vars.prim[[Self::JXI, i]]) =
                 cons_to_prim(vars.cons[[Self::JRHO, i]], vars.cons[[Self::JXI, i]]);
         }
+        Ok(())
     }
 
     #[inline(always)]
@@ -107,7 +109,7 @@ impl<const S: usize> Physics<E, S> for Euler1DIsot<S> {
         // - raw index loop over columns calculating the tuple of the primitive values per column
         // (~20% speed up over the zip)
         // - ndarray::Zip!, where each row is calculated in a separate zip
-        for i in 0..S {
+        for i in 0..vars.n_all() {
             (vars.cons[[Self::JRHO, i]], vars.cons[[Self::JXI, i]]) =
                 prim_to_cons(vars.prim[[Self::JRHO, i]], vars.prim[[Self::JXI, i]]);
         }
@@ -115,7 +117,7 @@ impl<const S: usize> Physics<E, S> for Euler1DIsot<S> {
 
     #[inline(always)]
     fn update_flux(vars: &mut Variables<E, S>) {
-        for i in 0..S {
+        for i in 0..vars.n_all() {
             (vars.flux[[Self::JRHO, i]], vars.flux[[Self::JXI, i]]) = calc_flux(
                 vars.cons[[Self::JRHO, i]],
                 vars.prim[[Self::JXI, i]],
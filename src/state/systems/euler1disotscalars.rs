@@ -0,0 +1,167 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Euler1DIsotScalars] struct, which is an implementer for [Physics] for isothermal
+//! Euler equations that also advect passive scalars.
+
+use crate::{mesh::RUNTIME_SIZE, state::Physics, variables::Variables};
+use color_eyre::Result;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for isothermal Euler
+/// equations in 1D, which also carry `E - 2` passive scalars.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable          | conservative variable
+/// ---            | ---                         | ---
+/// 0              | mass density                | mass density
+/// 1              | xi velocity                 | xi momentum
+/// 2 + k          | mass fraction of scalar `k` | partial density of scalar `k`
+///
+/// Passive scalars, like dye or the abundance of a chemical species, are carried along with the
+/// flow without acting back on it. Thus, the hydrodynamic rows behave exactly like
+/// [Euler1DIsot](super::Euler1DIsot).
+///
+/// Being isothermal means that the speed of sound for this system has the same value everywhere
+/// and never changes.
+///
+/// [Euler1DIsotScalars] does not model pressure and inner energy, nor the eta velocity. Accessors
+/// to any of these will return a vector of zeroes, whereas the corresponding equation indexes will
+/// return [usize::MAX].
+///
+/// Each scalar is treated like any other equation when reconstructing and calculating numerical
+/// fluxes, and it gets its own row in the boundary conditions, so that it can be handled separately
+/// with [BoundaryMode::Custom](crate::BoundaryMode::Custom).
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `cs`: The speed of sound
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0     = up_0
+/// uc_1     = up_0 * up_1
+/// uc_{2+k} = up_0 * up_{2+k}
+/// ```
+///
+/// They are converted back with:
+///
+/// ```text
+/// up_0     = uc_0
+/// up_1     = uc_1 / uc_0
+/// up_{2+k} = uc_{2+k} / uc_0
+/// ```
+///
+/// The physical flux is calculated with:
+///
+/// ```text
+/// Fp_0     = uc_1
+/// Fp_1     = up_0 * (up_1 * up_1 + cs * cs)
+/// Fp_{2+k} = up_{2+k} * Fp_0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Euler1DIsotScalars<const E: usize, const S: usize>;
+
+unsafe impl<const E: usize, const S: usize> Send for Euler1DIsotScalars<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for Euler1DIsotScalars<E, S> {}
+
+impl<const E: usize, const S: usize> Physics<E, S> for Euler1DIsotScalars<E, S> {
+    type Unsized = Euler1DIsotScalars<E, RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = false;
+    const IS_ISOTHERMAL: bool = !Self::IS_ADIABATIC;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = usize::MAX;
+    const NUM_SCALARS: usize = E - 2;
+    const JSCALARS: usize = 2;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "Euler1DIsotScalars".to_string()
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (vars.prim[[Self::JRHO, i]], vars.prim[[Self::JXI, i]]) =
+                super::euler1disot::cons_to_prim(vars.cons[[Self::JRHO, i]], vars.cons[[Self::JXI, i]]);
+        }
+        scalars_to_prim(vars, Self::JRHO, Self::JSCALARS);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (vars.cons[[Self::JRHO, i]], vars.cons[[Self::JXI, i]]) =
+                super::euler1disot::prim_to_cons(vars.prim[[Self::JRHO, i]], vars.prim[[Self::JXI, i]]);
+        }
+        scalars_to_cons(vars, Self::JRHO, Self::JSCALARS);
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (vars.flux[[Self::JRHO, i]], vars.flux[[Self::JXI, i]]) = super::euler1disot::calc_flux(
+                vars.cons[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.c_sound[i],
+            );
+        }
+        calc_scalar_flux(vars, Self::JRHO, Self::JSCALARS);
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)
+    }
+}
+
+/// Converts the partial densities of the passive scalars in the rows `j_scalars..E` to their mass
+/// fractions
+#[inline(always)]
+pub fn scalars_to_prim<const E: usize, const S: usize>(vars: &mut Variables<E, S>, j_rho: usize, j_scalars: usize) {
+    for j in j_scalars..E {
+        for i in 0..vars.n_all() {
+            vars.prim[[j, i]] = vars.cons[[j, i]] / vars.cons[[j_rho, i]];
+        }
+    }
+}
+
+/// Converts the mass fractions of the passive scalars in the rows `j_scalars..E` to their partial
+/// densities
+#[inline(always)]
+pub fn scalars_to_cons<const E: usize, const S: usize>(vars: &mut Variables<E, S>, j_rho: usize, j_scalars: usize) {
+    for j in j_scalars..E {
+        for i in 0..vars.n_all() {
+            vars.cons[[j, i]] = vars.prim[[j, i]] * vars.prim[[j_rho, i]];
+        }
+    }
+}
+
+/// Updates the physical flux of the passive scalars in the rows `j_scalars..E`, i.e. their mass
+/// fractions times the mass flux; assumes that the mass flux is already up to date
+#[inline(always)]
+pub fn calc_scalar_flux<const E: usize, const S: usize>(vars: &mut Variables<E, S>, j_rho: usize, j_scalars: usize) {
+    for j in j_scalars..E {
+        for i in 0..vars.n_all() {
+            vars.flux[[j, i]] = vars.prim[[j, i]] * vars.flux[[j_rho, i]];
+        }
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Euler2DAdiabatic] struct, which is an implementer for [Physics] for adiabatic Euler
+//! equations that also carry the momentum along the eta direction.
+
+use crate::{
+    mesh::RUNTIME_SIZE,
+    state::{eos, EosParams, EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::Result;
+use std::marker::PhantomData;
+
+const E: usize = 4;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for adiabatic Euler
+/// equations with velocities along xi and eta, closed by the [EquationOfState] `Q`, which defaults
+/// to [IdealGas].
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | mass density       | mass density
+/// 1              | xi velocity        | xi momentum
+/// 2              | eta velocity       | eta momentum
+/// 3              | pressure           | inner energy
+///
+/// Keep in mind that the coordinates in [corries](crate) are generalised, and that it is a purely
+/// 1-dimensional framework. `xi` is the only coordinate that can have more than one cell, whereas
+/// the `eta` and `Phi` directions only ever have one cell. Thus, the eta velocity is advected along
+/// xi, but nothing varies along eta. On curvilinear meshes, this allows modelling rotating disks,
+/// where eta is the azimuthal angle, and on cartesian meshes it allows modelling shear flows.
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `p(rho, e)`: the pressure from [EquationOfState::pressure]
+/// * `e(rho, p)`: the internal energy from [EquationOfState::internal_energy]
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0 = up_0
+/// uc_1 = up_0 * up_1
+/// uc_2 = up_0 * up_2
+/// uc_3 = e(up_0, up_3) + 0.5 * up_0 * (up_1 * up_1 + up_2 * up_2)
+/// ```
+///
+/// They are converted back with:
+///
+/// ```text
+/// up_0 = uc_0
+/// up_1 = uc_1 / uc_0
+/// up_2 = uc_2 / uc_0
+/// up_3 = p(uc_0, uc_3 - 0.5 / uc_0 * (uc_1 * uc_1 + uc_2 * uc_2))
+/// ```
+///
+/// The physical flux is calculated with:
+///
+/// ```text
+/// Fp_0 = uc_1
+/// Fp_1 = up_1 * uc_1 + up_3
+/// Fp_2 = up_1 * uc_2
+/// Fp_3 = (uc_3 + up_3) * up_1
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Euler2DAdiabatic<const S: usize, Q: EquationOfState = IdealGas>(PhantomData<Q>);
+
+unsafe impl<const S: usize, Q: EquationOfState> Send for Euler2DAdiabatic<S, Q> {}
+unsafe impl<const S: usize, Q: EquationOfState> Sync for Euler2DAdiabatic<S, Q> {}
+
+impl<const S: usize, Q: EquationOfState> Physics<E, S> for Euler2DAdiabatic<S, Q> {
+    type Unsized = Euler2DAdiabatic<RUNTIME_SIZE, Q>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = true;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = 2;
+    const JPRESSURE: usize = 3;
+
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    fn name() -> String {
+        format!("Euler2DAdiabatic<{}>", Q::name())
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        Q::validate_config(&physics_config.eos)
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JETA, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+            ) = cons_to_prim::<Q>(
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JETA, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JETA, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            ) = prim_to_cons::<Q>(
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JETA, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        eos::update_c_sound::<Q, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI, Self::JETA]);
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.flux[[Self::JRHO, i]],
+                vars.flux[[Self::JXI, i]],
+                vars.flux[[Self::JETA, i]],
+                vars.flux[[Self::JPRESSURE, i]],
+            ) = calc_flux(
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JETA, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)?;
+        super::euler1dadiabatic::validate(vars, Self::JPRESSURE)?;
+        eos::validate::<Q, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI, Self::JETA])
+    }
+}
+
+/// Updates physical flux corresponding to [Euler2DAdiabatic]
+#[inline(always)]
+pub fn calc_flux(xi_vel: f64, xi_mom: f64, eta_mom: f64, pressure: f64, energy: f64) -> (f64, f64, f64, f64) {
+    let (flux_rho, flux_xi, flux_energy) = super::euler1dadiabatic::calc_flux(xi_vel, xi_mom, pressure, energy);
+    (flux_rho, flux_xi, xi_vel * eta_mom, flux_energy)
+}
+
+/// Converts conservative to primitive variables corresponding to [Euler2DAdiabatic], using the
+/// equation of state `Q`
+#[inline(always)]
+pub fn cons_to_prim<Q: EquationOfState>(
+    rho_cons: f64,
+    xi_mom: f64,
+    eta_mom: f64,
+    energy: f64,
+    eos: &EosParams,
+) -> (f64, f64, f64, f64) {
+    let (rho_prim, xi_vel, eta_vel) = super::euler2disot::cons_to_prim(rho_cons, xi_mom, eta_mom);
+    (
+        rho_prim,
+        xi_vel,
+        eta_vel,
+        Q::pressure(
+            rho_cons,
+            energy - 0.5 / rho_cons * (xi_mom * xi_mom + eta_mom * eta_mom),
+            eos,
+        ),
+    )
+}
+
+/// Converts primitive to conservative variables corresponding to [Euler2DAdiabatic], using the
+/// equation of state `Q`
+#[inline(always)]
+pub fn prim_to_cons<Q: EquationOfState>(
+    rho_prim: f64,
+    xi_vel: f64,
+    eta_vel: f64,
+    pressure: f64,
+    eos: &EosParams,
+) -> (f64, f64, f64, f64) {
+    let (rho_cons, xi_mom, eta_mom) = super::euler2disot::prim_to_cons(rho_prim, xi_vel, eta_vel);
+    (
+        rho_cons,
+        xi_mom,
+        eta_mom,
+        Q::internal_energy(rho_prim, pressure, eos) + 0.5 * rho_prim * (xi_vel * xi_vel + eta_vel * eta_vel),
+    )
+}
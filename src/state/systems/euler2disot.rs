@@ -0,0 +1,168 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Euler2DIsot] struct, which is an implementer for [Physics] for isothermal Euler
+//! equations that also carry the momentum along the eta direction.
+
+use crate::{mesh::RUNTIME_SIZE, state::Physics, variables::Variables};
+use color_eyre::Result;
+
+const E: usize = 3;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for isothermal Euler
+/// equations with velocities along xi and eta.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | mass density       | mass density
+/// 1              | xi velocity        | xi momentum
+/// 2              | eta velocity       | eta momentum
+///
+/// Keep in mind that the coordinates in [corries](crate) are generalised, and that it is a purely
+/// 1-dimensional framework. `xi` is the only coordinate that can have more than one cell, whereas
+/// the `eta` and `Phi` directions only ever have one cell. Thus, the eta velocity is advected along
+/// xi, but nothing varies along eta. On curvilinear meshes, this allows modelling rotating disks,
+/// where eta is the azimuthal angle, and on cartesian meshes it allows modelling shear flows.
+///
+/// Being isothermal means that the speed of sound for this system has the same value everywhere
+/// and never changes.
+///
+/// [Euler2DIsot] does not model pressure and inner energy. Accessors to any of these will return a
+/// vector of zeroes, whereas the corresponding equation indexes will return [usize::MAX].
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `cs`: The speed of sound
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0 = up_0
+/// uc_1 = up_0 * up_1
+/// uc_2 = up_0 * up_2
+/// ```
+///
+/// They are converted back with:
+///
+/// ```text
+/// up_0 = uc_0
+/// up_1 = uc_1 / uc_0
+/// up_2 = uc_2 / uc_0
+/// ```
+///
+/// The physical flux is calculated with:
+///
+/// ```text
+/// Fp_0 = uc_1
+/// Fp_1 = up_0 * (up_1 * up_1 + cs * cs)
+/// Fp_2 = up_1 * uc_2
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Euler2DIsot<const S: usize>;
+
+unsafe impl<const S: usize> Send for Euler2DIsot<S> {}
+unsafe impl<const S: usize> Sync for Euler2DIsot<S> {}
+
+impl<const S: usize> Physics<E, S> for Euler2DIsot<S> {
+    type Unsized = Euler2DIsot<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = false;
+    const IS_ISOTHERMAL: bool = !Self::IS_ADIABATIC;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = 2;
+    const JPRESSURE: usize = usize::MAX;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "Euler2DIsot".to_string()
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JETA, i]],
+            ) = cons_to_prim(
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JETA, i]],
+            );
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JETA, i]],
+            ) = prim_to_cons(
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JETA, i]],
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.flux[[Self::JRHO, i]],
+                vars.flux[[Self::JXI, i]],
+                vars.flux[[Self::JETA, i]],
+            ) = calc_flux(
+                vars.cons[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JETA, i]],
+                vars.c_sound[i],
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)
+    }
+}
+
+/// Updates physical flux corresponding to [Euler2DIsot]
+#[inline(always)]
+pub fn calc_flux(rho: f64, xi_vel: f64, xi_mom: f64, eta_mom: f64, cs: f64) -> (f64, f64, f64) {
+    let (flux_rho, flux_xi) = super::euler1disot::calc_flux(rho, xi_vel, xi_mom, cs);
+    (flux_rho, flux_xi, xi_vel * eta_mom)
+}
+
+/// Converts conservative to primitive variables corresponding to [Euler2DIsot]
+#[inline(always)]
+pub fn cons_to_prim(rho_cons: f64, xi_mom: f64, eta_mom: f64) -> (f64, f64, f64) {
+    let (rho_prim, xi_vel) = super::euler1disot::cons_to_prim(rho_cons, xi_mom);
+    (rho_prim, xi_vel, eta_mom / rho_cons)
+}
+
+/// Converts primitive to conservative variables corresponding to [Euler2DIsot]
+#[inline(always)]
+pub fn prim_to_cons(rho_prim: f64, xi_vel: f64, eta_vel: f64) -> (f64, f64, f64) {
+    let (rho_cons, xi_mom) = super::euler1disot::prim_to_cons(rho_prim, xi_vel);
+    (rho_cons, xi_mom, eta_vel * rho_prim)
+}
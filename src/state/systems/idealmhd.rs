@@ -0,0 +1,298 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [IdealMhd] struct, which is an implementer for [Physics] for the equations of
+//! ideal magnetohydrodynamics with transverse velocities and magnetic fields.
+
+use crate::{
+    errorhandling::Validation,
+    mesh::RUNTIME_SIZE,
+    state::{EosParams, EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::{eyre::Context, Result};
+use ndarray::{aview1, Array2};
+
+const E: usize = 7;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for the equations of
+/// ideal magnetohydrodynamics (MHD) with an ideal gas, in 1.5 dimensions.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | mass density       | mass density
+/// 1              | xi velocity        | xi momentum
+/// 2              | eta velocity       | eta momentum
+/// 3              | Phi velocity       | Phi momentum
+/// 4              | eta magnetic field | eta magnetic field
+/// 5              | Phi magnetic field | Phi magnetic field
+/// 6              | pressure           | energy
+///
+/// Since nothing varies along eta and Phi, the divergence constraint on the magnetic field demands
+/// that the xi magnetic field `B_xi` is constant. It is not evolved, but taken from
+/// [MhdConfig::b_xi](crate::MhdConfig). The magnetic field is scaled such that the
+/// magnetic pressure is `0.5 * B^2`, i.e. without factors of `4 pi` or `mu_0`.
+///
+/// A constant `B_xi` is only divergence free on cartesian meshes, and the magnetic pressure and
+/// tension are missing from the geometric source terms, so this system only runs on cartesian
+/// meshes, see [Physics::HAS_GEOMETRIC_SOURCE].
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `gamma`: the adiabatic index
+/// * `v^2 = up_1 * up_1 + up_2 * up_2 + up_3 * up_3`
+/// * `B^2 = B_xi * B_xi + up_4 * up_4 + up_5 * up_5`
+/// * `p_tot = up_6 + 0.5 * B^2`: the total pressure
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0 = up_0
+/// uc_1 = up_0 * up_1
+/// uc_2 = up_0 * up_2
+/// uc_3 = up_0 * up_3
+/// uc_4 = up_4
+/// uc_5 = up_5
+/// uc_6 = up_6 / (gamma - 1) + 0.5 * up_0 * v^2 + 0.5 * B^2
+/// ```
+///
+/// They are converted back with:
+///
+/// ```text
+/// up_0 = uc_0
+/// up_1 = uc_1 / uc_0
+/// up_2 = uc_2 / uc_0
+/// up_3 = uc_3 / uc_0
+/// up_4 = uc_4
+/// up_5 = uc_5
+/// up_6 = (gamma - 1) * (uc_6 - 0.5 * uc_0 * v^2 - 0.5 * B^2)
+/// ```
+///
+/// The physical flux is calculated with:
+///
+/// ```text
+/// Fp_0 = uc_1
+/// Fp_1 = up_1 * uc_1 + p_tot - B_xi * B_xi
+/// Fp_2 = up_1 * uc_2 - B_xi * up_4
+/// Fp_3 = up_1 * uc_3 - B_xi * up_5
+/// Fp_4 = up_1 * up_4 - B_xi * up_2
+/// Fp_5 = up_1 * up_5 - B_xi * up_3
+/// Fp_6 = (uc_6 + p_tot) * up_1 - B_xi * (up_1 * B_xi + up_2 * up_4 + up_3 * up_5)
+/// ```
+///
+/// # Eigen values
+///
+/// `Variables::c_sound` holds the sound speed `a = sqrt(gamma * p / rho)`, while the eigen values
+/// are sorted as `v_xi - c_f`, `v_xi - c_a`, `v_xi - c_s`, `v_xi`, `v_xi + c_s`, `v_xi + c_a`,
+/// `v_xi + c_f`, with the Alfven speed `c_a = |B_xi| / sqrt(rho)`, and the fast and slow
+/// magnetosonic speeds:
+///
+/// ```text
+/// c_f^2 = 0.5 * (a^2 + B^2 / rho + sqrt((a^2 + B^2 / rho)^2 - 4 * a^2 * B_xi^2 / rho))
+/// c_s^2 = 0.5 * (a^2 + B^2 / rho - sqrt((a^2 + B^2 / rho)^2 - 4 * a^2 * B_xi^2 / rho))
+/// ```
+///
+/// The numerical flux schemes only use the outermost eigen values, i.e. the fast magnetosonic
+/// waves.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdealMhd<const S: usize>;
+
+unsafe impl<const S: usize> Send for IdealMhd<S> {}
+unsafe impl<const S: usize> Sync for IdealMhd<S> {}
+
+impl<const S: usize> IdealMhd<S> {
+    /// The row index for the Phi velocity in Variables::prim or Variables::cons
+    pub const JPHI: usize = 3;
+
+    /// The row index for the eta magnetic field in Variables::prim or Variables::cons
+    pub const JBETA: usize = 4;
+
+    /// The row index for the Phi magnetic field in Variables::prim or Variables::cons
+    pub const JBPHI: usize = 5;
+}
+
+impl<const S: usize> Physics<E, S> for IdealMhd<S> {
+    type Unsized = IdealMhd<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = true;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = 2;
+    const JPRESSURE: usize = 6;
+    const HAS_GEOMETRIC_SOURCE: bool = false;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "IdealMhd".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        IdealGas::validate_config(&physics_config.eos)?;
+        physics_config
+            .systems
+            .mhd
+            .validate()
+            .context("Validating SystemsConfig::mhd")
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            let prim = cons_to_prim(column(&vars.cons, i), vars.systems.mhd.b_xi, &vars.eos);
+            vars.prim.column_mut(i).assign(&aview1(&prim));
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            let cons = prim_to_cons(column(&vars.prim, i), vars.systems.mhd.b_xi, &vars.eos);
+            vars.cons.column_mut(i).assign(&aview1(&cons));
+        }
+    }
+
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.c_sound[i] = IdealGas::c_sound(
+                vars.prim[[Self::JRHO, i]],
+                0.0,
+                vars.prim[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            let xi_vel = vars.prim[[Self::JXI, i]];
+            let (c_fast, c_alfven, c_slow) = wave_speeds(column(&vars.prim, i), vars.c_sound[i], vars.systems.mhd.b_xi);
+            for (j, c) in [-c_fast, -c_alfven, -c_slow, 0.0, c_slow, c_alfven, c_fast]
+                .into_iter()
+                .enumerate()
+            {
+                vars.eigen_vals[[j, i]] = xi_vel + c;
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_min(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            let (c_fast, _, _) = wave_speeds(column(&vars.prim, i), vars.c_sound[i], vars.systems.mhd.b_xi);
+            vars.eigen_vals[[0, i]] = vars.prim[[Self::JXI, i]] - c_fast;
+        }
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_max(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            let (c_fast, _, _) = wave_speeds(column(&vars.prim, i), vars.c_sound[i], vars.systems.mhd.b_xi);
+            vars.eigen_vals[[E - 1, i]] = vars.prim[[Self::JXI, i]] + c_fast;
+        }
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            let flux = calc_flux(column(&vars.prim, i), column(&vars.cons, i), vars.systems.mhd.b_xi);
+            vars.flux.column_mut(i).assign(&aview1(&flux));
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)?;
+        super::euler1dadiabatic::validate(vars, Self::JPRESSURE)
+    }
+}
+
+/// Copies column `i` of `array` into an array
+#[inline(always)]
+fn column(array: &Array2<f64>, i: usize) -> [f64; E] {
+    std::array::from_fn(|j| array[[j, i]])
+}
+
+/// Returns the fast magnetosonic, Alfven, and slow magnetosonic speeds for the primitive
+/// variables `prim` of a single cell, with the sound speed `c_sound`
+#[inline(always)]
+pub fn wave_speeds(prim: [f64; E], c_sound: f64, b_xi: f64) -> (f64, f64, f64) {
+    let [rho, _, _, _, b_eta, b_phi, _] = prim;
+    let a2 = c_sound * c_sound;
+    let b2 = (b_xi * b_xi + b_eta * b_eta + b_phi * b_phi) / rho;
+    let b_xi2 = b_xi * b_xi / rho;
+    let root = ((a2 + b2) * (a2 + b2) - 4.0 * a2 * b_xi2).max(0.0).sqrt();
+    (
+        (0.5 * (a2 + b2 + root)).sqrt(),
+        b_xi2.sqrt(),
+        (0.5 * (a2 + b2 - root)).max(0.0).sqrt(),
+    )
+}
+
+/// Updates physical flux corresponding to [IdealMhd] for a single cell
+#[inline(always)]
+pub fn calc_flux(prim: [f64; E], cons: [f64; E], b_xi: f64) -> [f64; E] {
+    let [_, xi_vel, eta_vel, phi_vel, b_eta, b_phi, pressure] = prim;
+    let [_, xi_mom, eta_mom, phi_mom, _, _, energy] = cons;
+    let p_tot = pressure + 0.5 * (b_xi * b_xi + b_eta * b_eta + b_phi * b_phi);
+    [
+        xi_mom,
+        xi_vel * xi_mom + p_tot - b_xi * b_xi,
+        xi_vel * eta_mom - b_xi * b_eta,
+        xi_vel * phi_mom - b_xi * b_phi,
+        xi_vel * b_eta - b_xi * eta_vel,
+        xi_vel * b_phi - b_xi * phi_vel,
+        (energy + p_tot) * xi_vel - b_xi * (xi_vel * b_xi + eta_vel * b_eta + phi_vel * b_phi),
+    ]
+}
+
+/// Converts conservative to primitive variables corresponding to [IdealMhd] for a single cell
+#[inline(always)]
+pub fn cons_to_prim(cons: [f64; E], b_xi: f64, eos: &EosParams) -> [f64; E] {
+    let [rho, xi_mom, eta_mom, phi_mom, b_eta, b_phi, energy] = cons;
+    let e_kin = 0.5 / rho * (xi_mom * xi_mom + eta_mom * eta_mom + phi_mom * phi_mom);
+    let e_mag = 0.5 * (b_xi * b_xi + b_eta * b_eta + b_phi * b_phi);
+    [
+        rho,
+        xi_mom / rho,
+        eta_mom / rho,
+        phi_mom / rho,
+        b_eta,
+        b_phi,
+        IdealGas::pressure(rho, energy - e_kin - e_mag, eos),
+    ]
+}
+
+/// Converts primitive to conservative variables corresponding to [IdealMhd] for a single cell
+#[inline(always)]
+pub fn prim_to_cons(prim: [f64; E], b_xi: f64, eos: &EosParams) -> [f64; E] {
+    let [rho, xi_vel, eta_vel, phi_vel, b_eta, b_phi, pressure] = prim;
+    let e_kin = 0.5 * rho * (xi_vel * xi_vel + eta_vel * eta_vel + phi_vel * phi_vel);
+    let e_mag = 0.5 * (b_xi * b_xi + b_eta * b_eta + b_phi * b_phi);
+    [
+        rho,
+        rho * xi_vel,
+        rho * eta_vel,
+        rho * phi_vel,
+        b_eta,
+        b_phi,
+        IdealGas::internal_energy(rho, pressure, eos) + e_kin + e_mag,
+    ]
+}
@@ -0,0 +1,121 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [LinearAdvection] struct, which is an implementer for [Physics] for the
+//! 1-dimensional linear advection equation.
+
+use crate::{errorhandling::Validation, mesh::RUNTIME_SIZE, state::Physics, variables::Variables, PhysicsConfig};
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
+};
+
+const E: usize = 1;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for the 1-dimensional
+/// linear advection equation, i.e. a single quantity `q` carried along with a constant velocity
+/// `a`:
+///
+/// ```text
+/// dq/dt + a * dq/dxi = 0
+/// ```
+///
+/// This is a model equation, which is useful for testing [NumFlux](crate::rhs::NumFlux) and
+/// [TimeSolver](crate::time::TimeSolver) implementations in isolation, since its solution is
+/// just the initial condition shifted by `a * t`.
+///
+/// # Variables
+///
+/// There is only one equation, and the primitive and the conservative variable are the same:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | advected quantity  | advected quantity
+///
+/// The advected quantity plays the part of the mass density, so [JRHO](Physics::JRHO) points to
+/// it, while [JXI](Physics::JXI), [JETA](Physics::JETA), and [JPRESSURE](Physics::JPRESSURE) are
+/// set to [usize::MAX]. The velocity `a` is set through
+/// [AdvectionConfig::velocity](crate::AdvectionConfig), and it is the only eigen value of
+/// this system. The speed of sound is not used.
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_0`: the primitive variable
+/// * `uc_0`: the conservative variable
+/// * `Fp_0`: the physical flux
+///
+/// Then
+///
+/// ```text
+/// uc_0 = up_0
+/// Fp_0 = a * uc_0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinearAdvection<const S: usize>;
+
+unsafe impl<const S: usize> Send for LinearAdvection<S> {}
+unsafe impl<const S: usize> Sync for LinearAdvection<S> {}
+
+impl<const S: usize> Physics<E, S> for LinearAdvection<S> {
+    type Unsized = LinearAdvection<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = false;
+    const IS_ISOTHERMAL: bool = false;
+    const JRHO: usize = 0;
+    const JXI: usize = usize::MAX;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = usize::MAX;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "LinearAdvection".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        physics_config
+            .systems
+            .advection
+            .validate()
+            .context("Validating SystemsConfig::advection")
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        vars.prim.assign(&vars.cons);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        vars.cons.assign(&vars.prim);
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_min(vars: &mut Variables<E, S>) {
+        vars.eigen_vals.row_mut(0).fill(vars.systems.advection.velocity);
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_max(vars: &mut Variables<E, S>) {
+        vars.eigen_vals.row_mut(E - 1).fill(vars.systems.advection.velocity);
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.flux[[Self::JRHO, i]] = vars.systems.advection.velocity * vars.cons[[Self::JRHO, i]];
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        check_finite_arrayd!(vars.prim, vars.cons);
+        Ok(())
+    }
+}
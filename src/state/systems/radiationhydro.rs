@@ -0,0 +1,169 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [RadiationHydro] struct, which is an implementer for [Physics] for 1-dimensional
+//! adiabatic gas coupled to radiation in the flux-limited diffusion approximation.
+
+use crate::{
+    mesh::RUNTIME_SIZE,
+    state::{eos, EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::{
+    eyre::{ensure, ContextCompat},
+    Result,
+};
+
+const E: usize = 4;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for 1-dimensional
+/// adiabatic Euler equations of a gas that exchanges energy with radiation, as used for radiative
+/// shocks and cooling disks.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable        | conservative variable
+/// ---            | ---                       | ---
+/// 0              | mass density              | mass density
+/// 1              | xi velocity               | xi momentum
+/// 2              | gas pressure              | gas energy
+/// 3              | radiation energy density  | radiation energy density
+///
+/// The radiation energy density is found through [RadiationHydro::JRAD]. The gas is closed by an
+/// [IdealGas], and its temperature follows from the pressure through the mean molecular weight in
+/// [SystemsConfig::radiation](crate::SystemsConfig::radiation).
+///
+/// The hyperbolic part only advects the radiation energy with the gas, while its diffusion and
+/// its exchange with the gas are integrated implicitly after each time step by the
+/// [RadiationSource](crate::rhs::RadiationSource), so the stiffness of the diffusion does not
+/// limit the time step width. Radiation pressure and the work it does on the gas are neglected,
+/// which holds as long as the radiation energy density is small compared to the gas pressure.
+///
+/// [RadiationHydro] does not model the eta velocity. Accessors to it will return a vector of
+/// zeroes, whereas its equation index will return [usize::MAX].
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+///
+/// The gas rows are converted like those of [Euler1DAdiabatic](crate::state::Euler1DAdiabatic),
+/// while the radiation energy density is the same in both sets of variables, i.e. `uc_3 = up_3`.
+///
+/// The physical flux of the radiation energy density is `Fp_3 = up_1 * up_3`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RadiationHydro<const S: usize>;
+
+unsafe impl<const S: usize> Send for RadiationHydro<S> {}
+unsafe impl<const S: usize> Sync for RadiationHydro<S> {}
+
+impl<const S: usize> RadiationHydro<S> {
+    /// The row index for the radiation energy density
+    pub const JRAD: usize = 3;
+}
+
+impl<const S: usize> Physics<E, S> for RadiationHydro<S> {
+    type Unsized = RadiationHydro<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = true;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = 2;
+    const HAS_RADIATION: bool = true;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "RadiationHydro".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        IdealGas::validate_config(&physics_config.eos)?;
+        physics_config
+            .systems
+            .radiation
+            .as_ref()
+            .context("RadiationHydro needs SystemsConfig::radiation to be set!")?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::cons_to_prim::<IdealGas>(
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+            vars.prim[[Self::JRAD, i]] = vars.cons[[Self::JRAD, i]];
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::prim_to_cons::<IdealGas>(
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+            vars.cons[[Self::JRAD, i]] = vars.prim[[Self::JRAD, i]];
+        }
+    }
+
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        eos::update_c_sound::<IdealGas, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI]);
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.flux[[Self::JRHO, i]],
+                vars.flux[[Self::JXI, i]],
+                vars.flux[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::calc_flux(
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            );
+            vars.flux[[Self::JRAD, i]] = vars.prim[[Self::JXI, i]] * vars.prim[[Self::JRAD, i]];
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)?;
+        super::euler1dadiabatic::validate(vars, Self::JPRESSURE)?;
+        ensure!(
+            vars.prim.row(Self::JRAD).iter().all(|&e_rad| e_rad >= 0.0),
+            "The radiation energy density of RadiationHydro needs to be non-negative! Got {}",
+            vars.prim.row(Self::JRAD)
+        );
+        eos::validate::<IdealGas, E, S>(vars, Self::JRHO, Self::JPRESSURE, &[Self::JXI])
+    }
+}
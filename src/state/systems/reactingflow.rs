@@ -0,0 +1,204 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [ReactingFlow] struct, which is an implementer for [Physics] for adiabatic Euler
+//! equations of a gas made up of reacting species.
+
+use super::euler1disotscalars::{calc_scalar_flux, scalars_to_cons, scalars_to_prim};
+use crate::{
+    mesh::RUNTIME_SIZE,
+    state::{EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::{
+    eyre::{ensure, ContextCompat},
+    Result,
+};
+use ndarray::s;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for adiabatic Euler
+/// equations in 1D of a gas made up of the `E - 3` species of the reaction network in
+/// [SystemsConfig::network](crate::SystemsConfig::network), like partially ionized hydrogen.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable           | conservative variable
+/// ---            | ---                          | ---
+/// 0              | mass density                 | mass density
+/// 1              | xi velocity                  | xi momentum
+/// 2              | pressure                     | energy
+/// 3 + k          | mass fraction of species `k` | partial density of species `k`
+///
+/// The species are carried like the passive scalars of
+/// [Euler1DAdiabaticScalars](super::Euler1DAdiabaticScalars), so they can be written to output
+/// with [DataName::Scalar](crate::DataName::Scalar), but unlike those, they act back on the flow.
+/// The energy includes the chemical energy stored in the formation energies of the species, which
+/// does not contribute to the pressure, and the temperature follows from the pressure through the
+/// mean molecular weight of the mixture, see [ReactionNetwork](crate::state::ReactionNetwork).
+/// Reactions only move energy between the chemical and the thermal part, so the energy equation
+/// stays conservative.
+///
+/// The reactions themselves are integrated implicitly per cell after each time step by the
+/// [ReactionSource](crate::rhs::ReactionSource), while the transport is handled like any other
+/// system. The gas is closed by an [IdealGas].
+///
+/// [ReactingFlow] does not model the eta velocity. Accessors to it will return a vector of zeroes,
+/// whereas its equation index will return [usize::MAX].
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `gamma`: the adiabatic index
+/// * `q_k`: the formation energy per unit mass of species `k`
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0     = up_0
+/// uc_1     = up_0 * up_1
+/// uc_2     = up_2 / (gamma - 1) + up_0 * sum_k up_{3+k} * q_k + 0.5 * up_0 * up_1 * up_1
+/// uc_{3+k} = up_0 * up_{3+k}
+/// ```
+///
+/// They are converted back with:
+///
+/// ```text
+/// up_0     = uc_0
+/// up_1     = uc_1 / uc_0
+/// up_2     = (gamma - 1) * (uc_2 - sum_k uc_{3+k} * q_k - 0.5 / uc_0 * uc_1 * uc_1)
+/// up_{3+k} = uc_{3+k} / uc_0
+/// ```
+///
+/// The physical flux is calculated with:
+///
+/// ```text
+/// Fp_0     = uc_1
+/// Fp_1     = up_1 * uc_1 + up_2
+/// Fp_2     = (uc_2 + up_2) * up_1
+/// Fp_{3+k} = up_{3+k} * Fp_0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReactingFlow<const E: usize, const S: usize>;
+
+unsafe impl<const E: usize, const S: usize> Send for ReactingFlow<E, S> {}
+unsafe impl<const E: usize, const S: usize> Sync for ReactingFlow<E, S> {}
+
+impl<const E: usize, const S: usize> Physics<E, S> for ReactingFlow<E, S> {
+    type Unsized = ReactingFlow<E, RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = true;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = 2;
+    const NUM_SCALARS: usize = E - 3;
+    const JSCALARS: usize = 3;
+    const HAS_REACTIONS: bool = true;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "ReactingFlow".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        IdealGas::validate_config(&physics_config.eos)?;
+        let network = physics_config
+            .systems
+            .network
+            .as_ref()
+            .context("ReactingFlow needs SystemsConfig::network to be set!")?;
+        ensure!(
+            network.species.len() == Self::NUM_SCALARS,
+            "ReactingFlow with E = {} carries {} species, but SystemsConfig::network has {}!",
+            E,
+            Self::NUM_SCALARS,
+            network.species.len()
+        );
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        scalars_to_prim(vars, Self::JRHO, Self::JSCALARS);
+        for i in 0..vars.n_all() {
+            let rho = vars.cons[[Self::JRHO, i]];
+            let e_chem = vars
+                .network
+                .chemical_energy(rho, vars.prim.slice(s![Self::JSCALARS.., i]));
+            (
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::cons_to_prim::<IdealGas>(
+                rho,
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]] - e_chem,
+                &vars.eos,
+            );
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            let rho = vars.prim[[Self::JRHO, i]];
+            let e_chem = vars
+                .network
+                .chemical_energy(rho, vars.prim.slice(s![Self::JSCALARS.., i]));
+            (
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::prim_to_cons::<IdealGas>(
+                rho,
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                &vars.eos,
+            );
+            vars.cons[[Self::JPRESSURE, i]] += e_chem;
+        }
+        scalars_to_cons(vars, Self::JRHO, Self::JSCALARS);
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.flux[[Self::JRHO, i]],
+                vars.flux[[Self::JXI, i]],
+                vars.flux[[Self::JPRESSURE, i]],
+            ) = super::euler1dadiabatic::calc_flux(
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            );
+        }
+        calc_scalar_flux(vars, Self::JRHO, Self::JSCALARS);
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)?;
+        super::euler1dadiabatic::validate(vars, Self::JPRESSURE)?;
+        ensure!(
+            vars.prim.slice(s![Self::JSCALARS.., ..]).iter().all(|&y| y >= 0.0),
+            "The mass fractions of ReactingFlow need to be non-negative! Got {}",
+            vars.prim.slice(s![Self::JSCALARS.., ..])
+        );
+        Ok(())
+    }
+}
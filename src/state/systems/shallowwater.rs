@@ -0,0 +1,226 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [ShallowWater] struct, which is an implementer for [Physics] for the 1-dimensional
+//! shallow water equations.
+
+use crate::{
+    errorhandling::Validation, mesh::RUNTIME_SIZE, state::Physics, variables::Variables, PhysicsConfig,
+    SoundSpeedProfile,
+};
+use color_eyre::{
+    eyre::{ensure, Context},
+    Result,
+};
+use ndarray::Array1;
+
+const E: usize = 2;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for the 1-dimensional
+/// shallow water equations over a bottom with the elevation `b(xi)`, i.e. the bathymetry.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | water depth `h`    | surface elevation `h + b`
+/// 1              | xi velocity        | xi discharge `h * u`
+///
+/// Since the bottom does not change over time, evolving the surface elevation is equivalent to
+/// evolving the water depth. Carrying the surface elevation means that the numerical flux schemes
+/// do not see any jumps in a lake at rest, so their numerical diffusion does not stir it up. Note
+/// that sums over the conservative row 0 include the bottom, so measure the amount of water with
+/// [ShallowWater::depth] instead.
+///
+/// The equation indexes [JRHO](Physics::JRHO) and [JXI](Physics::JXI) point to the two rows,
+/// i.e. the water depth plays the part of the mass density. The gravitational acceleration `g` is
+/// set through [ShallowWaterConfig::gravity](crate::ShallowWaterConfig::gravity), and the
+/// bathymetry through [ShallowWaterConfig::bathymetry](crate::ShallowWaterConfig::bathymetry). The
+/// latter is applied by [State::apply_bathymetry](crate::state::State::apply_bathymetry), and
+/// pushes on the water through the [BathymetrySource](crate::rhs::BathymetrySource).
+///
+/// The speed of sound is replaced by the speed of gravity waves `c = sqrt(g * h)`, so this system
+/// is neither adiabatic nor isothermal.
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `b`: the elevation of the bottom
+/// * `g`: the gravitational acceleration
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0 = up_0 + b
+/// uc_1 = up_0 * up_1
+/// ```
+///
+/// They are converted back with:
+///
+/// ```text
+/// up_0 = max(uc_0 - b, 0)
+/// up_1 = uc_1 / up_0
+/// ```
+///
+/// where the velocity is set to 0 in cells shallower than [ShallowWater::DRY_DEPTH], so that
+/// the front of water running onto a dry bottom does not divide by zero.
+///
+/// The physical flux is calculated in the pre-balanced form:
+///
+/// ```text
+/// Fp_0 = uc_1
+/// Fp_1 = up_0 * up_1 * up_1 + 0.5 * g * (up_0 * up_0 - b * b)
+/// ```
+///
+/// Compared to the usual `0.5 * g * up_0 * up_0`, the pressure term is shifted by
+/// `-0.5 * g * b * b`, which the [BathymetrySource](crate::rhs::BathymetrySource) accounts for.
+///
+/// Neither the flux nor the bathymetry carry the geometric source terms of curvilinear meshes, so
+/// this system only runs on cartesian meshes, see [Physics::HAS_GEOMETRIC_SOURCE].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShallowWater<const S: usize>;
+
+unsafe impl<const S: usize> Send for ShallowWater<S> {}
+unsafe impl<const S: usize> Sync for ShallowWater<S> {}
+
+impl<const S: usize> ShallowWater<S> {
+    /// The water depth below which a cell is considered dry, i.e. its velocity is set to 0
+    pub const DRY_DEPTH: f64 = 1.0e-10;
+
+    /// Returns the water depth `h` in each cell, i.e. the conservative surface elevation `h + b`
+    /// minus the bottom, so that its sum is the conserved amount of water.
+    ///
+    /// # Arguments
+    ///
+    /// * `vars` - the [Variables] holding the conservative variables and the bottom
+    pub fn depth(vars: &Variables<E, S>) -> Array1<f64> {
+        &vars.cons.row(Self::JRHO) - &vars.bottom
+    }
+}
+
+impl<const S: usize> Physics<E, S> for ShallowWater<S> {
+    type Unsized = ShallowWater<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = false;
+    const IS_ISOTHERMAL: bool = false;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = usize::MAX;
+    const IS_NEWTONIAN_GAS: bool = false;
+    const HAS_BOTTOM: bool = true;
+    const HAS_GEOMETRIC_SOURCE: bool = false;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "ShallowWater".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        ensure!(
+            physics_config.c_sound_profile == SoundSpeedProfile::Global,
+            "ShallowWater derives its wave speed from the water depth, so PhysicsConfig::c_sound_profile needs to be \
+             SoundSpeedProfile::Global! Got {:?}",
+            physics_config.c_sound_profile
+        );
+        physics_config
+            .systems
+            .shallow_water
+            .validate()
+            .context("Validating SystemsConfig::shallow_water")
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (vars.prim[[Self::JRHO, i]], vars.prim[[Self::JXI, i]]) = cons_to_prim(
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.bottom[i],
+                Self::DRY_DEPTH,
+            );
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (vars.cons[[Self::JRHO, i]], vars.cons[[Self::JXI, i]]) =
+                prim_to_cons(vars.prim[[Self::JRHO, i]], vars.prim[[Self::JXI, i]], vars.bottom[i]);
+        }
+    }
+
+    fn update_derived_variables(vars: &mut Variables<E, S>) {
+        Self::update_c_sound(vars);
+        Self::update_eigen_vals(vars);
+    }
+
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.c_sound[i] = (vars.systems.shallow_water.gravity * vars.prim[[Self::JRHO, i]]).sqrt();
+        }
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (vars.flux[[Self::JRHO, i]], vars.flux[[Self::JXI, i]]) = calc_flux(
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.bottom[i],
+                vars.systems.shallow_water.gravity,
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        check_finite_arrayd!(vars.prim, vars.cons);
+        ensure!(
+            vars.prim.row(Self::JRHO).iter().all(|&h| h >= 0.0),
+            "The water depth turned negative! Got: {}",
+            vars.prim.row(Self::JRHO)
+        );
+        Ok(())
+    }
+}
+
+/// Updates physical flux corresponding to [ShallowWater]
+#[inline(always)]
+pub fn calc_flux(depth: f64, xi_vel: f64, discharge: f64, bottom: f64, g: f64) -> (f64, f64) {
+    (
+        discharge,
+        depth * xi_vel * xi_vel + 0.5 * g * (depth * depth - bottom * bottom),
+    )
+}
+
+/// Converts conservative to primitive variables corresponding to [ShallowWater]
+#[inline(always)]
+pub fn cons_to_prim(surface: f64, discharge: f64, bottom: f64, dry_depth: f64) -> (f64, f64) {
+    let depth = (surface - bottom).max(0.0);
+    if depth < dry_depth {
+        (depth, 0.0)
+    } else {
+        (depth, discharge / depth)
+    }
+}
+
+/// Converts primitive to conservative variables corresponding to [ShallowWater]
+#[inline(always)]
+pub fn prim_to_cons(depth: f64, xi_vel: f64, bottom: f64) -> (f64, f64) {
+    (depth + bottom, depth * xi_vel)
+}
@@ -0,0 +1,332 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+//! Exports the [Srhd] struct, which is an implementer for [Physics] for the equations of
+//! 1-dimensional special relativistic hydrodynamics.
+
+use crate::{
+    mesh::RUNTIME_SIZE,
+    state::{EquationOfState, IdealGas, Physics},
+    variables::Variables,
+    PhysicsConfig,
+};
+use color_eyre::{
+    eyre::{bail, ensure, Context},
+    Result,
+};
+
+const E: usize = 3;
+
+/// Implenter for [Physics] that manipulates [State](crate::state::State) for the equations of
+/// 1-dimensional special relativistic hydrodynamics (SRHD) with an ideal gas, in units where the
+/// speed of light is 1.
+///
+/// # Variables
+///
+/// The equations are sorted such that they correspond to the primitive and conservative variables
+/// such that:
+///
+/// equation index | primitive variable | conservative variable
+/// ---            | ---                | ---
+/// 0              | rest mass density  | lab frame mass density `D`
+/// 1              | xi velocity        | xi momentum density `S`
+/// 2              | pressure           | energy density minus the mass density `tau`
+///
+/// # Conversions
+///
+/// Let
+///
+/// * `up_j`: primitive variables at equation index `j`
+/// * `uc_j`: conservative variables at equation index `j`
+/// * `Fp_j`: physical flux at equation index `j`
+/// * `gamma`: the adiabatic index
+/// * `W = 1 / sqrt(1 - up_1 * up_1)`: the Lorentz factor
+/// * `h = 1 + gamma / (gamma - 1) * up_2 / up_0`: the specific enthalpy
+///
+/// Assuming we start with the primitive variables, the conservative variables are calculated with:
+///
+/// ```text
+/// uc_0 = up_0 * W
+/// uc_1 = up_0 * h * W^2 * up_1
+/// uc_2 = up_0 * h * W^2 - up_2 - uc_0
+/// ```
+///
+/// Going back is not possible in closed form. Instead, the pressure is found as the root of
+///
+/// ```text
+/// f(p) = (gamma - 1) * rho(p) * eps(p) - p
+/// ```
+///
+/// with Newton-Raphson iterations, where, for a given pressure `p`,
+///
+/// ```text
+/// v = uc_1 / (uc_2 + uc_0 + p)
+/// rho(p) = uc_0 / W(v)
+/// eps(p) = (uc_2 + uc_0 * (1 - W(v)) + p * (1 - W(v)^2)) / (uc_0 * W(v))
+/// ```
+///
+/// The iteration starts from the current pressure in `Variables::prim`, and stops once the relative
+/// change in the pressure drops below [Srhd::RECOVERY_TOLERANCE]. If that does not happen within
+/// [Srhd::RECOVERY_ITER_MAX] iterations, or if the conservative variables do not belong to any
+/// physical state, [Physics::update_prim] returns an error.
+///
+/// The physical flux is calculated with:
+///
+/// ```text
+/// Fp_0 = uc_0 * up_1
+/// Fp_1 = uc_1 * up_1 + up_2
+/// Fp_2 = (uc_2 + up_2) * up_1
+/// ```
+///
+/// # Eigen values
+///
+/// With the speed of sound `c = sqrt(gamma * up_2 / (up_0 * h))`, the eigen values are the
+/// relativistic sums of the velocity and the speed of sound, i.e.
+///
+/// ```text
+/// lambda_min = (up_1 - c) / (1 - up_1 * c)
+/// lambda_mid = up_1
+/// lambda_max = (up_1 + c) / (1 + up_1 * c)
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Srhd<const S: usize>;
+
+unsafe impl<const S: usize> Send for Srhd<S> {}
+unsafe impl<const S: usize> Sync for Srhd<S> {}
+
+impl<const S: usize> Srhd<S> {
+    /// The relative change in the pressure below which the recovery of the primitive variables is
+    /// considered converged
+    pub const RECOVERY_TOLERANCE: f64 = 1.0e-12;
+
+    /// The maximum number of Newton-Raphson iterations when recovering the primitive variables
+    pub const RECOVERY_ITER_MAX: usize = 100;
+}
+
+impl<const S: usize> Physics<E, S> for Srhd<S> {
+    type Unsized = Srhd<RUNTIME_SIZE>;
+    const NUM_EQ: usize = E;
+    const IS_ADIABATIC: bool = true;
+    const JRHO: usize = 0;
+    const JXI: usize = 1;
+    const JETA: usize = usize::MAX;
+    const JPRESSURE: usize = 2;
+    const IS_NEWTONIAN_GAS: bool = false;
+
+    fn new() -> Self {
+        Self
+    }
+
+    fn name() -> String {
+        "Srhd".to_string()
+    }
+
+    fn validate_physics_config(physics_config: &PhysicsConfig) -> Result<()> {
+        IdealGas::validate_config(&physics_config.eos)
+    }
+
+    #[inline(always)]
+    fn update_prim(vars: &mut Variables<E, S>) -> Result<()> {
+        for i in 0..vars.n_all() {
+            (
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+            ) = cons_to_prim(
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.gamma,
+                Self::RECOVERY_TOLERANCE,
+                Self::RECOVERY_ITER_MAX,
+            )
+            .with_context(|| format!("Recovering the primitive variables in cell {}", i))?;
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn update_cons(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            ) = prim_to_cons(
+                vars.prim[[Self::JRHO, i]],
+                vars.prim[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.gamma,
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn update_c_sound(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.c_sound[i] = c_sound(vars.prim[[Self::JRHO, i]], vars.prim[[Self::JPRESSURE, i]], vars.gamma);
+        }
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_min(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.eigen_vals[[0, i]] = add_velocities(vars.prim[[Self::JXI, i]], -vars.c_sound[i]);
+        }
+    }
+
+    #[inline(always)]
+    fn update_eigen_vals_max(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            vars.eigen_vals[[E - 1, i]] = add_velocities(vars.prim[[Self::JXI, i]], vars.c_sound[i]);
+        }
+    }
+
+    #[inline(always)]
+    fn update_flux(vars: &mut Variables<E, S>) {
+        for i in 0..vars.n_all() {
+            (
+                vars.flux[[Self::JRHO, i]],
+                vars.flux[[Self::JXI, i]],
+                vars.flux[[Self::JPRESSURE, i]],
+            ) = calc_flux(
+                vars.prim[[Self::JXI, i]],
+                vars.cons[[Self::JRHO, i]],
+                vars.cons[[Self::JXI, i]],
+                vars.prim[[Self::JPRESSURE, i]],
+                vars.cons[[Self::JPRESSURE, i]],
+            );
+        }
+    }
+
+    #[inline(always)]
+    fn validate(vars: &Variables<E, S>) -> Result<()> {
+        super::euler1disot::validate(vars, Self::JRHO)?;
+        super::euler1dadiabatic::validate(vars, Self::JPRESSURE)?;
+        for (i, xi_vel) in vars.prim.row(Self::JXI).iter().enumerate() {
+            ensure!(
+                xi_vel.abs() < 1.0,
+                "The velocity needs to be smaller than the speed of light! Got {} in cell {}",
+                xi_vel,
+                i
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Relativistic addition of the velocities `v` and `w`
+#[inline(always)]
+pub fn add_velocities(v: f64, w: f64) -> f64 {
+    (v + w) / (1.0 + v * w)
+}
+
+/// Returns the relativistic speed of sound for the rest mass density `rho` and `pressure`
+#[inline(always)]
+pub fn c_sound(rho: f64, pressure: f64, gamma: f64) -> f64 {
+    (gamma * pressure / (rho * enthalpy(rho, pressure, gamma))).sqrt()
+}
+
+/// Returns the specific enthalpy for the rest mass density `rho` and `pressure`
+#[inline(always)]
+fn enthalpy(rho: f64, pressure: f64, gamma: f64) -> f64 {
+    1.0 + gamma / (gamma - 1.0) * pressure / rho
+}
+
+/// Updates physical flux corresponding to [Srhd]
+#[inline(always)]
+pub fn calc_flux(xi_vel: f64, rho_cons: f64, xi_mom: f64, pressure: f64, tau: f64) -> (f64, f64, f64) {
+    (rho_cons * xi_vel, xi_mom * xi_vel + pressure, (tau + pressure) * xi_vel)
+}
+
+/// Converts primitive to conservative variables corresponding to [Srhd]
+#[inline(always)]
+pub fn prim_to_cons(rho_prim: f64, xi_vel: f64, pressure: f64, gamma: f64) -> (f64, f64, f64) {
+    let lorentz_sq = 1.0 / (1.0 - xi_vel * xi_vel);
+    let rho_cons = rho_prim * lorentz_sq.sqrt();
+    let rho_h_lorentz_sq = rho_prim * enthalpy(rho_prim, pressure, gamma) * lorentz_sq;
+    (
+        rho_cons,
+        rho_h_lorentz_sq * xi_vel,
+        rho_h_lorentz_sq - pressure - rho_cons,
+    )
+}
+
+/// Converts conservative to primitive variables corresponding to [Srhd] through Newton-Raphson
+/// iterations on the pressure, see [Srhd] for details.
+///
+/// # Arguments
+///
+/// * `rho_cons`, `xi_mom`, `tau` - The conservative variables
+/// * `pressure_guess` - The pressure to start iterating from, usually the one from the last time
+///   step
+/// * `gamma` - The adiabatic index
+/// * `tolerance` - The relative change in the pressure below which the iteration stops
+/// * `iter_max` - The maximum number of iterations
+#[inline(always)]
+pub fn cons_to_prim(
+    rho_cons: f64,
+    xi_mom: f64,
+    tau: f64,
+    pressure_guess: f64,
+    gamma: f64,
+    tolerance: f64,
+    iter_max: usize,
+) -> Result<(f64, f64, f64)> {
+    // the pressure needs to keep the velocity below the speed of light
+    let pressure_min = (xi_mom.abs() - tau - rho_cons).max(0.0);
+    ensure!(
+        rho_cons > 0.0 && tau.is_finite() && xi_mom.is_finite(),
+        "Cannot recover the primitive variables from D = {}, S = {}, tau = {}!",
+        rho_cons,
+        xi_mom,
+        tau
+    );
+    let mut pressure = if pressure_guess.is_finite() && pressure_guess > pressure_min {
+        pressure_guess
+    } else {
+        pressure_min.max(f64::MIN_POSITIVE)
+    };
+    for _ in 0..iter_max {
+        let xi_vel = xi_mom / (tau + rho_cons + pressure);
+        let lorentz = 1.0 / (1.0 - xi_vel * xi_vel).sqrt();
+        let rho_prim = rho_cons / lorentz;
+        let eps = (tau + rho_cons * (1.0 - lorentz) + pressure * (1.0 - lorentz * lorentz)) / (rho_cons * lorentz);
+        let f = (gamma - 1.0) * rho_prim * eps - pressure;
+        // the derivative of f, approximated as in Marti & Müller (2003)
+        let c_sound_sq = gamma * pressure / (rho_prim * enthalpy(rho_prim, pressure, gamma));
+        let df = xi_vel * xi_vel * c_sound_sq - 1.0;
+        // do not step below the minimum pressure, but approach it instead, which does not count as
+        // converging
+        let pressure_newton = pressure - f / df;
+        let pressure_new = pressure_newton.max(0.5 * (pressure + pressure_min));
+        if pressure_new == pressure_newton && (pressure_new - pressure).abs() <= tolerance * pressure_new {
+            let xi_vel = xi_mom / (tau + rho_cons + pressure_new);
+            let rho_prim = rho_cons * (1.0 - xi_vel * xi_vel).sqrt();
+            if !(pressure_new > 0.0 && rho_prim > 0.0 && xi_vel.abs() < 1.0) {
+                bail!(
+                    "The recovery converged to the unphysical state rho = {}, v = {}, p = {} for D = {}, S = {}, \
+                     tau = {}!",
+                    rho_prim,
+                    xi_vel,
+                    pressure_new,
+                    rho_cons,
+                    xi_mom,
+                    tau
+                );
+            }
+            return Ok((rho_prim, xi_vel, pressure_new));
+        }
+        pressure = pressure_new;
+    }
+    bail!(
+        "The recovery did not converge within {} iterations for D = {}, S = {}, tau = {}! Last pressure: {}",
+        iter_max,
+        rho_cons,
+        xi_mom,
+        tau,
+        pressure
+    )
+}
@@ -4,7 +4,12 @@
 
 //! Exports the [Variables] struct
 
-use crate::{errorhandling::Validation, Collectable, Data, DataName, PhysicsConfig, StructAssociation};
+use crate::{
+    errorhandling::Validation,
+    mesh::RUNTIME_SIZE,
+    state::{EosParams, ReactionNetwork},
+    Collectable, Data, DataName, PhysicsConfig, SoundSpeedProfile, StructAssociation, SystemsConfig,
+};
 use color_eyre::{
     eyre::{bail, ensure},
     Result,
@@ -32,6 +37,26 @@ pub struct Variables<const E: usize, const S: usize> {
     /// Adiabatic index
     pub gamma: f64,
 
+    /// Parameters of the equation of state
+    pub eos: EosParams,
+
+    /// How the speed of sound of isothermal systems is set
+    pub c_sound_profile: SoundSpeedProfile,
+
+    /// The parameters that only apply to specific [Physics](crate::state::Physics) types
+    pub systems: SystemsConfig,
+
+    /// The elevation of the bottom; set by
+    /// [State::apply_bathymetry](crate::state::State::apply_bathymetry)
+    pub bottom: Array1<f64>,
+
+    /// The reaction network between the species of reacting systems
+    pub network: ReactionNetwork,
+
+    /// The number of passive scalars, which occupy the last rows of `prim` and `cons`; set by
+    /// [State::new](crate::state::State::new)
+    pub n_scalars: usize,
+
     /// Helper vector that only contains zeros
     zero_vec: Array1<f64>,
 }
@@ -45,7 +70,7 @@ impl<const E: usize, const S: usize> Variables<E, S> {
     /// # Arguments
     ///
     /// * `physics_config`: carries configuration for [State](crate::state::State) and
-    /// [Physics](crate::state::physics) objects.
+    ///   [Physics](crate::state::physics) objects.
     ///
     /// # Examples
     ///
@@ -59,14 +84,54 @@ impl<const E: usize, const S: usize> Variables<E, S> {
     /// let vars = Variables::<E,S>::new(&PhysicsConfig::default());
     /// ```
     pub fn new(physics_config: &PhysicsConfig) -> Self {
+        Self::with_size(physics_config, S)
+    }
+
+    /// Constructs a new [Variables] object with `n_all` cells, which is how runtime-sized
+    /// [Variables], with `S =` [RUNTIME_SIZE], are built.
+    ///
+    /// # Arguments
+    ///
+    /// * `physics_config`: carries configuration for [State](crate::state::State) and
+    ///   [Physics](crate::state::physics) objects.
+    /// * `n_all`: the number of cells, usually [Mesh::n_all](crate::mesh::Mesh::n_all)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use corries::prelude::*;
+    /// use corries::variables::Variables;
+    ///
+    /// const S: usize = RUNTIME_SIZE;
+    /// set_Physics_and_E!(Euler1DIsot);
+    ///
+    /// let vars = Variables::<E, S>::with_size(&PhysicsConfig::default(), 100);
+    /// assert_eq!(vars.n_all(), 100);
+    /// ```
+    pub fn with_size(physics_config: &PhysicsConfig, n_all: usize) -> Self {
         Variables {
-            prim: Array2::zeros((E, S)),
-            cons: Array2::zeros((E, S)),
-            c_sound: Array1::zeros(S),
-            eigen_vals: Array2::zeros((E, S)),
-            flux: Array2::zeros((E, S)),
+            prim: Array2::zeros((E, n_all)),
+            cons: Array2::zeros((E, n_all)),
+            c_sound: Array1::zeros(n_all),
+            eigen_vals: Array2::zeros((E, n_all)),
+            flux: Array2::zeros((E, n_all)),
             gamma: physics_config.adiabatic_index,
-            zero_vec: Array1::zeros(S),
+            eos: EosParams::new(physics_config),
+            c_sound_profile: physics_config.c_sound_profile.clone(),
+            systems: physics_config.systems.clone(),
+            bottom: Array1::zeros(n_all),
+            network: ReactionNetwork::new(physics_config),
+            n_scalars: 0,
+            zero_vec: Array1::zeros(n_all),
+        }
+    }
+
+    /// Returns the number of cells, which is `S` unless these [Variables] are runtime-sized.
+    pub fn n_all(&self) -> usize {
+        if S == RUNTIME_SIZE {
+            self.prim.ncols()
+        } else {
+            S
         }
     }
 
@@ -80,6 +145,28 @@ impl<const E: usize, const S: usize> Variables<E, S> {
         self.prim.assign(&rhs.prim);
         self.cons.assign(&rhs.cons);
         self.c_sound.assign(&rhs.c_sound);
+        self.bottom.assign(&rhs.bottom);
+    }
+
+    /// Sets the speed of sound to `self.c_sound_profile`, evaluated at the coordinates `xi`.
+    ///
+    /// This is a no-op for [SoundSpeedProfile::Global].
+    pub fn apply_c_sound_profile(&mut self, xi: &Array1<f64>) -> Result<()> {
+        if self.c_sound_profile == SoundSpeedProfile::Global {
+            return Ok(());
+        }
+        for i in 0..self.n_all() {
+            let c_sound = self.c_sound_profile.c_sound(xi[i]).unwrap_or_default();
+            ensure!(
+                c_sound.is_finite() && c_sound > 0.0,
+                "The speed of sound profile needs to be finite and positive! Got c_sound = {} at xi = {} in cell {}",
+                c_sound,
+                xi[i],
+                i
+            );
+            self.c_sound[i] = c_sound;
+        }
+        Ok(())
     }
 
     /// Return a view to the vector of minimal eigen values.
@@ -101,6 +188,15 @@ impl<const E: usize, const S: usize> Collectable for Variables<E, S> {
             (StructAssociation::Physics, DataName::CSound) => {
                 self.write_vector(&self.c_sound.view(), data, mesh_offset)
             },
+            (StructAssociation::Physics, DataName::Scalar(k)) => {
+                ensure!(
+                    k < self.n_scalars,
+                    "Tried writing passive scalar {}, but there are only {} scalars!",
+                    k,
+                    self.n_scalars
+                );
+                self.write_vector(&self.prim.row(E - self.n_scalars + k), data, mesh_offset)
+            },
             (StructAssociation::Physics, x) => bail!("Tried associating {:?} with Physics!", x),
             (StructAssociation::Mesh, x) | (StructAssociation::TimeStep, x) => {
                 bail!("name.association() for {:?} returned {:?}", x, data.association)
@@ -6,6 +6,7 @@
 
 use crate::{config::CorriesConfig, mesh::Mesh, rhs::Rhs, state::Physics, NumFlux, State};
 use color_eyre::Result;
+use ndarray::Array2;
 use std::fmt::Display;
 
 pub mod rkf;
@@ -23,6 +24,18 @@ pub enum DtKind {
     /// Denotes that the time step was limited by the CFL criterium.
     Cfl,
 
+    /// Denotes that the time step was limited by the stopping time of an explicitly integrated
+    /// drag between gas and dust.
+    Drag,
+
+    /// Denotes that the time step was limited by the diffusion time across a cell of the physical
+    /// viscosity, the alpha viscosity, or the heat conduction.
+    Diffusion,
+
+    /// Denotes that the time step was limited by the time the gas needs to fall across a cell in
+    /// an external gravitational potential.
+    Gravity,
+
     /// Used when dumping state because of an error.
     ErrorDump,
 }
@@ -35,6 +48,9 @@ impl Display for DtKind {
         match self {
             DtKind::Init => write!(f, "init"),
             DtKind::Cfl => write!(f, "cfl"),
+            DtKind::Drag => write!(f, "drag"),
+            DtKind::Diffusion => write!(f, "diffusion"),
+            DtKind::Gravity => write!(f, "gravity"),
             DtKind::ErrorDump => write!(f, "err"),
         }
     }
@@ -99,10 +115,10 @@ pub trait TimeSolver<P: Physics<E, S>, const E: usize, const S: usize> {
     /// let file_name = "noh";
     ///
     /// let config = CorriesConfig::default_riemann_test::<N, E, S>(t_end, folder_name, file_name);
-    /// let (mut u, mut solver, mesh, _) = config.init_corries::<P, N, T, E, S>(|_,_,_| Ok(())).unwrap();
+    /// let (mut u, mut solver, mut mesh, _) = config.init_corries::<P, N, T, E, S>(|_,_,_| Ok(())).unwrap();
     ///
     /// // solver carries the an instance of [TimeSolver]
-    /// solver.next_solution(&mut u, &mesh).unwrap();
+    /// solver.next_solution(&mut u, &mut mesh).unwrap();
     /// ```
     fn next_solution<N: NumFlux<E, S>>(
         &mut self,
@@ -111,4 +127,13 @@ pub trait TimeSolver<P: Physics<E, S>, const E: usize, const S: usize> {
         rhs: &mut Rhs<N, E, S>,
         mesh: &Mesh<S>,
     ) -> Result<()>;
+
+    /// Returns the flux through the eastern cell faces, see [Rhs::flux], integrated over the last
+    /// call to [TimeSolver::next_solution].
+    ///
+    /// The conservative variables in each cell changed by `-mesh.deta_dphi_d_volume[i]` times the
+    /// difference between the integrated fluxes through its eastern and western faces (plus source
+    /// terms), which is what the [refinement](crate::refinement) module uses for its flux
+    /// correction.
+    fn integrated_flux(&self) -> &Array2<f64>;
 }
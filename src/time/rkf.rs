@@ -57,6 +57,12 @@ pub struct RungeKuttaFehlberg<P: Physics<E, S>, const E: usize, const S: usize>
 
     /// Stores the full intermediate solution
     utilde: State<P, E, S>,
+
+    /// Stores the flux through the cell faces for each of the intermediate solutions, see [Rhs::flux]
+    flux_bundle: Array3<f64>,
+
+    /// Flux through the cell faces integrated over the last time step
+    flux_integrated: Array2<f64>,
 }
 
 unsafe impl<P: Physics<E, S>, const E: usize, const S: usize> Send for RungeKuttaFehlberg<P, E, S> {}
@@ -79,9 +85,10 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> TimeSolver<P, E
                 ),
             };
         let order = bt.order;
+        let n_all = config.mesh_config.n_all_for::<S>()?;
         Ok(Self {
             bt,
-            k_bundle: Array3::zeros([order, E, S]),
+            k_bundle: Array3::zeros([order, E, n_all]),
             err_new: 0.0,
             err_old: 0.0,
             n_asc: 0,
@@ -89,8 +96,10 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> TimeSolver<P, E
             asc_absolute_tolerance,
             asc_timestep_friction,
             solution_accepted: true,
-            u_cons_low: Array2::zeros((E, S)),
-            utilde: State::new(&config.physics_config),
+            u_cons_low: Array2::zeros((E, n_all)),
+            utilde: State::with_size(&config.physics_config, n_all),
+            flux_bundle: Array3::zeros([order, E, n_all]),
+            flux_integrated: Array2::zeros((E, n_all)),
         })
     }
 
@@ -102,7 +111,7 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> TimeSolver<P, E
         mesh: &Mesh<S>,
     ) -> Result<()> {
         time.iter += 1;
-        time.calc_dt_expl(u, mesh)
+        time.calc_dt_expl(u, rhs, mesh)
             .context("time.calc_dt_expl at the beginning of RungeKuttaFehlberg::next_solution")?;
         time.cap_dt();
 
@@ -110,7 +119,7 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> TimeSolver<P, E
             self.solution_accepted = false;
             self.n_asc = 0;
             while !self.solution_accepted {
-                time.dt = self.calc_rkf_solution(time.dt, u, rhs, mesh).context(
+                time.dt = self.calc_rkf_solution(time.t, time.dt, u, rhs, mesh).context(
                     "RungeKuttaFehlberg::calc_rkf_solution in the while loop in RungeKuttaFehlberg::next_solution",
                 )?;
                 if self.err_new < 1.0 {
@@ -124,11 +133,18 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> TimeSolver<P, E
         // NOTE: for embedded methods, this call calculates a solution with the finalised this->dt since the solution is
         // already considered accepted. This is necessary, since this->dt might have been capped again.
         let _ = self
-            .calc_rkf_solution(time.dt, u, rhs, mesh)
+            .calc_rkf_solution(time.t, time.dt, u, rhs, mesh)
             .context("RungeKuttaFehlberg::calc_rkf_solution at the end of RungeKuttaFehlberg::next_solution")?;
 
+        self.flux_integrated.fill(0.0);
+        for q in 0..self.bt.order {
+            self.flux_integrated
+                .scaled_add(time.dt * self.bt.b_high[q], &self.flux_bundle.index_axis(Axis(0), q));
+        }
+
         u.cent.cons.assign(&self.utilde.cent.cons);
-        u.update_vars_from_cons(&mut rhs.boundary_west, &mut rhs.boundary_east, mesh);
+        u.update_vars_from_cons(&mut rhs.boundary_west, &mut rhs.boundary_east, mesh)
+            .context("Calling u.update_vars_from_cons at the end of RungeKuttaFehlberg::calc_rkf_solution")?;
         if cfg!(feature = "validation") {
             u.validate()
                 .context("Calling u.update_everything_from_prim at the end of RungeKuttaFehlberg::calc_rkf_solution")?;
@@ -137,6 +153,10 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> TimeSolver<P, E
         time.t += time.dt;
         Ok(())
     }
+
+    fn integrated_flux(&self) -> &Array2<f64> {
+        &self.flux_integrated
+    }
 }
 
 impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> RungeKuttaFehlberg<P, E, S> {
@@ -144,12 +164,14 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> RungeKuttaFehlb
     ///
     /// # Arguments
     ///
+    /// * `t` - Time at the beginning of the time step
     /// * `dt_in` - Input time step width
     /// * `u` - Input [Physics] state
     /// * `rhs` - Solves the right-hand side
     /// * `mesh` - Information about spatial properties
     fn calc_rkf_solution<N: NumFlux<E, S>>(
         &mut self,
+        t: f64,
         dt_in: f64,
         u: &mut State<P, E, S>,
         rhs: &mut Rhs<N, E, S>,
@@ -163,11 +185,13 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> RungeKuttaFehlb
             for p in 0..q {
                 // PERF: This was benchmarked against using a raw index loop; performance diffs
                 // were within the error margins
-                self.utilde.cent.cons -= &(dt_out * self.bt.a[[p, q]] * &self.k_bundle.index_axis(Axis(0), p));
+                self.utilde.cent.cons -= &(dt_out * self.bt.a[[q, p]] * &self.k_bundle.index_axis(Axis(0), p));
             }
+            rhs.update_time(t + self.bt.c[q] * dt_out);
             rhs.update(&mut self.utilde, mesh)
                 .context("Calling rhs.update while calculating k_bundle in RungeKuttaFehlberg::calc_rkf_solution")?;
             self.k_bundle.index_axis_mut(Axis(0), q).assign(&rhs.full_rhs);
+            self.flux_bundle.index_axis_mut(Axis(0), q).assign(rhs.flux());
         }
 
         // calculate high order solution
@@ -184,7 +208,7 @@ impl<P: Physics<E, S> + 'static, const E: usize, const S: usize> RungeKuttaFehlb
             }
 
             // calc err_new
-            let s = s![.., 2..S - 2];
+            let s = s![.., mesh.ixi_in..=mesh.ixi_out];
             self.err_new = Zip::from(&self.utilde.cent.cons.slice(s))
                 .and(&self.u_cons_low.slice(s))
                 .fold(0.0f64, |acc, ucons, ulow| {
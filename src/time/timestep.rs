@@ -10,7 +10,7 @@ use crate::{
     data::{Data, DataName, StructAssociation},
     mesh::Mesh,
     state::Physics,
-    Collectable, DataValue, State,
+    Collectable, DataValue, NumFlux, Rhs, State,
 };
 use color_eyre::{eyre::bail, Result};
 
@@ -99,9 +99,16 @@ impl TimeStep {
 
     /// Calculates the explicit time step width and updates it in `self.dt` and `self.dt_kind`.
     ///
+    /// Apart from the CFL criterium, the time step width is limited by the time scales of the
+    /// source terms that are integrated explicitly, see [Rhs::calc_dt_sources], and by the time the
+    /// physical viscosity and the heat conduction need to diffuse across a cell, see
+    /// [Rhs::calc_dt_diffusion]. Those are scaled with the same safety parameter as the CFL time
+    /// step.
+    ///
     /// # Arguments
     ///
     /// * `u` - The current [State]
+    /// * `rhs` - Solves the right-hand side, including the source terms
     /// * `mesh` - Information about spatial properties
     ///
     /// # Examples
@@ -125,16 +132,28 @@ impl TimeStep {
     /// // define the config instance
     /// let config = CorriesConfig::default_riemann_test::<N, E, S>(t_end, folder_name, file_name);
     /// let (mut u, mut solver, mesh, _) = config.init_corries::<P, N, T, E, S>(|_,_,_| Ok(())).unwrap();
-    /// solver.timestep.calc_dt_expl(&mut u, &mesh).unwrap();
+    /// solver.timestep.calc_dt_expl(&mut u, &solver.rhs, &mesh).unwrap();
     /// ```
-    pub fn calc_dt_expl<P: Physics<E, S>, const E: usize, const S: usize>(
+    pub fn calc_dt_expl<P: Physics<E, S>, N: NumFlux<E, S>, const E: usize, const S: usize>(
         &mut self,
         u: &mut State<P, E, S>,
-        // rhs: &Rhs<S, EQ>,
+        rhs: &Rhs<N, E, S>,
         mesh: &Mesh<S>,
     ) -> Result<()> {
-        //TODO: source term time steps
         self.dt = u.calc_dt_cfl(self.dt_cfl_param, mesh)?;
+        self.dt_kind = DtKind::Cfl;
+        if let Some((dt_sources, dt_kind)) = rhs.calc_dt_sources(u, mesh) {
+            if self.dt_cfl_param * dt_sources < self.dt {
+                self.dt = self.dt_cfl_param * dt_sources;
+                self.dt_kind = dt_kind;
+            }
+        }
+        if let Some(dt_diffusion) = rhs.calc_dt_diffusion(u, mesh) {
+            if self.dt_cfl_param * dt_diffusion < self.dt {
+                self.dt = self.dt_cfl_param * dt_diffusion;
+                self.dt_kind = DtKind::Diffusion;
+            }
+        }
         if self.dt < self.dt_min {
             bail!(
                 "Time step width dt dipped below dt_min! Got dt = {}, dt_min = {}",
@@ -142,7 +161,6 @@ impl TimeStep {
                 self.dt_min
             );
         }
-        self.dt_kind = DtKind::Cfl;
         Ok(())
     }
 
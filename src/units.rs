@@ -12,6 +12,24 @@ const LENGTH_CGS: f64 = 0.01;
 /// Mass scale in CGS units
 const MASS_CGS: f64 = 0.001;
 
+/// Boltzmann constant in SI units
+const BOLTZMANN_SI: f64 = 1.380649e-23;
+
+/// Atomic mass unit in SI units
+const ATOMIC_MASS_SI: f64 = 1.66053906660e-27;
+
+/// Energy of 1 eV in SI units
+const ELECTRON_VOLT_SI: f64 = 1.602176634e-19;
+
+/// Speed of light in SI units
+const SPEED_OF_LIGHT_SI: f64 = 2.99792458e8;
+
+/// Radiation constant `a = 4 * sigma_SB / c` in SI units
+const RADIATION_CONSTANT_SI: f64 = 7.565723e-16;
+
+/// Gravitational constant in SI units
+const GRAVITATIONAL_CONSTANT_SI: f64 = 6.67430e-11;
+
 /// Enum for the different kinds of unit systems available; defaults to SI
 #[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
 pub enum UnitsMode {
@@ -71,6 +89,55 @@ impl Units {
         }
     }
 
+    /// Returns the Boltzmann constant in the units of the simulation
+    pub fn boltzmann_constant(&self) -> f64 {
+        match self.mode {
+            UnitsMode::SI => BOLTZMANN_SI,
+            UnitsMode::CGS => BOLTZMANN_SI / (MASS_CGS * LENGTH_CGS * LENGTH_CGS),
+        }
+    }
+
+    /// Returns the atomic mass unit in the units of the simulation
+    pub fn atomic_mass_unit(&self) -> f64 {
+        match self.mode {
+            UnitsMode::SI => ATOMIC_MASS_SI,
+            UnitsMode::CGS => ATOMIC_MASS_SI / MASS_CGS,
+        }
+    }
+
+    /// Returns the energy of 1 eV in the units of the simulation
+    pub fn electron_volt(&self) -> f64 {
+        match self.mode {
+            UnitsMode::SI => ELECTRON_VOLT_SI,
+            UnitsMode::CGS => ELECTRON_VOLT_SI / (MASS_CGS * LENGTH_CGS * LENGTH_CGS),
+        }
+    }
+
+    /// Returns the speed of light in the units of the simulation
+    pub fn speed_of_light(&self) -> f64 {
+        match self.mode {
+            UnitsMode::SI => SPEED_OF_LIGHT_SI,
+            UnitsMode::CGS => SPEED_OF_LIGHT_SI / LENGTH_CGS,
+        }
+    }
+
+    /// Returns the radiation constant in the units of the simulation, i.e. the energy density of
+    /// black body radiation per temperature to the fourth power
+    pub fn radiation_constant(&self) -> f64 {
+        match self.mode {
+            UnitsMode::SI => RADIATION_CONSTANT_SI,
+            UnitsMode::CGS => RADIATION_CONSTANT_SI * LENGTH_CGS / MASS_CGS,
+        }
+    }
+
+    /// Returns the gravitational constant in the units of the simulation
+    pub fn gravitational_constant(&self) -> f64 {
+        match self.mode {
+            UnitsMode::SI => GRAVITATIONAL_CONSTANT_SI,
+            UnitsMode::CGS => GRAVITATIONAL_CONSTANT_SI * MASS_CGS / (LENGTH_CGS * LENGTH_CGS * LENGTH_CGS),
+        }
+    }
+
     /// Takes the argument representing a length, and converts it to CGS units
     pub fn convert_length_to_cgs(&self, input: f64) -> f64 {
         let si_input = self.convert_length_to_si(input);
@@ -34,15 +34,16 @@ impl Data {
     /// # Arguments
     ///
     /// * `data` - `DataName` that identifies what this struct will be holding
+    /// * `n_all` - The number of cells in the mesh
     /// * `mesh_offset` - The possible offset for vector-like values
-    pub fn new<const S: usize>(name: &DataName, mesh_offset: usize) -> Self {
+    pub fn new(name: &DataName, n_all: usize, mesh_offset: usize) -> Self {
         Self {
             name: *name,
             payload: match name.data_type() {
                 DataType::Usize => DataValue::Usize(0),
                 DataType::Float => DataValue::Float(0.0),
                 DataType::String => DataValue::String("".to_string()),
-                DataType::VectorFloat => DataValue::VectorFloat(Array1::zeros([S - 2 * mesh_offset])),
+                DataType::VectorFloat => DataValue::VectorFloat(Array1::zeros([n_all - 2 * mesh_offset])),
             },
             data_type: name.data_type(),
             association: name.association(),
@@ -59,6 +60,7 @@ impl fmt::Display for Data {
             DataName::Prim(j) => write!(f, "prim_{j}"),
             DataName::Cons(j) => write!(f, "cons_{j}"),
             DataName::CSound => write!(f, "c_sound"),
+            DataName::Scalar(k) => write!(f, "scalar_{k}"),
             DataName::T => write!(f, "t"),
             DataName::Iter => write!(f, "iter"),
             DataName::Dt => write!(f, "dt"),
@@ -93,6 +95,9 @@ pub enum DataName {
     /// Speed of sound
     CSound,
 
+    /// Mass fraction of the passive scalar with the index given through the payload
+    Scalar(usize),
+
     // ===============
     // TimeIntegration
     // ===============
@@ -160,6 +165,7 @@ impl DataName {
             Self::Prim(_) => DataType::VectorFloat,
             Self::Cons(_) => DataType::VectorFloat,
             Self::CSound => DataType::VectorFloat,
+            Self::Scalar(_) => DataType::VectorFloat,
             Self::Iter => DataType::Usize,
             Self::T => DataType::Float,
             Self::Dt => DataType::Float,
@@ -176,6 +182,7 @@ impl DataName {
             Self::Prim(_) => StructAssociation::Physics,
             Self::Cons(_) => StructAssociation::Physics,
             Self::CSound => StructAssociation::Physics,
+            Self::Scalar(_) => StructAssociation::Physics,
             Self::Iter => StructAssociation::TimeStep,
             Self::T => StructAssociation::TimeStep,
             Self::Dt => StructAssociation::TimeStep,
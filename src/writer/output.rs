@@ -94,7 +94,7 @@ impl Output {
             data: outputconfig
                 .data_names
                 .iter()
-                .map(|name| Data::new::<S>(name, mesh_offset))
+                .map(|name| Data::new(name, mesh.n_all, mesh_offset))
                 .collect(),
             first_output: true,
             rows: match outputconfig.string_conversion_mode {
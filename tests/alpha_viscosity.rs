@@ -0,0 +1,168 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_noh, prelude::*};
+const S: usize = 100;
+
+/// Point mass with `G * M = 1`
+fn point_mass() -> Option<Potential> {
+    let mass = 1.0 / Units::new(UnitsMode::SI).gravitational_constant();
+    Some(Potential::PointMass { mass, softening: 0.0 })
+}
+
+/// Config for an isothermal disk on a cylindrical mesh
+fn disk_config<N: NumFlux<E, S> + 'static, const E: usize>() -> CorriesConfig {
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/alpha", "alpha_viscosity");
+    config.mesh_config.mode = MeshMode::Cylindrical;
+    config.mesh_config.xi_in = 1.0;
+    config.mesh_config.xi_out = 2.0;
+    config.physics_config.alpha_viscosity = Some(AlphaViscosityConfig { alpha: 0.01 });
+    config.physics_config.potential = point_mass();
+    config
+}
+
+#[test]
+fn alpha_viscosity_is_rejected_for_cartesian_meshes() {
+    set_Physics_and_E!(Euler2DIsot);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config = disk_config::<N, E>();
+    config.mesh_config.mode = MeshMode::Cartesian;
+    assert!(config.init_corries::<P, N, T, E, S>(init_noh).is_err());
+}
+
+#[test]
+fn alpha_viscosity_is_rejected_without_eta_velocity() {
+    set_Physics_and_E!(Euler1DIsot);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    assert!(disk_config::<N, E>().init_corries::<P, N, T, E, S>(init_noh).is_err());
+}
+
+#[test]
+fn alpha_viscosity_is_rejected_without_point_mass() {
+    set_Physics_and_E!(Euler2DIsot);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    assert!(disk_config::<N, E>().init_corries::<P, N, T, E, S>(init_noh).is_ok());
+    let mut config = disk_config::<N, E>();
+    config.physics_config.potential = None;
+    assert!(config.init_corries::<P, N, T, E, S>(init_noh).is_err());
+    let mut config = disk_config::<N, E>();
+    config.physics_config.potential = Some(Potential::Uniform { acceleration: -1.0 });
+    assert!(config.init_corries::<P, N, T, E, S>(init_noh).is_err());
+}
+
+mod viscous_ring {
+    use super::*;
+    const S: usize = 250;
+    set_Physics_and_E!(Euler2DIsot);
+    type N = Kt<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+
+    /// The speed of sound at `xi = 1`, i.e. the aspect ratio `H / r` of the disk there
+    const ASPECT_RATIO: f64 = 0.03;
+
+    /// The alpha parameter of the viscosity
+    const ALPHA: f64 = 1.0;
+
+    /// The kinematic viscosity, which is the same everywhere since `c_s^2 / Omega_K` is constant
+    const NU: f64 = ALPHA * ASPECT_RATIO * ASPECT_RATIO;
+
+    /// The dimensionless time `tau = 12 * nu * t / R_0^2` of the initial conditions
+    const TAU_0: f64 = 0.02;
+
+    /// The density floor outside of the ring
+    const SIGMA_FLOOR: f64 = 1.0e-6;
+
+    /// Returns the modified Bessel function of the first kind `I_{1/4}(z)`, using its power series
+    fn bessel_i_quarter(z: f64) -> f64 {
+        // Gamma(1.25)
+        let mut term = (0.5 * z).powf(0.25) / 0.906_402_477_055_477;
+        let mut sum = term;
+        let mut k = 0.0;
+        while term > 1.0e-17 * sum {
+            k += 1.0;
+            term *= 0.25 * z * z / (k * (k + 0.25));
+            sum += term;
+        }
+        sum
+    }
+
+    /// Surface density of the spreading ring of Lynden-Bell & Pringle (1974) at `r` and `tau`,
+    /// for a ring of mass `pi` at `R_0 = 1`
+    fn sigma_exact(r: f64, tau: f64) -> f64 {
+        (r.powf(-0.25) / tau * (-(1.0 + r * r) / tau).exp() * bessel_i_quarter(2.0 * r / tau)).max(SIGMA_FLOOR)
+    }
+
+    /// The ring in equilibrium between the gravity of the central mass, the centrifugal force and
+    /// the pressure, drifting with the analytic radial velocity
+    /// `v_r = -3.0 / (Sigma * sqrt(r)) * d(nu * Sigma * sqrt(r)) / dr`
+    fn init_ring<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, mesh: &Mesh<S>) -> Result<()>
+    where
+        N: NumFlux<E, S>,
+        T: TimeSolver<P, E, S>,
+    {
+        let h = 1.0e-6;
+        let pressure = |r: f64| sigma_exact(r, TAU_0) * ASPECT_RATIO * ASPECT_RATIO * r.powf(-1.5);
+        for i in 0..S {
+            let r = mesh.xi_cent[i];
+            let sigma = sigma_exact(r, TAU_0);
+            let d_sigma_sqrt_r =
+                (sigma_exact(r + h, TAU_0) * (r + h).sqrt() - sigma_exact(r - h, TAU_0) * (r - h).sqrt()) / (2.0 * h);
+            let dp_dr = (pressure(r + h) - pressure(r - h)) / (2.0 * h);
+            u.cent.prim[[P::JRHO, i]] = sigma;
+            u.cent.prim[[P::JXI, i]] = if sigma > SIGMA_FLOOR {
+                -3.0 * NU * d_sigma_sqrt_r / (sigma * r.sqrt())
+            } else {
+                0.0
+            };
+            u.cent.prim[[P::JETA, i]] = (1.0 / r + r * dp_dr / sigma).sqrt();
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn viscous_ring_spreads() -> Result<()> {
+        let tau_end = 0.05;
+        let t_end = (tau_end - TAU_0) / (12.0 * NU);
+        let name = "alpha_viscosity_viscous_ring";
+        let mut config =
+            CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+        config.mesh_config.mode = MeshMode::Cylindrical;
+        config.mesh_config.xi_in = 0.3;
+        config.mesh_config.xi_out = 2.5;
+        let boundary_condition = BoundaryMode::Custom(vec![
+            (P::JRHO, CustomBoundaryMode::NoGradients),
+            (P::JXI, CustomBoundaryMode::NoGradients),
+            (P::JETA, CustomBoundaryMode::ExtrapolateEtaVelocityKepler),
+        ]);
+        config.boundary_condition_west = boundary_condition.clone();
+        config.boundary_condition_east = boundary_condition;
+        // G * M = 1, and the locally isothermal speed of sound c_s = H * Omega_K
+        config.physics_config.c_sound_profile = SoundSpeedProfile::PowerLaw {
+            c_sound_0: ASPECT_RATIO,
+            xi_0: 1.0,
+            q: 0.75,
+        };
+        config.physics_config.potential = point_mass();
+        config.physics_config.alpha_viscosity = Some(AlphaViscosityConfig { alpha: ALPHA });
+        let mut components = config
+            .init_corries::<P, N, T, E, S>(init_ring)
+            .context("While calling CorriesConfig::init_corries")?;
+        components.run_corries()?;
+
+        let (u, _, mesh, _) = &components;
+        let (mut l1_error, mut l1_norm) = (0.0, 0.0);
+        for i in mesh.ixi_in..=mesh.ixi_out {
+            let exact = sigma_exact(mesh.xi_cent[i], tau_end);
+            l1_error += (u.cent.prim[[P::JRHO, i]] - exact).abs() * mesh.cell_width[i];
+            l1_norm += exact * mesh.cell_width[i];
+        }
+        assert!(l1_error / l1_norm < 0.05, "relative L1 error: {}", l1_error / l1_norm);
+        Ok(())
+    }
+}
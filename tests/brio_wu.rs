@@ -0,0 +1,186 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{
+    initfuncs::{init_brio_wu, init_sod},
+    prelude::*,
+};
+const S: usize = 400;
+
+fn get_config<N: NumFlux<E, S> + 'static, const E: usize>(
+    adiabatic_index: f64,
+    b_xi: f64,
+    t_end: f64,
+    name: &str,
+) -> CorriesConfig {
+    CorriesConfig {
+        print_banner: false,
+        // twice as wide as usual, so that the waves stay far away from the edges
+        mesh_config: MeshConfig {
+            xi_in: 0.0,
+            ..MeshConfig::default_riemann_test()
+        },
+        physics_config: PhysicsConfig {
+            units_mode: UnitsMode::SI,
+            adiabatic_index,
+            systems: SystemsConfig {
+                mhd: MhdConfig { b_xi },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        boundary_condition_west: BoundaryMode::NoGradients,
+        boundary_condition_east: BoundaryMode::NoGradients,
+        numerics_config: NumericsConfig::default_riemann_test::<N, E, S>(t_end),
+        output_counter_max: 1,
+        writer_config: vec![
+            OutputConfig::default_stdout(),
+            OutputConfig::default_file(&format!("results/integrationtests/{}", name), name, E),
+        ],
+    }
+}
+
+fn run_brio_wu<N: NumFlux<7, S> + 'static>(name: &str) -> Result<()> {
+    set_Physics_and_E!(IdealMhd);
+    type T = RungeKuttaFehlberg<P, E, S>;
+
+    // the mesh spans [0, 2] instead of [0, 1], and running until 0.15 instead of 0.1 stretches the
+    // self-similar solution by 1.5 around the initial discontinuity, while keeping the eastern
+    // fast rarefaction away from the edge
+    let mut components = get_config::<N, E>(2.0, 0.75, 0.15, name)
+        .init_corries::<P, N, T, E, S>(init_brio_wu)
+        .context("While calling CorriesConfig::init_corries")?;
+    let cons_initial = components.0.cent.cons.clone();
+    components.run_corries()?;
+
+    // the waves stay far away from the edges of the mesh, so the velocities vanish there, and no
+    // mass, energy, or eta magnetic flux crosses them
+    let (u, mesh) = (&components.0, &components.2);
+    let s = mesh.ixi_in..=mesh.ixi_out;
+    for j in [P::JRHO, P::JBETA, P::JPRESSURE] {
+        assert_relative_eq!(
+            u.cent.cons.row(j).slice(ndarray::s![s.clone()]).sum(),
+            cons_initial.row(j).slice(ndarray::s![s.clone()]).sum(),
+            max_relative = 1.0e-10,
+            epsilon = 1.0e-10
+        );
+    }
+
+    // nothing drives motion or fields along Phi
+    assert!(u.cent.prim.row(P::JPHI).iter().all(|&x| x.abs() < 1.0e-12));
+    assert!(u.cent.prim.row(P::JBPHI).iter().all(|&x| x.abs() < 1.0e-12));
+
+    // compare the plateaus to a run with 1600 cells: the one between the fast rarefaction and
+    // the compound wave, the one between the contact and the slow shock, and the one between the
+    // slow shock and the fast rarefaction on the low pressure side
+    let at = |x: f64| s.clone().find(|&i| mesh.xi_cent[i] >= x).unwrap();
+    for (x, rho, pressure, max_relative) in [
+        (0.9, 0.6700, 0.4510, 0.08),
+        (1.165, 0.2346, 0.5219, 0.03),
+        (1.315, 0.1173, 0.0881, 0.01),
+    ] {
+        assert_relative_eq!(u.cent.prim[[P::JRHO, at(x)]], rho, max_relative = max_relative);
+        assert_relative_eq!(
+            u.cent.prim[[P::JPRESSURE, at(x)]],
+            pressure,
+            max_relative = max_relative
+        );
+    }
+
+    // the eta magnetic field turns over inside the compound wave, and the pressure drops across
+    // the slow shock
+    let b_eta = u.cent.prim.row(P::JBETA);
+    let compound_wave = s.clone().find(|&i| b_eta[i] <= 0.0).unwrap();
+    let slow_shock = s.clone().rev().find(|&i| u.cent.prim[[P::JPRESSURE, i]] > 0.3).unwrap();
+    assert_relative_eq!(mesh.xi_cent[compound_wave], 0.954, epsilon = 0.02);
+    assert_relative_eq!(mesh.xi_cent[slow_shock], 1.222, epsilon = 0.02);
+    Ok(())
+}
+
+#[test]
+fn brio_wu_hll() -> Result<()> {
+    run_brio_wu::<Hll<7, S>>("brio_wu_hll")
+}
+
+#[test]
+fn brio_wu_kt() -> Result<()> {
+    run_brio_wu::<Kt<7, S>>("brio_wu_kt")
+}
+
+/// Sod test for any adiabatic system
+fn init_sod_mhd<P, N, T, const E: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        let west = i < breakpoint_index;
+        u.cent.prim[[P::JRHO, i]] = if west { 1.0 } else { 0.125 };
+        u.cent.prim[[P::JPRESSURE, i]] = if west { 1.0 } else { 0.1 };
+    }
+    Ok(())
+}
+
+#[test]
+fn unmagnetised_mhd_matches_euler() -> Result<()> {
+    type P1 = Euler1DAdiabatic<S>;
+    type P2 = IdealMhd<S>;
+    type N1 = Kt<3, S>;
+    type N2 = Kt<7, S>;
+    type T1 = RungeKuttaFehlberg<P1, 3, S>;
+    type T2 = RungeKuttaFehlberg<P2, 7, S>;
+
+    let mut components_euler = get_config::<N1, 3>(1.4, 0.0, 0.1, "sod_euler_vs_mhd")
+        .init_corries::<P1, N1, T1, 3, S>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_euler.run_corries()?;
+    let mut components_mhd = get_config::<N2, 7>(1.4, 0.0, 0.1, "sod_mhd")
+        .init_corries::<P2, N2, T2, 7, S>(init_sod_mhd)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_mhd.run_corries()?;
+
+    let (u_euler, u_mhd) = (&components_euler.0, &components_mhd.0);
+    for (j1, j2) in [(P1::JRHO, P2::JRHO), (P1::JXI, P2::JXI), (P1::JPRESSURE, P2::JPRESSURE)] {
+        assert_relative_eq!(
+            u_mhd.cent.prim.row(j2),
+            u_euler.cent.prim.row(j1),
+            max_relative = 1.0e-12,
+            epsilon = 1.0e-12
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn mhd_needs_a_cartesian_mesh() {
+    set_Physics_and_E!(IdealMhd);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type P1 = Euler1DAdiabatic<S>;
+    type N1 = Hll<3, S>;
+    type T1 = RungeKuttaFehlberg<P1, 3, S>;
+    for mode in [MeshMode::Cylindrical, MeshMode::LogSpherical] {
+        let mesh_config = MeshConfig {
+            mode,
+            ..MeshConfig::default_riemann_test()
+        };
+        let mut config = get_config::<N, E>(2.0, 0.75, 0.1, "brio_wu_cylindrical");
+        config.mesh_config = mesh_config.clone();
+        assert!(config.init_corries::<P, N, T, E, S>(init_brio_wu).is_err());
+
+        // hydrodynamic systems bring their geometric source terms along
+        let mut config = get_config::<N1, 3>(1.4, 0.0, 0.1, "sod_cylindrical");
+        config.mesh_config = mesh_config;
+        assert!(config.init_corries::<P1, N1, T1, 3, S>(init_sod).is_ok());
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::prelude::*;
+const S: usize = 100;
+set_Physics_and_E!(DustyGas);
+type N = Hll<E, S>;
+type T = RungeKuttaFehlberg<P, E, S>;
+
+fn get_config(drag: Option<DragConfig>, t_end: f64, name: &str) -> CorriesConfig {
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    config.physics_config.adiabatic_index = 1.4;
+    config.physics_config.systems.drag = drag;
+    config
+}
+
+/// Gas moving through dust at rest, with equal amounts of gas and dust
+fn init_drift<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    u.cent.prim.row_mut(P::JRHO).fill(1.0);
+    u.cent.prim.row_mut(P::JXI).fill(1.0);
+    u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+    u.cent.prim.row_mut(P::JRHO_DUST).fill(1.0);
+    u.cent.prim.row_mut(P::JXI_DUST).fill(0.0);
+    Ok(())
+}
+
+/// Sums of the momentum and the energy of both fluids in the computational area
+fn totals(u: &State<P, E, S>, mesh: &Mesh<S>) -> (f64, f64) {
+    (mesh.ixi_in..=mesh.ixi_out)
+        .map(|i| {
+            let xi_mom_dust = u.cent.cons[[P::JXI_DUST, i]];
+            (
+                u.cent.cons[[P::JXI, i]] + xi_mom_dust,
+                u.cent.cons[[P::JPRESSURE, i]] + 0.5 * xi_mom_dust * u.cent.prim[[P::JXI_DUST, i]],
+            )
+        })
+        .fold((0.0, 0.0), |acc, x| (acc.0 + x.0, acc.1 + x.1))
+}
+
+/// Lets the relative velocity between gas and dust decay on the stopping time `stopping_time`, and
+/// compares it against the analytic solution `dv = dv_0 * exp(-1.0 * (1 + eps) * t / t_s)`
+fn run_drift(update: DragUpdate, max_relative: f64, max_relative_energy: f64, name: &str) -> Result<()> {
+    let (stopping_time, t_end) = (0.1, 0.2);
+    let drag = DragConfig { stopping_time, update };
+    let mut components = get_config(Some(drag), t_end, name)
+        .init_corries::<P, N, T, E, S>(init_drift)
+        .context("While calling CorriesConfig::init_corries")?;
+    let (xi_mom_initial, energy_initial) = totals(&components.0, &components.2);
+    components.run_corries()?;
+
+    let (u, _, mesh, _) = &components;
+    let xi_vel_diff = (2.0 * -t_end / stopping_time).exp();
+    for i in mesh.ixi_in..=mesh.ixi_out {
+        assert_relative_eq!(
+            u.cent.prim[[P::JXI, i]] - u.cent.prim[[P::JXI_DUST, i]],
+            xi_vel_diff,
+            max_relative = max_relative
+        );
+        // the centre of mass keeps its velocity
+        assert_relative_eq!(
+            0.5 * (u.cent.prim[[P::JXI, i]] + u.cent.prim[[P::JXI_DUST, i]]),
+            0.5,
+            max_relative = 1.0e-12
+        );
+    }
+
+    // the kinetic energy dissipated by the drag heats the gas
+    let (xi_mom, energy) = totals(u, mesh);
+    assert_relative_eq!(xi_mom, xi_mom_initial, max_relative = 1.0e-12);
+    assert_relative_eq!(energy, energy_initial, max_relative = max_relative_energy);
+    assert!(u.cent.prim.row(P::JPRESSURE).iter().all(|&p| p > 1.0));
+    Ok(())
+}
+
+#[test]
+fn drift_explicit() -> Result<()> {
+    // the dust kinetic energy is quadratic in its momentum, so the Runge-Kutta stages only
+    // conserve the total energy up to the truncation error
+    run_drift(DragUpdate::Explicit, 1.0e-4, 1.0e-6, "dusty_gas_drift_explicit")
+}
+
+#[test]
+fn drift_implicit() -> Result<()> {
+    // backward Euler steps slow down the relaxation a bit
+    run_drift(DragUpdate::Implicit, 0.1, 1.0e-12, "dusty_gas_drift_implicit")
+}
+
+#[test]
+fn drift_exponential() -> Result<()> {
+    run_drift(DragUpdate::Exponential, 1.0e-10, 1.0e-12, "dusty_gas_drift_exponential")
+}
+
+/// Relative error of the explicit drift against its analytic solution, with `n_steps` time steps
+/// of the Runge-Kutta scheme `rkf_mode`
+fn drift_error(rkf_mode: RKFMode, n_steps: usize) -> Result<f64> {
+    let (stopping_time, t_end) = (0.1, 0.2);
+    let drag = DragConfig {
+        stopping_time,
+        update: DragUpdate::Explicit,
+    };
+    let mut config = get_config(Some(drag), t_end, "dusty_gas_drift_convergence");
+    config
+        .writer_config
+        .retain(|output| output.stream_mode == StreamMode::Stdout);
+    config.numerics_config.dt_max = t_end / n_steps as f64;
+    config.numerics_config.time_integration_config = TimeIntegrationConfig::Rkf(RkfConfig {
+        rkf_mode,
+        asc: false,
+        ..Default::default()
+    });
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_drift)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    let (u, _, mesh, _) = &components;
+    let xi_vel_diff = (2.0 * -t_end / stopping_time).exp();
+    let i = mesh.ixi_in;
+    Ok(((u.cent.prim[[P::JXI, i]] - u.cent.prim[[P::JXI_DUST, i]]) / xi_vel_diff - 1.0).abs())
+}
+
+#[test]
+fn drift_converges_with_the_order_of_the_runge_kutta_scheme() -> Result<()> {
+    // the relaxation of the relative velocity is a linear ordinary differential equation, so
+    // halving the time step has to shrink the error by 2^order
+    for (rkf_mode, order) in [
+        (RKFMode::RK2, 2.0),
+        (RKFMode::RK3, 3.0),
+        (RKFMode::RK4, 4.0),
+        (RKFMode::RKF45, 5.0),
+    ] {
+        let (error_coarse, error_fine) = (drift_error(rkf_mode, 200)?, drift_error(rkf_mode, 400)?);
+        let measured_order = (error_coarse / error_fine).log2();
+        assert!(
+            measured_order > order - 0.2,
+            "{:?} converged with order {}",
+            rkf_mode,
+            measured_order
+        );
+    }
+    Ok(())
+}
+
+/// Sod shock tube in the gas, with as much dust as gas on both sides
+fn init_dusty_sod<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        let west = i < breakpoint_index;
+        u.cent.prim[[P::JRHO, i]] = if west { 1.0 } else { 0.125 };
+        u.cent.prim[[P::JPRESSURE, i]] = if west { 1.0 } else { 0.1 };
+        u.cent.prim[[P::JRHO_DUST, i]] = u.cent.prim[[P::JRHO, i]];
+    }
+    Ok(())
+}
+
+#[test]
+fn tightly_coupled_dust_does_not_limit_the_time_step() -> Result<()> {
+    for update in [DragUpdate::Implicit, DragUpdate::Exponential] {
+        let drag = DragConfig {
+            stopping_time: 1.0e-8,
+            update,
+        };
+        let mut components = get_config(Some(drag), 0.1, "dusty_sod")
+            .init_corries::<P, N, T, E, S>(init_dusty_sod)
+            .context("While calling CorriesConfig::init_corries")?;
+        let (u, _, mesh, _) = &components;
+        let mass_dust_initial: f64 = (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.cons[[P::JRHO_DUST, i]])
+            .sum();
+        components.run_corries()?;
+
+        // the dust is dragged along with the gas, with a time step that is set by the CFL
+        // criterium, even though the stopping time is orders of magnitude shorter
+        let (u, solver, mesh, _) = &components;
+        assert_eq!(solver.timestep.dt_kind, DtKind::Cfl);
+        assert!(solver.timestep.iter < 1000);
+        let mass_dust: f64 = (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.cons[[P::JRHO_DUST, i]])
+            .sum();
+        assert_relative_eq!(mass_dust, mass_dust_initial, max_relative = 1.0e-12);
+        assert!((mesh.ixi_in..=mesh.ixi_out)
+            .all(|i| (u.cent.prim[[P::JXI, i]] - u.cent.prim[[P::JXI_DUST, i]]).abs() < 1.0e-6));
+        assert!(u.cent.prim.row(P::JXI).iter().any(|&v| v > 0.1));
+    }
+    Ok(())
+}
+
+/// Sod shock tube in the gas, with as much dust as gas on the west side, and no dust on the east
+/// side
+fn init_half_dusty_sod<N, T>(u: &mut State<P, E, S>, solver: &mut Solver<P, N, T, E, S>, mesh: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    init_dusty_sod(u, solver, mesh)?;
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent
+        .prim
+        .row_mut(P::JRHO_DUST)
+        .slice_mut(ndarray::s![breakpoint_index..])
+        .fill(0.0);
+    Ok(())
+}
+
+#[test]
+fn dust_free_regions_are_allowed() -> Result<()> {
+    for update in [DragUpdate::Explicit, DragUpdate::Implicit, DragUpdate::Exponential] {
+        let drag = DragConfig {
+            stopping_time: 0.01,
+            update,
+        };
+        let mut components = get_config(Some(drag), 0.1, "dusty_sod_half")
+            .init_corries::<P, N, T, E, S>(init_half_dusty_sod)
+            .context("While calling CorriesConfig::init_corries")?;
+        let (u, _, mesh, _) = &components;
+        let mass_dust_initial: f64 = (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.cons[[P::JRHO_DUST, i]])
+            .sum();
+        components.run_corries()?;
+
+        // the dust follows the gas east, but next to none of it reaches the eastern edge
+        let (u, _, mesh, _) = &components;
+        let mass_dust: f64 = (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.cons[[P::JRHO_DUST, i]])
+            .sum();
+        assert_relative_eq!(mass_dust, mass_dust_initial, max_relative = 1.0e-12);
+        assert!(u.cent.prim.iter().all(|x| x.is_finite()));
+        assert!(u.cent.prim.row(P::JRHO_DUST).iter().all(|&rho| rho >= 0.0));
+        assert!(u.cent.prim[[P::JRHO_DUST, mesh.ixi_out]] < 1.0e-12);
+    }
+    Ok(())
+}
+
+#[test]
+fn explicit_drag_limits_the_time_step() -> Result<()> {
+    let drag = DragConfig {
+        stopping_time: 1.0e-4,
+        update: DragUpdate::Explicit,
+    };
+    let mut components = get_config(Some(drag), 1.0e-3, "dusty_sod_explicit")
+        .init_corries::<P, N, T, E, S>(init_dusty_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+    assert_eq!(components.1.timestep.dt_kind, DtKind::Drag);
+    Ok(())
+}
+
+#[test]
+fn drag_is_rejected_for_other_systems() {
+    type PE = Euler1DAdiabatic<S>;
+    type NE = Hll<3, S>;
+    type TE = RungeKuttaFehlberg<PE, 3, S>;
+    let drag = DragConfig {
+        stopping_time: 1.0,
+        update: DragUpdate::Explicit,
+    };
+    assert!(get_config(Some(drag), 0.1, "drag_euler")
+        .init_corries::<PE, NE, TE, 3, S>(corries::initfuncs::init_sod)
+        .is_err());
+}
@@ -0,0 +1,198 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_sod, prelude::*};
+const S: usize = 100;
+const E: usize = 3;
+
+fn get_config(eos: EosConfig, adiabatic_index: f64, t_end: f64, name: &str) -> CorriesConfig {
+    type N = Hll<E, S>;
+    CorriesConfig {
+        print_banner: false,
+        mesh_config: MeshConfig::default_riemann_test(),
+        physics_config: PhysicsConfig {
+            units_mode: UnitsMode::SI,
+            adiabatic_index,
+            eos,
+            ..Default::default()
+        },
+        boundary_condition_west: BoundaryMode::NoGradients,
+        boundary_condition_east: BoundaryMode::NoGradients,
+        numerics_config: NumericsConfig::default_riemann_test::<N, E, S>(t_end),
+        output_counter_max: 1,
+        writer_config: vec![
+            OutputConfig::default_stdout(),
+            OutputConfig::default_file(&format!("results/integrationtests/{}", name), name, E),
+        ],
+    }
+}
+
+/// Shock tube in water, with a high pressure in the west
+fn init_water<P, N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    u.cent.prim.row_mut(P::JRHO).fill(1000.0);
+    for i in 0..S {
+        u.cent.prim[[P::JPRESSURE, i]] = if i < breakpoint_index { 1.0e9 } else { 1.0e5 };
+    }
+    Ok(())
+}
+
+/// The Sod test, for any equation of state
+fn init_sod_eos<P, N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        let west = i < breakpoint_index;
+        u.cent.prim[[P::JRHO, i]] = if west { 1.0 } else { 0.125 };
+        u.cent.prim[[P::JPRESSURE, i]] = if west { 1.0 } else { 0.1 };
+    }
+    Ok(())
+}
+
+fn run_sod_ideal_gas() -> Result<State<Euler1DAdiabatic<S>, E, S>> {
+    type N = Hll<E, S>;
+    type P = Euler1DAdiabatic<S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut components = get_config(EosConfig::IdealGas, 1.4, 0.1, "sod_ideal_gas")
+        .init_corries::<P, N, T, E, S>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+    Ok(components.0)
+}
+
+#[test]
+fn stiffened_gas_without_stiffening_matches_ideal_gas() -> Result<()> {
+    type N = Hll<E, S>;
+    type P = Euler1DAdiabatic<S, StiffenedGas>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+
+    let u_ideal = run_sod_ideal_gas()?;
+    let mut components = get_config(EosConfig::StiffenedGas { p_inf: 0.0 }, 1.4, 0.1, "sod_stiffened_gas")
+        .init_corries::<P, N, T, E, S>(init_sod_eos)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    assert_relative_eq!(
+        components.0.cent.prim,
+        u_ideal.cent.prim,
+        max_relative = 1.0e-12,
+        epsilon = 1.0e-12
+    );
+    Ok(())
+}
+
+#[test]
+fn water_shock_tube() -> Result<()> {
+    type N = Hll<E, S>;
+    type P = Euler1DAdiabatic<S, StiffenedGas>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+
+    let mut components = get_config(EosConfig::StiffenedGas { p_inf: 6.0e8 }, 4.4, 5.0e-5, "water")
+        .init_corries::<P, N, T, E, S>(init_water)
+        .context("While calling CorriesConfig::init_corries")?;
+    let mass_initial: f64 = components.0.cent.cons.row(P::JRHO).sum();
+    components.run_corries()?;
+
+    // the speed of sound in water is a couple km/s, so the waves travel roughly an eighth of the
+    // mesh and leave its edges untouched, while the water is hardly compressed
+    let (u, mesh) = (&components.0, &components.2);
+    let rho = u.cent.prim.row(P::JRHO);
+    assert!(rho.iter().all(|&rho| rho > 900.0 && rho < 1150.0));
+    assert_relative_eq!(rho[mesh.ixi_in], 1000.0, max_relative = 1.0e-6);
+    assert_relative_eq!(rho[mesh.ixi_out], 1000.0, max_relative = 1.0e-6);
+
+    // with waves this weak, the velocity between them follows from the acoustic impedances on
+    // both sides, i.e. u = (p_west - p_east) / (rho * (c_west + c_east))
+    let c_sound = |p: f64| (4.4 * (p + 6.0e8) / 1000.0).sqrt();
+    let xi_vel_expect = (1.0e9 - 1.0e5) / (1000.0 * (c_sound(1.0e9) + c_sound(1.0e5)));
+    assert_relative_eq!(u.cent.prim[[P::JXI, S / 2]], xi_vel_expect, max_relative = 0.05);
+    // the mass is conserved up to the tiny bit that numerical diffusion carries across the edges
+    assert_relative_eq!(u.cent.cons.row(P::JRHO).sum(), mass_initial, max_relative = 1.0e-10);
+    Ok(())
+}
+
+#[test]
+fn mismatched_eos_is_rejected() {
+    type N = Hll<E, S>;
+    type P = Euler1DAdiabatic<S, Polytropic>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    assert!(get_config(EosConfig::IdealGas, 1.4, 0.1, "eos_mismatch")
+        .init_corries::<P, N, T, E, S>(init_water)
+        .is_err());
+}
+
+/// Writes a table for the ideal gas with adiabatic index 1.4, covering the states in the Sod test,
+/// and returns its path
+fn write_ideal_gas_table(name: &str) -> Result<String> {
+    let (n_rho, n_e) = (49, 141);
+    let mut text = format!("{} {}\n", n_rho, n_e);
+    for k in 0..n_rho {
+        for l in 0..n_e {
+            let (rho, e) = (0.05 + k as f64 * 0.025, 1.0 + l as f64 * 0.05);
+            let pressure = 0.4 * rho * e;
+            text += &format!("{} {} {} {}\n", rho, e, pressure, (1.4 * pressure / rho).sqrt());
+        }
+    }
+    let path = std::env::temp_dir().join(name).to_str().unwrap().to_string();
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+#[test]
+fn tabulated_ideal_gas_matches_ideal_gas() -> Result<()> {
+    type N = Hll<E, S>;
+    type P = Euler1DAdiabatic<S, Tabulated>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+
+    let u_ideal = run_sod_ideal_gas()?;
+    let eos = EosConfig::Tabulated {
+        table: EosTable::from_file(&write_ideal_gas_table("corries_sod_eos.txt")?)?,
+        interpolation: EosInterpolation::Bicubic,
+    };
+    let mut components = get_config(eos, 1.4, 0.1, "sod_tabulated")
+        .init_corries::<P, N, T, E, S>(init_sod_eos)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    // the pressure is bilinear in rho and e, so only the interpolated speed of sound differs
+    assert_relative_eq!(
+        components.0.cent.prim,
+        u_ideal.cent.prim,
+        max_relative = 1.0e-4,
+        epsilon = 1.0e-4
+    );
+    Ok(())
+}
+
+#[test]
+fn leaving_the_eos_table_is_reported() -> Result<()> {
+    type N = Hll<E, S>;
+    type P = Euler1DAdiabatic<S, Tabulated>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+
+    // the table for the Sod test does not cover the water shock tube
+    let eos = EosConfig::Tabulated {
+        table: EosTable::from_file(&write_ideal_gas_table("corries_water_eos.txt")?)?,
+        interpolation: EosInterpolation::Bilinear,
+    };
+    let mut components = get_config(eos, 1.4, 1.0e-4, "water_tabulated")
+        .init_corries::<P, N, T, E, S>(init_water)
+        .context("While calling CorriesConfig::init_corries")?;
+    let err = components.run_corries().unwrap_err();
+    assert!(format!("{:?}", err).contains("outside of its range"));
+    Ok(())
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_sod, prelude::*};
+const S: usize = 100;
+
+/// Uniform gas at rest
+fn init_uniform<N, T>(
+    u: &mut State<Euler1DAdiabatic<S>, 3, S>,
+    _: &mut Solver<Euler1DAdiabatic<S>, N, T, 3, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    N: NumFlux<3, S>,
+    T: TimeSolver<Euler1DAdiabatic<S>, 3, S>,
+{
+    type P = Euler1DAdiabatic<S>;
+    u.cent.prim.row_mut(P::JRHO).fill(1.0);
+    u.cent.prim.row_mut(P::JXI).fill(0.0);
+    u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+    Ok(())
+}
+
+#[test]
+fn uniform_field_lets_the_gas_fall_freely() -> Result<()> {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let (acceleration, t_end) = (-2.0, 0.5);
+    let name = "gravity_free_fall";
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    config.physics_config.potential = Some(Potential::Uniform { acceleration });
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_uniform)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    // without any gradients, the whole gas picks up the same velocity, while the work done by
+    // gravity only goes into the kinetic energy; this is only exact up to the five digits
+    // the Butcher tableau of the default time integrator is given with
+    let (u, _, mesh, _) = &components;
+    for i in mesh.ixi_in..=mesh.ixi_out {
+        assert_relative_eq!(u.cent.prim[[P::JXI, i]], acceleration * t_end, max_relative = 1.0e-5);
+        assert_relative_eq!(u.cent.prim[[P::JRHO, i]], 1.0, max_relative = 1.0e-10);
+        assert_relative_eq!(u.cent.prim[[P::JPRESSURE, i]], 1.0, max_relative = 1.0e-6);
+    }
+    Ok(())
+}
+
+#[test]
+fn time_dependent_potential_is_followed() -> Result<()> {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let t_end = 1.0;
+    let name = "gravity_time_dependent";
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    // the acceleration grows linearly in time, i.e. g = t
+    config.physics_config.potential = Some(Potential::Function(|xi, t| -t * xi));
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_uniform)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    let (u, _, mesh, _) = &components;
+    for i in mesh.ixi_in..=mesh.ixi_out {
+        assert_relative_eq!(u.cent.prim[[P::JXI, i]], 0.5 * t_end * t_end, max_relative = 1.0e-5);
+    }
+    Ok(())
+}
+
+#[test]
+fn strong_field_limits_the_time_step() -> Result<()> {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config = CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/gravity", "gravity");
+    config.physics_config.potential = Some(Potential::Uniform { acceleration: 1.0e4 });
+    let (mut u, mut solver, mesh, _) = config
+        .init_corries::<P, N, T, E, S>(init_uniform)
+        .context("While calling CorriesConfig::init_corries")?;
+    solver.timestep.calc_dt_expl(&mut u, &solver.rhs, &mesh)?;
+    assert_eq!(solver.timestep.dt_kind, DtKind::Gravity);
+    let dx = mesh.cell_width[mesh.ixi_in];
+    assert_relative_eq!(
+        solver.timestep.dt,
+        config.numerics_config.dt_cfl_param * (dx / 1.0e4).sqrt(),
+        max_relative = 1.0e-10
+    );
+    Ok(())
+}
+
+#[test]
+fn potential_is_rejected_for_shallow_water() {
+    set_Physics_and_E!(ShallowWater);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config = CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/gravity", "gravity");
+    config.physics_config.potential = Some(Potential::Uniform { acceleration: -1.0 });
+    assert!(config.init_corries::<P, N, T, E, S>(init_sod).is_err());
+}
+
+#[test]
+fn point_mass_at_the_origin_needs_a_softening() {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config = CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/gravity", "gravity");
+    config.mesh_config.xi_in = 0.0;
+    config.physics_config.potential = Some(Potential::PointMass {
+        mass: 1.0,
+        softening: 0.0,
+    });
+    assert!(config.init_corries::<P, N, T, E, S>(init_uniform).is_err());
+    config.physics_config.potential = Some(Potential::PointMass {
+        mass: 1.0,
+        softening: 0.1,
+    });
+    assert!(config.init_corries::<P, N, T, E, S>(init_uniform).is_ok());
+}
@@ -0,0 +1,106 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_noh, prelude::*};
+const S: usize = 100;
+
+const PROFILE: SoundSpeedProfile = SoundSpeedProfile::PowerLaw {
+    c_sound_0: 1.0,
+    xi_0: 1.0,
+    q: 0.5,
+};
+
+fn get_config<N: NumFlux<E, S> + 'static, const E: usize>(t_end: f64, name: &str) -> CorriesConfig {
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    config.physics_config.c_sound_profile = PROFILE;
+    config
+}
+
+/// Uniform pressure at rest, i.e. `rho = 1 / c_sound^2`, which is in equilibrium without any
+/// external forces
+fn init_equilibrium<P, N, T, const E: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        u.cent.prim[[P::JRHO, i]] = PROFILE.c_sound(mesh.xi_cent[i]).unwrap().powi(-2);
+    }
+    Ok(())
+}
+
+#[test]
+fn profile_is_applied_to_centres_and_faces() -> Result<()> {
+    set_Physics_and_E!(Euler1DIsot);
+    type N = Kt<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut components = get_config::<N, E>(0.1, "locally_isothermal_faces")
+        .init_corries::<P, N, T, E, S>(init_noh)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    let (u, mesh) = (&components.0, &components.2);
+    for (c_sound, xi) in [
+        (&u.cent.c_sound, &mesh.xi_cent),
+        (&u.west.c_sound, &mesh.xi_west),
+        (&u.east.c_sound, &mesh.xi_east),
+    ] {
+        assert_relative_eq!(
+            c_sound,
+            &xi.mapv(|xi| PROFILE.c_sound(xi).unwrap()),
+            max_relative = 1.0e-14
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn locally_isothermal_equilibrium_is_kept() -> Result<()> {
+    set_Physics_and_E!(Euler1DIsot);
+    type N = Kt<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut components = get_config::<N, E>(0.2, "locally_isothermal_equilibrium")
+        .init_corries::<P, N, T, E, S>(init_equilibrium)
+        .context("While calling CorriesConfig::init_corries")?;
+    let rho_initial = components.0.cent.prim.row(P::JRHO).to_owned();
+    components.run_corries()?;
+
+    // the no-gradients boundaries do not follow the profile, so only check the cells the waves
+    // launched at the edges of the mesh did not reach yet
+    let (u, mesh) = (&components.0, &components.2);
+    for i in (mesh.ixi_in..=mesh.ixi_out).filter(|&i| (mesh.xi_cent[i] - 1.5).abs() < 0.2) {
+        assert_relative_eq!(u.cent.prim[[P::JRHO, i]], rho_initial[i], max_relative = 1.0e-3);
+        assert!(u.cent.prim[[P::JXI, i]].abs() < 1.0e-3);
+    }
+    Ok(())
+}
+
+#[test]
+fn profile_is_rejected_for_adiabatic_systems() {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    assert!(get_config::<N, E>(0.1, "locally_isothermal_adiabatic")
+        .init_corries::<P, N, T, E, S>(init_noh)
+        .is_err());
+}
+
+#[test]
+fn non_positive_profile_is_rejected() {
+    set_Physics_and_E!(Euler1DIsot);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config = get_config::<N, E>(0.1, "locally_isothermal_negative");
+    config.physics_config.c_sound_profile = SoundSpeedProfile::Function(|xi| 1.5 - xi);
+    assert!(config.init_corries::<P, N, T, E, S>(init_noh).is_err());
+}
@@ -0,0 +1,163 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{
+    initfuncs::{init_shock_formation, init_sine_wave, init_square_wave},
+    prelude::*,
+};
+
+const T_END: f64 = 0.25;
+
+/// Runs the advected sine wave, and returns the L1 error against the shifted initial sine wave
+fn sine_wave_error_l1<N: NumFlux<1, S> + 'static, const S: usize>(
+    numflux_config: NumFluxConfig,
+    name: &str,
+) -> Result<f64> {
+    type P<const S: usize> = LinearAdvection<S>;
+    type T<const S: usize> = RungeKuttaFehlberg<P<S>, 1, S>;
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, 1, S>(T_END, &format!("results/integrationtests/{}", name), name);
+    config.mesh_config.n_all = Some(S);
+    config.numerics_config.numflux_config = numflux_config;
+    let mut components = config
+        .init_corries::<P<S>, N, T<S>, 1, S>(init_sine_wave)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    // the inflow boundary does not know about the wave, so only compare the cells that the
+    // boundary has not reached yet
+    let (u, _, mesh, _) = &components;
+    let length = mesh.xi_out - mesh.xi_in;
+    let xi_clean = mesh.xi_in + T_END + 0.1 * length;
+    let cells: Vec<usize> = (mesh.ixi_in..=mesh.ixi_out)
+        .filter(|&i| mesh.xi_cent[i] > xi_clean)
+        .collect();
+    Ok(cells
+        .iter()
+        .map(|&i| {
+            let exact =
+                1.0 + 0.5 * (2.0 * std::f64::consts::PI * (mesh.xi_cent[i] - T_END - mesh.xi_in) / length).sin();
+            (u.cent.prim[[0, i]] - exact).abs()
+        })
+        .sum::<f64>()
+        / cells.len() as f64)
+}
+
+/// Measures the convergence order between the resolutions 50, 100, and 200
+fn convergence_order(errors: &[f64]) -> f64 {
+    (errors[0] / errors[2]).log2() / 2.0
+}
+
+#[test]
+fn sine_wave_convergence_hll() -> Result<()> {
+    let errors = [
+        sine_wave_error_l1::<Hll<1, 54>, 54>(NumFluxConfig::Hll, "sine_wave_hll_54")?,
+        sine_wave_error_l1::<Hll<1, 104>, 104>(NumFluxConfig::Hll, "sine_wave_hll_104")?,
+        sine_wave_error_l1::<Hll<1, 204>, 204>(NumFluxConfig::Hll, "sine_wave_hll_204")?,
+    ];
+    let order = convergence_order(&errors);
+    assert!((0.8..1.2).contains(&order), "order = {}, errors = {:?}", order, errors);
+    Ok(())
+}
+
+#[test]
+fn sine_wave_convergence_kt() -> Result<()> {
+    let numflux_config = NumFluxConfig::Kt {
+        limiter_mode: LimiterMode::NoLimiter,
+    };
+    let errors = [
+        sine_wave_error_l1::<Kt<1, 54>, 54>(numflux_config, "sine_wave_kt_54")?,
+        sine_wave_error_l1::<Kt<1, 104>, 104>(numflux_config, "sine_wave_kt_104")?,
+        sine_wave_error_l1::<Kt<1, 204>, 204>(numflux_config, "sine_wave_kt_204")?,
+    ];
+    let order = convergence_order(&errors);
+    assert!((1.8..2.2).contains(&order), "order = {}, errors = {:?}", order, errors);
+    Ok(())
+}
+
+#[test]
+fn square_wave_stays_bounded() -> Result<()> {
+    const S: usize = 200;
+    set_Physics_and_E!(LinearAdvection);
+    type N = Kt<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let name = "square_wave_minmod";
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(T_END, &format!("results/integrationtests/{}", name), name);
+    config.numerics_config.numflux_config = NumFluxConfig::Kt {
+        limiter_mode: LimiterMode::MinMod,
+    };
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_square_wave)
+        .context("While calling CorriesConfig::init_corries")?;
+    let (u, _, mesh, _) = &components;
+    let s = mesh.ixi_in..=mesh.ixi_out;
+    let mass_initial: f64 = s.clone().map(|i| u.cent.cons[[P::JRHO, i]]).sum();
+    components.run_corries()?;
+
+    // the wave does not reach the outflow boundary, and both boundaries see the same flux
+    let u = &components.0;
+    let mass: f64 = s.clone().map(|i| u.cent.cons[[P::JRHO, i]]).sum();
+    assert_relative_eq!(mass, mass_initial, max_relative = 1.0e-12);
+
+    // a TVD limiter must not create new extrema at the jumps
+    assert!(u
+        .cent
+        .prim
+        .row(P::JRHO)
+        .iter()
+        .all(|&q| (1.0 - 1.0e-12..=2.0 + 1.0e-12).contains(&q)));
+    Ok(())
+}
+
+fn run_shock_formation<N: NumFlux<1, 200> + 'static>(name: &str) -> Result<()> {
+    const S: usize = 200;
+    set_Physics_and_E!(Burgers);
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut components =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.4, &format!("results/integrationtests/{}", name), name)
+            .init_corries::<P, N, T, E, S>(init_shock_formation)
+            .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    // the shock forms at t = 0.2 at xi = 1.4, and then moves on with a velocity of 0.5, so it sits
+    // at xi = 1.5 at t = 0.4; find it where the velocity drops through 0.5
+    let (u, _, mesh, _) = &components;
+    let i_shock = (mesh.ixi_in..mesh.ixi_out)
+        .find(|&i| u.cent.prim[[P::JXI, i]] >= 0.5 && u.cent.prim[[P::JXI, i + 1]] < 0.5)
+        .unwrap();
+    assert_relative_eq!(mesh.xi_east[i_shock], 1.5, epsilon = 2.0 * mesh.dxi);
+    assert!(u
+        .cent
+        .prim
+        .row(P::JXI)
+        .iter()
+        .all(|&v| (-1.0e-12..=1.0 + 1.0e-12).contains(&v)));
+    Ok(())
+}
+
+#[test]
+fn shock_formation_hll() -> Result<()> {
+    run_shock_formation::<Hll<1, 200>>("shock_formation_hll")
+}
+
+#[test]
+fn shock_formation_kt() -> Result<()> {
+    run_shock_formation::<Kt<1, 200>>("shock_formation_kt")
+}
+
+#[test]
+fn model_initfuncs_reject_other_systems() {
+    const S: usize = 200;
+    set_Physics_and_E!(Euler1DIsot);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let config =
+        || CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/model_reject", "model");
+    assert!(config().init_corries::<P, N, T, E, S>(init_sine_wave).is_err());
+    assert!(config().init_corries::<P, N, T, E, S>(init_square_wave).is_err());
+    assert!(config().init_corries::<P, N, T, E, S>(init_shock_formation).is_err());
+}
@@ -0,0 +1,207 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_sod, prelude::*};
+use std::f64::consts::PI;
+const S: usize = 100;
+
+/// Returns the amplitude of the mode `cos(pi * xi)` of `row` on the unit interval
+fn cosine_amplitude(row: ndarray::ArrayView1<f64>, mesh: &Mesh<S>) -> f64 {
+    (mesh.ixi_in..=mesh.ixi_out)
+        .map(|i| 2.0 * row[i] * (PI * mesh.xi_cent[i]).cos() * mesh.cell_width[i])
+        .sum()
+}
+
+/// Shear flow along eta, that varies like `cos(pi * xi)` along xi, in a gas with a speed of sound
+/// so low that the numerical diffusion of the shear is negligible
+fn init_shear<N, T>(
+    u: &mut State<Euler2DIsot<S>, 3, S>,
+    _: &mut Solver<Euler2DIsot<S>, N, T, 3, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    N: NumFlux<3, S>,
+    T: TimeSolver<Euler2DIsot<S>, 3, S>,
+{
+    type P = Euler2DIsot<S>;
+    u.cent.prim.row_mut(P::JRHO).fill(1.0);
+    u.cent.prim.row_mut(P::JXI).fill(0.0);
+    for i in 0..S {
+        u.cent.prim[[P::JETA, i]] = 0.1 * (PI * mesh.xi_cent[i]).cos();
+    }
+    u.cent.c_sound.fill(0.01);
+    Ok(())
+}
+
+#[test]
+fn shear_wave_decays_with_the_kinematic_viscosity() -> Result<()> {
+    set_Physics_and_E!(Euler2DIsot);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let (mu, t_end) = (0.02, 2.0);
+    let name = "navier_stokes_shear";
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    config.physics_config.transport = Some(TransportConfig {
+        viscosity: Viscosity::Constant(mu),
+        thermal_conductivity: 0.0,
+        mean_molecular_weight: 1.0,
+    });
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_shear)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    // the shear wave decays like exp(-1.0 * nu * pi^2 * t) with nu = mu / rho, while the gas
+    // stays at rest along xi
+    let (u, _, mesh, _) = &components;
+    assert_relative_eq!(
+        cosine_amplitude(u.cent.prim.row(P::JETA), mesh),
+        0.1 * (-mu * PI * PI * t_end).exp(),
+        max_relative = 1.0e-2
+    );
+    assert!(u.cent.prim.row(P::JXI).iter().all(|&v| v == 0.0));
+    assert!(u.cent.prim.row(P::JRHO).iter().all(|&rho| rho == 1.0));
+    Ok(())
+}
+
+/// Gas with a pressure, and thus temperature, perturbation like `cos(pi * xi)`, and a speed of
+/// sound so low that the gas does not move while the perturbation is conducted away
+fn init_hot_spot<N, T>(
+    u: &mut State<Euler1DAdiabatic<S>, 3, S>,
+    _: &mut Solver<Euler1DAdiabatic<S>, N, T, 3, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    N: NumFlux<3, S>,
+    T: TimeSolver<Euler1DAdiabatic<S>, 3, S>,
+{
+    type P = Euler1DAdiabatic<S>;
+    u.cent.prim.row_mut(P::JRHO).fill(1.0);
+    u.cent.prim.row_mut(P::JXI).fill(0.0);
+    for i in 0..S {
+        u.cent.prim[[P::JPRESSURE, i]] = 1.0e-4 * (1.0 + 0.01 * (PI * mesh.xi_cent[i]).cos());
+    }
+    Ok(())
+}
+
+#[test]
+fn temperature_perturbation_decays_with_the_thermal_diffusivity() -> Result<()> {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let (diffusivity, t_end) = (2.0, 0.05);
+    let name = "navier_stokes_conduction";
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    // K = rho * c_v * chi, with c_v = k_B / ((gamma - 1) * m_u) for a mean molecular weight of 1
+    let units = Units::new(UnitsMode::SI);
+    let gamma = config.physics_config.adiabatic_index;
+    config.physics_config.transport = Some(TransportConfig {
+        viscosity: Viscosity::Constant(0.0),
+        thermal_conductivity: diffusivity * units.boltzmann_constant() / ((gamma - 1.0) * units.atomic_mass_unit()),
+        mean_molecular_weight: 1.0,
+    });
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_hot_spot)
+        .context("While calling CorriesConfig::init_corries")?;
+    let total_energy = |u: &State<P, E, S>, mesh: &Mesh<S>| {
+        (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.cons[[P::JPRESSURE, i]] * mesh.cell_width[i])
+            .sum::<f64>()
+    };
+    let energy_initial = total_energy(&components.0, &components.2);
+    components.run_corries()?;
+
+    // at constant density, the perturbation decays like exp(-1.0 * chi * pi^2 * t)
+    let (u, solver, mesh, _) = &components;
+    assert_eq!(solver.timestep.dt_kind, DtKind::Diffusion);
+    assert_relative_eq!(
+        cosine_amplitude(u.cent.prim.row(P::JPRESSURE), mesh),
+        1.0e-6 * (-diffusivity * PI * PI * t_end).exp(),
+        max_relative = 1.0e-2
+    );
+    assert_relative_eq!(total_energy(u, mesh), energy_initial, max_relative = 1.0e-10);
+    Ok(())
+}
+
+fn transport_config(thermal_conductivity: f64) -> Option<TransportConfig> {
+    Some(TransportConfig {
+        viscosity: Viscosity::Constant(1.0),
+        thermal_conductivity,
+        mean_molecular_weight: 1.0,
+    })
+}
+
+#[test]
+fn heat_conduction_is_rejected_for_isothermal_systems() {
+    set_Physics_and_E!(Euler1DIsot);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/transport", "transport");
+    config.physics_config.transport = transport_config(1.0);
+    assert!(config.init_corries::<P, N, T, E, S>(init_sod).is_err());
+}
+
+#[test]
+fn transport_is_rejected_for_shallow_water() {
+    set_Physics_and_E!(ShallowWater);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/transport", "transport");
+    config.physics_config.transport = transport_config(0.0);
+    assert!(config.init_corries::<P, N, T, E, S>(init_sod).is_err());
+}
+
+#[test]
+fn viscosity_is_rejected_for_curvilinear_meshes() {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/transport", "transport");
+    config.mesh_config.mode = MeshMode::Cylindrical;
+    config.mesh_config.xi_in = 1.0;
+    config.mesh_config.xi_out = 2.0;
+    config.physics_config.transport = transport_config(1.0);
+    assert!(config.init_corries::<P, N, T, E, S>(init_sod).is_err());
+}
+
+/// Gas at rest with uniform density and pressure
+fn init_uniform<N, T>(
+    u: &mut State<Euler1DAdiabatic<S, StiffenedGas>, 3, S>,
+    _: &mut Solver<Euler1DAdiabatic<S, StiffenedGas>, N, T, 3, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    N: NumFlux<3, S>,
+    T: TimeSolver<Euler1DAdiabatic<S, StiffenedGas>, 3, S>,
+{
+    type P = Euler1DAdiabatic<S, StiffenedGas>;
+    u.cent.prim.row_mut(P::JRHO).fill(1.0);
+    u.cent.prim.row_mut(P::JXI).fill(0.0);
+    u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+    Ok(())
+}
+
+#[test]
+fn heat_conduction_is_rejected_for_non_ideal_gases() {
+    const E: usize = 3;
+    type P = Euler1DAdiabatic<S, StiffenedGas>;
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/transport", "transport");
+    config.physics_config.eos = EosConfig::StiffenedGas { p_inf: 0.0 };
+    config.physics_config.transport = transport_config(1.0);
+    assert!(config.clone().init_corries::<P, N, T, E, S>(init_uniform).is_err());
+
+    // a constant viscosity does not depend on the temperature
+    config.physics_config.transport = transport_config(0.0);
+    assert!(config.init_corries::<P, N, T, E, S>(init_uniform).is_ok());
+}
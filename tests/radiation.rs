@@ -0,0 +1,158 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_sod, prelude::*};
+const S: usize = 100;
+set_Physics_and_E!(RadiationHydro);
+type N = Hll<E, S>;
+type T = RungeKuttaFehlberg<P, E, S>;
+
+fn get_config(radiation: RadiationConfig, t_end: f64, name: &str) -> CorriesConfig {
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    config.physics_config.adiabatic_index = 5.0 / 3.0;
+    config.physics_config.systems.radiation = Some(radiation);
+    config
+}
+
+/// Width parameter `4 * D * t_0` of the initial radiation pulse
+const PULSE_WIDTH: f64 = 2.0;
+
+/// Gaussian pulse of radiation in a cold gas at rest, where the numerical diffusion of the radiation
+/// energy density along with the sound waves is negligible
+fn init_pulse<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, mesh: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    u.cent.prim.row_mut(P::JRHO).fill(1.0);
+    u.cent.prim.row_mut(P::JXI).fill(0.0);
+    u.cent.prim.row_mut(P::JPRESSURE).fill(1.0e-4);
+    for i in 0..S {
+        u.cent.prim[[P::JRAD, i]] = 1.0e-6 * (-(mesh.xi_cent[i] - 10.0).powi(2) / PULSE_WIDTH).exp();
+    }
+    Ok(())
+}
+
+#[test]
+fn radiation_pulse_diffuses() -> Result<()> {
+    // the Rosseland opacity is chosen such that D = c / (3 * kappa_R * rho) = 1, while the
+    // radiation does not exchange energy with the gas
+    let c_light = Units::new(UnitsMode::SI).speed_of_light();
+    let radiation = RadiationConfig {
+        mean_molecular_weight: 1.0,
+        opacity_rosseland: Opacity::Constant(c_light / 3.0),
+        opacity_planck: Opacity::Constant(0.0),
+    };
+    let t_end = 1.0;
+    let mut config = get_config(radiation, t_end, "radiation_pulse");
+    config.mesh_config.xi_in = 0.0;
+    config.mesh_config.xi_out = 20.0;
+    // the implicit diffusion is only first order accurate in time
+    config.numerics_config.dt_max = 0.01;
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_pulse)
+        .context("While calling CorriesConfig::init_corries")?;
+    let energy_initial = components.0.cent.cons.row(P::JPRESSURE).to_owned();
+    components.run_corries()?;
+
+    // compare against the spreading Gaussian E = E_0 * sqrt(t_0 / (t_0 + t)) * exp(-x^2 / (4 * D * (t_0 + t)))
+    let (u, _, mesh, _) = &components;
+    let width = PULSE_WIDTH + 4.0 * t_end;
+    let (mut l1_error, mut l1_norm) = (0.0, 0.0);
+    for i in mesh.ixi_in..=mesh.ixi_out {
+        let exact = 1.0e-6 * (PULSE_WIDTH / width).sqrt() * (-(mesh.xi_cent[i] - 10.0).powi(2) / width).exp();
+        l1_error += (u.cent.prim[[P::JRAD, i]] - exact).abs();
+        l1_norm += exact;
+    }
+    assert!(l1_error / l1_norm < 0.01, "relative L1 error: {}", l1_error / l1_norm);
+
+    // the gas stays untouched
+    assert_eq!(u.cent.cons.row(P::JPRESSURE), energy_initial);
+    assert!(u.cent.prim.row(P::JXI).iter().all(|&v| v == 0.0));
+    Ok(())
+}
+
+/// Hot gas at rest without any radiation
+fn init_hot_gas<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let units = Units::new(UnitsMode::SI);
+    let (rho, temperature) = (1.0, 1.0e6);
+    u.cent.prim.row_mut(P::JRHO).fill(rho);
+    u.cent.prim.row_mut(P::JXI).fill(0.0);
+    u.cent
+        .prim
+        .row_mut(P::JPRESSURE)
+        .fill(rho * units.boltzmann_constant() * temperature / units.atomic_mass_unit());
+    u.cent.prim.row_mut(P::JRAD).fill(0.0);
+    Ok(())
+}
+
+#[test]
+fn gas_and_radiation_relax_to_thermal_equilibrium() -> Result<()> {
+    // Kramers-like Planck opacity of 10 m^2/kg at 1e6 K, which couples gas and radiation on a far
+    // shorter time scale than the time step width
+    let radiation = RadiationConfig {
+        mean_molecular_weight: 1.0,
+        opacity_rosseland: Opacity::Constant(1.0e8),
+        opacity_planck: Opacity::PowerLaw {
+            kappa_0: 1.0e22,
+            rho_exponent: 1.0,
+            temperature_exponent: -3.5,
+        },
+    };
+    let mut components = get_config(radiation, 1.0e-6, "radiation_relaxation")
+        .init_corries::<P, N, T, E, S>(init_hot_gas)
+        .context("While calling CorriesConfig::init_corries")?;
+    // in the first time step, a bit of the radiation emitted next to the boundaries diffuses into
+    // the ghost cells before the boundary conditions catch up, so look at the centre of the mesh
+    let i = S / 2;
+    let total_initial = components.0.cent.cons[[P::JPRESSURE, i]];
+    components.run_corries()?;
+
+    // the radiation takes up energy from the gas until it reaches a * T^4
+    let (u, _, mesh, _) = &components;
+    let units = Units::new(UnitsMode::SI);
+    let temperature = u.cent.prim[[P::JPRESSURE, i]] * units.atomic_mass_unit()
+        / (u.cent.prim[[P::JRHO, i]] * units.boltzmann_constant());
+    assert!(temperature < 1.0e6);
+    assert_relative_eq!(
+        u.cent.prim[[P::JRAD, i]],
+        units.radiation_constant() * temperature.powi(4),
+        max_relative = 1.0e-6
+    );
+    assert_relative_eq!(
+        u.cent.cons[[P::JPRESSURE, i]] + u.cent.cons[[P::JRAD, i]],
+        total_initial,
+        max_relative = 1.0e-12
+    );
+    assert!((mesh.ixi_in..=mesh.ixi_out).all(|k| u.cent.prim[[P::JRAD, k]] > 0.0));
+    Ok(())
+}
+
+#[test]
+fn radiation_is_rejected_for_other_systems() {
+    type P = Euler1DAdiabatic<S>;
+    type N = Hll<3, S>;
+    type T = RungeKuttaFehlberg<P, 3, S>;
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, 3, S>(0.1, "results/integrationtests/radiation", "radiation");
+    config.physics_config.systems.radiation = Some(RadiationConfig {
+        mean_molecular_weight: 1.0,
+        opacity_rosseland: Opacity::Constant(1.0),
+        opacity_planck: Opacity::Constant(1.0),
+    });
+    assert!(config.init_corries::<P, N, T, 3, S>(init_sod).is_err());
+}
+
+#[test]
+fn radiation_hydro_needs_radiation() {
+    let config = CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/radiation", "radiation");
+    assert!(config.init_corries::<P, N, T, E, S>(init_pulse).is_err());
+}
@@ -0,0 +1,226 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_sod, prelude::*};
+use ndarray::s;
+const S: usize = 100;
+
+/// Uniform hydrogen at rest, with a bit of ionization to get the collisions going
+fn init_hydrogen<P, N, T, const E: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let (rho, temperature, ionization) = (1.0e-10, 3.0e4, 1.0e-3);
+    let m_electron = 5.48579909065e-4;
+    let m_hydrogen = 1.007276466621 + m_electron;
+    let mass_fractions = ndarray::arr1(&[
+        1.0 - ionization,
+        ionization * (1.0 - m_electron / m_hydrogen),
+        ionization * m_electron / m_hydrogen,
+    ]);
+    let pressure = u.cent.network.pressure(rho, temperature, mass_fractions.view());
+    u.cent.prim.fill(0.0);
+    u.cent.prim.row_mut(P::JRHO).fill(rho);
+    u.cent.prim.row_mut(P::JPRESSURE).fill(pressure);
+    for (k, y) in mass_fractions.iter().enumerate() {
+        u.cent.prim.row_mut(P::JSCALARS + k).fill(*y);
+    }
+    Ok(())
+}
+
+#[test]
+fn hydrogen_relaxes_to_ionization_equilibrium() -> Result<()> {
+    set_Physics_and_E!(ReactingFlow, 3);
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let name = "hydrogen_ionization";
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(100.0, &format!("results/integrationtests/{}", name), name);
+    config.mesh_config.xi_in = 0.0;
+    config.mesh_config.xi_out = 1.0e8;
+    config.physics_config.units_mode = UnitsMode::CGS;
+    config.physics_config.adiabatic_index = 5.0 / 3.0;
+    let network_config = NetworkConfig::hydrogen_ionization(&UnitsMode::CGS);
+    config.physics_config.systems.network = Some(network_config.clone());
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_hydrogen)
+        .context("While calling CorriesConfig::init_corries")?;
+    let (u, _, mesh, _) = &components;
+    let (i, s_species) = (mesh.ixi_in, s![P::JSCALARS.., mesh.ixi_in]);
+    let energy_initial = u.cent.cons[[P::JPRESSURE, i]];
+    let temperature_initial = u.cent.network.temperature(
+        u.cent.prim[[P::JRHO, i]],
+        u.cent.prim[[P::JPRESSURE, i]],
+        u.cent.prim.slice(s_species),
+    );
+    components.run_corries()?;
+
+    // the gas stays at rest, and only trades thermal for chemical energy
+    let (u, _, mesh, _) = &components;
+    assert_relative_eq!(u.cent.cons[[P::JPRESSURE, i]], energy_initial, max_relative = 1.0e-12);
+    assert!(u.cent.prim.row(P::JXI).iter().all(|&v| v == 0.0));
+    for j in 0..E {
+        assert!(u.cent.prim.row(j).iter().all(|&x| x == u.cent.prim[[j, i]]));
+    }
+    let mass_fractions = u.cent.prim.slice(s_species);
+    assert_relative_eq!(mass_fractions.sum(), 1.0, max_relative = 1.0e-10);
+
+    // ionization releases as many electrons as protons
+    let masses: Vec<f64> = network_config.species.iter().map(|species| species.mass).collect();
+    assert_relative_eq!(
+        mass_fractions[1] / masses[1],
+        mass_fractions[2] / masses[2],
+        max_relative = 1.0e-8
+    );
+
+    // in equilibrium, ionization and recombination balance at the final temperature, i.e.
+    // k_ion(T) * n_H = k_rec(T) * n_H+
+    let temperature = u.cent.network.temperature(
+        u.cent.prim[[P::JRHO, i]],
+        u.cent.prim[[P::JPRESSURE, i]],
+        mass_fractions,
+    );
+    assert!(temperature < temperature_initial);
+    let rate_coefficient = |reaction: &ReactionConfig| {
+        reaction.prefactor
+            * temperature.powf(reaction.temperature_exponent)
+            * (-reaction.activation_temperature / temperature).exp()
+    };
+    let (k_ion, k_rec) = (
+        rate_coefficient(&network_config.reactions[0]),
+        rate_coefficient(&network_config.reactions[1]),
+    );
+    assert!(mass_fractions[1] > 0.1);
+    assert_relative_eq!(
+        k_ion * mass_fractions[0] / masses[0],
+        k_rec * mass_fractions[1] / masses[1],
+        max_relative = 1.0e-6
+    );
+    assert!((mesh.ixi_in..=mesh.ixi_out).all(|j| u.cent.prim[[P::JPRESSURE, j]] > 0.0));
+    Ok(())
+}
+
+/// Sod test, where the gas in the west is made up of a light species, and the gas in the east of
+/// a heavy one
+fn init_sod_species<P, N, T, const E: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        let west = i < breakpoint_index;
+        u.cent.prim[[P::JRHO, i]] = if west { 1.0 } else { 0.125 };
+        u.cent.prim[[P::JPRESSURE, i]] = if west { 1.0 } else { 0.1 };
+        u.cent.prim[[P::JSCALARS, i]] = if west { 1.0 } else { 0.0 };
+        u.cent.prim[[P::JSCALARS + 1, i]] = if west { 0.0 } else { 1.0 };
+    }
+    Ok(())
+}
+
+#[test]
+fn inert_species_match_euler_sod() -> Result<()> {
+    type P1 = Euler1DAdiabatic<S>;
+    type P2 = ReactingFlow<5, S>;
+    type N1 = Hll<3, S>;
+    type N2 = Hll<5, S>;
+    type T1 = RungeKuttaFehlberg<P1, 3, S>;
+    type T2 = RungeKuttaFehlberg<P2, 5, S>;
+
+    let mut config_1d = CorriesConfig::default_riemann_test::<N1, 3, S>(
+        0.25,
+        "results/integrationtests/reacting_sod_euler",
+        "reacting_sod_euler",
+    );
+    config_1d.physics_config.adiabatic_index = 1.4;
+    let mut components_1d = config_1d
+        .init_corries::<P1, N1, T1, 3, S>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_1d.run_corries()?;
+
+    let species = |name: &str, mass| SpeciesConfig {
+        name: name.to_string(),
+        mass,
+        formation_energy: 0.0,
+    };
+    let mut config_species = CorriesConfig::default_riemann_test::<N2, 5, S>(
+        0.25,
+        "results/integrationtests/reacting_sod_species",
+        "reacting_sod_species",
+    );
+    config_species.physics_config.adiabatic_index = 1.4;
+    config_species.physics_config.systems.network = Some(NetworkConfig {
+        species: vec![species("light", 1.0), species("heavy", 2.0)],
+        reactions: vec![],
+        tolerance: 1.0e-10,
+    });
+    let mut components_species = config_species
+        .init_corries::<P2, N2, T2, 5, S>(init_sod_species)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_species.run_corries()?;
+
+    // without reactions and formation energies, the species are just carried along, and only
+    // change the temperature
+    let (u_1d, u_species, mesh) = (&components_1d.0, &components_species.0, &components_species.2);
+    assert_eq!(components_1d.1.timestep.t, components_species.1.timestep.t);
+    for j in [P1::JRHO, P1::JXI, P1::JPRESSURE] {
+        assert_relative_eq!(u_species.cent.prim.row(j), u_1d.cent.prim.row(j), epsilon = 1.0e-12);
+    }
+    // the heavy species in the east has twice the mean molecular weight, and thus twice the
+    // temperature of the light species in the west at the same pressure and mass density
+    let (i_west, i_east) = (mesh.ixi_in, mesh.ixi_out);
+    let temperature_per_specific_pressure = |i: usize| {
+        let (rho, pressure) = (
+            u_species.cent.prim[[P2::JRHO, i]],
+            u_species.cent.prim[[P2::JPRESSURE, i]],
+        );
+        u_species
+            .cent
+            .network
+            .temperature(rho, pressure, u_species.cent.prim.slice(s![P2::JSCALARS.., i]))
+            * rho
+            / pressure
+    };
+    assert_relative_eq!(
+        temperature_per_specific_pressure(i_east),
+        2.0 * temperature_per_specific_pressure(i_west),
+        max_relative = 1.0e-6
+    );
+    Ok(())
+}
+
+#[test]
+fn network_is_rejected_for_other_systems() {
+    type P = Euler1DAdiabatic<S>;
+    type N = Hll<3, S>;
+    type T = RungeKuttaFehlberg<P, 3, S>;
+    let mut config = CorriesConfig::default_riemann_test::<N, 3, S>(0.1, "results/integrationtests/network", "network");
+    config.physics_config.systems.network = Some(NetworkConfig::hydrogen_ionization(&UnitsMode::SI));
+    assert!(config.init_corries::<P, N, T, 3, S>(init_sod).is_err());
+}
+
+#[test]
+fn reacting_flow_needs_a_matching_network() {
+    type P = ReactingFlow<5, S>;
+    type N = Hll<5, S>;
+    type T = RungeKuttaFehlberg<P, 5, S>;
+    let mut config = CorriesConfig::default_riemann_test::<N, 5, S>(0.1, "results/integrationtests/network", "network");
+    assert!(config.init_corries::<P, N, T, 5, S>(init_sod_species).is_err());
+    config.physics_config.systems.network = Some(NetworkConfig::hydrogen_ionization(&UnitsMode::SI));
+    assert!(config.init_corries::<P, N, T, 5, S>(init_sod_species).is_err());
+}
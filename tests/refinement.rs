@@ -0,0 +1,347 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{initfuncs::init_sod, prelude::*};
+const S: usize = 54;
+const SF: usize = 4 * 10 + 4;
+
+set_Physics_and_E!(Euler1DAdiabatic);
+type PF = Euler1DAdiabatic<SF>;
+
+fn totals<const SX: usize>(u: &State<impl Physics<E, SX>, E, SX>, mesh: &Mesh<SX>) -> [f64; E] {
+    let mut totals = [0.0; E];
+    for (j, total) in totals.iter_mut().enumerate() {
+        *total = (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.cons[[j, i]] * mesh.volume[i])
+            .sum();
+    }
+    totals
+}
+
+/// Reflecting walls on both ends of the coarse mesh, so that nothing leaves the computational area
+fn walls() -> BoundaryMode {
+    BoundaryMode::Custom(vec![
+        (P::JRHO, CustomBoundaryMode::NoGradients),
+        (P::JXI, CustomBoundaryMode::Reflecting),
+        (P::JPRESSURE, CustomBoundaryMode::NoGradients),
+    ])
+}
+
+fn refinement_config() -> RefinementConfig {
+    RefinementConfig {
+        ratio: 4,
+        patches: vec![(1.4, 1.6)],
+    }
+}
+
+#[test]
+fn sod_refined_conserves_mass_and_energy() -> Result<()> {
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type NF = Hll<E, SF>;
+    type TF = RungeKuttaFehlberg<PF, E, SF>;
+
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.15, "results/integrationtests/sod_refined", "sod_refined");
+    config.physics_config.adiabatic_index = 1.4;
+    config.boundary_condition_west = walls();
+    config.boundary_condition_east = walls();
+    let mut refinement =
+        StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config(), init_sod)
+            .context("While calling StaticRefinement::new")?;
+    let totals_initial = totals(&refinement.u, &refinement.mesh);
+
+    refinement.run_corries()?;
+
+    // the waves cross the edges of the patch, and the coarse mesh is closed off by walls, so mass
+    // and energy are only conserved if the flux correction works
+    let totals_final = totals(&refinement.u, &refinement.mesh);
+    assert!(refinement.solver.timestep.t >= 0.15 - 1.0e-12);
+    assert_relative_eq!(totals_final[P::JRHO], totals_initial[P::JRHO], max_relative = 1.0e-12);
+    // the conservative energy shares its index with the primitive pressure
+    assert_relative_eq!(
+        totals_final[P::JPRESSURE],
+        totals_initial[P::JPRESSURE],
+        max_relative = 1.0e-12
+    );
+
+    // the patch has to be in sync with the coarse cells it covers
+    let patch = &refinement.patches[0];
+    assert_eq!(patch.solver.timestep.t, refinement.solver.timestep.t);
+    let totals_patch = totals(&patch.u, &patch.mesh);
+    let totals_covered: f64 = (patch.i_coarse_west..=patch.i_coarse_east)
+        .map(|k| refinement.u.cent.cons[[P::JRHO, k]] * refinement.mesh.volume[k])
+        .sum();
+    assert_relative_eq!(totals_patch[P::JRHO], totals_covered, max_relative = 1.0e-12);
+    Ok(())
+}
+
+#[test]
+fn sod_refined_with_transport_conserves_energy() -> Result<()> {
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type NF = Hll<E, SF>;
+    type TF = RungeKuttaFehlberg<PF, E, SF>;
+
+    let mut config = CorriesConfig::default_riemann_test::<N, E, S>(
+        0.15,
+        "results/integrationtests/sod_refined_transport",
+        "sod_refined_transport",
+    );
+    config.physics_config.adiabatic_index = 1.4;
+    config.physics_config.units_mode = UnitsMode::SI;
+    config.physics_config.transport = Some(TransportConfig {
+        viscosity: Viscosity::Constant(1.0e-3),
+        thermal_conductivity: 100.0,
+        mean_molecular_weight: 1.0,
+    });
+    config.boundary_condition_west = walls();
+    config.boundary_condition_east = walls();
+    let mut refinement =
+        StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config(), init_sod)
+            .context("While calling StaticRefinement::new")?;
+    let totals_initial = totals(&refinement.u, &refinement.mesh);
+
+    refinement.run_corries()?;
+
+    // the viscosity and the heat conduction carry energy through the edges of the patch too, which
+    // the flux correction needs to account for; the walls do no work and conduct no heat
+    let totals_final = totals(&refinement.u, &refinement.mesh);
+    assert_relative_eq!(totals_final[P::JRHO], totals_initial[P::JRHO], max_relative = 1.0e-12);
+    assert_relative_eq!(
+        totals_final[P::JPRESSURE],
+        totals_initial[P::JPRESSURE],
+        max_relative = 1.0e-12
+    );
+    Ok(())
+}
+
+#[test]
+fn alpha_viscosity_is_rejected() {
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type NF = Hll<E, SF>;
+    type TF = RungeKuttaFehlberg<PF, E, SF>;
+    let mut config = CorriesConfig::default_riemann_test::<N, E, S>(
+        0.15,
+        "results/integrationtests/sod_refined_rejected",
+        "sod_refined_rejected",
+    );
+    config.physics_config.alpha_viscosity = Some(AlphaViscosityConfig { alpha: 0.01 });
+    assert!(StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config(), init_sod).is_err());
+}
+
+#[test]
+fn runtime_sized_patches_match_compile_time_ones() -> Result<()> {
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type NF = Hll<E, SF>;
+    type TF = RungeKuttaFehlberg<PF, E, SF>;
+    type PR = Euler1DAdiabatic<RUNTIME_SIZE>;
+    type NR = Hll<E, RUNTIME_SIZE>;
+    type TR = RungeKuttaFehlberg<PR, E, RUNTIME_SIZE>;
+
+    let mut config = CorriesConfig::default_riemann_test::<N, E, S>(
+        0.1,
+        "results/integrationtests/sod_refined_runtime",
+        "sod_refined_runtime",
+    );
+    config.physics_config.adiabatic_index = 1.4;
+    let mut refinement =
+        StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config(), init_sod)
+            .context("While calling StaticRefinement::new")?;
+    refinement.run_corries()?;
+    let mut refinement_runtime =
+        StaticRefinement::<P, N, T, PR, NR, TR, E, S, RUNTIME_SIZE>::new(&config, &refinement_config(), init_sod)
+            .context("While calling StaticRefinement::new")?;
+    refinement_runtime.run_corries()?;
+    assert_eq!(refinement_runtime.u.cent.prim, refinement.u.cent.prim);
+    assert_eq!(
+        refinement_runtime.patches[0].u.cent.prim,
+        refinement.patches[0].u.cent.prim
+    );
+
+    // runtime-sized patches may cover any number of coarse cells
+    let mut refinement_config = refinement_config();
+    refinement_config.patches = vec![(1.2, 1.3), (1.4, 1.8)];
+    let refinement_runtime =
+        StaticRefinement::<P, N, T, PR, NR, TR, E, S, RUNTIME_SIZE>::new(&config, &refinement_config, init_sod)
+            .context("While calling StaticRefinement::new")?;
+    assert_eq!(refinement_runtime.patches[0].mesh.n_all, 4 * 5 + 4);
+    assert_eq!(refinement_runtime.patches[1].mesh.n_all, 4 * 20 + 4);
+    Ok(())
+}
+
+#[test]
+fn misplaced_patches_are_rejected() {
+    type N = Kt<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type NF = Kt<E, SF>;
+    type TF = RungeKuttaFehlberg<PF, E, SF>;
+    let config = CorriesConfig::default_riemann_test::<N, E, S>(
+        0.15,
+        "results/integrationtests/sod_refined_rejected",
+        "sod_refined_rejected",
+    );
+
+    // not aligned with the coarse cell faces
+    let mut refinement_config = refinement_config();
+    refinement_config.patches = vec![(1.41, 1.61)];
+    assert!(StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config, init_sod).is_err());
+
+    // covers a different number of cells than SF allows for
+    refinement_config.patches = vec![(1.4, 1.8)];
+    assert!(StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config, init_sod).is_err());
+
+    // touches the edge of the coarse mesh
+    refinement_config.patches = vec![(1.0, 1.2)];
+    assert!(StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config, init_sod).is_err());
+}
+
+#[test]
+fn mismatched_patch_physics_is_rejected() {
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type Pfs = Euler1DAdiabatic<SF, StiffenedGas>;
+    type NF = Hll<E, SF>;
+    type TF = RungeKuttaFehlberg<Pfs, E, SF>;
+    let config = CorriesConfig::default_riemann_test::<N, E, S>(
+        0.15,
+        "results/integrationtests/sod_refined_rejected",
+        "sod_refined_rejected",
+    );
+
+    // same system on the patches, but with a different equation of state
+    assert!(StaticRefinement::<P, N, T, Pfs, NF, TF, E, S, SF>::new(&config, &refinement_config(), init_sod).is_err());
+}
+
+#[test]
+fn sod_adaptive_follows_the_shock() -> Result<()> {
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type NF = Hll<E, SF>;
+    type TF = RungeKuttaFehlberg<PF, E, SF>;
+
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.12, "results/integrationtests/sod_adaptive", "sod_adaptive");
+    config.physics_config.adiabatic_index = 1.4;
+    config.boundary_condition_west = walls();
+    config.boundary_condition_east = walls();
+    let adaptive_config = AdaptiveRefinementConfig {
+        ratio: 4,
+        indicator: RefinementIndicator::Lohner { filter: 0.01 },
+        threshold: 0.5,
+        buffer: 2,
+        regrid_interval: 2,
+        max_patches: 4,
+    };
+    let mut amr = AdaptiveRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &adaptive_config, init_sod)
+        .context("While calling AdaptiveRefinement::new")?;
+    let totals_initial = totals(&amr.levels.u, &amr.levels.mesh);
+    let patch_initial = amr.levels.patches[0].i_coarse_west;
+
+    amr.run_corries()?;
+
+    // patches get created and removed along the way, without breaking conservation; the walls keep
+    // the numerical diffusion in front of the shock from leaking out through the edge of the mesh
+    let totals_final = totals(&amr.levels.u, &amr.levels.mesh);
+    assert_relative_eq!(totals_final[P::JRHO], totals_initial[P::JRHO], max_relative = 1.0e-12);
+    assert_relative_eq!(
+        totals_final[P::JPRESSURE],
+        totals_initial[P::JPRESSURE],
+        max_relative = 1.0e-12
+    );
+
+    // the shock has moved east, and the easternmost patch moved with it; the density behind the shock
+    // is about 0.27, and 0.125 in front of it
+    let rho = amr.levels.u.cent.prim.row(P::JRHO);
+    let i_shock = (amr.levels.mesh.ixi_in..=amr.levels.mesh.ixi_out)
+        .filter(|&i| rho[i] > 0.2)
+        .max()
+        .unwrap();
+    let patch = amr.levels.patches.last().unwrap();
+    assert!(patch.i_coarse_west > patch_initial);
+    assert!(patch.i_coarse_west <= i_shock && i_shock < patch.i_coarse_east);
+    Ok(())
+}
+
+mod isothermal {
+    use super::*;
+    use corries::initfuncs::init_noh;
+    set_Physics_and_E!(Euler1DIsot);
+    type PF = Euler1DIsot<SF>;
+
+    /// Checks that the patch kept the speed of sound of the coarse mesh, and that the gas behind
+    /// the shocks, which both start from the centre of the patch, got compressed to
+    /// `rho = (3 + sqrt(5)) / 2`, which is what the isothermal Noh test with `c_sound = 1` and an
+    /// inflow speed of 1 yields; a patch without pressure would pile up the gas without bound.
+    fn check_noh_patch(patch: &Patch<PF, Hll<E, SF>, RungeKuttaFehlberg<PF, E, SF>, E, SF>) {
+        assert!(patch.u.cent.c_sound.iter().all(|&c| c == 1.0));
+        let i_centre = SF / 2;
+        for i in i_centre - 4..i_centre + 4 {
+            assert_relative_eq!(
+                patch.u.cent.prim[[P::JRHO, i]],
+                0.5 * (3.0 + 5.0_f64.sqrt()),
+                max_relative = 0.01
+            );
+        }
+    }
+
+    #[test]
+    fn noh_refined_keeps_the_speed_of_sound() -> Result<()> {
+        type N = Hll<E, S>;
+        type T = RungeKuttaFehlberg<P, E, S>;
+        type NF = Hll<E, SF>;
+        type TF = RungeKuttaFehlberg<PF, E, SF>;
+
+        let config =
+            CorriesConfig::default_riemann_test::<N, E, S>(0.1, "results/integrationtests/noh_refined", "noh_refined");
+        let mut refinement =
+            StaticRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &refinement_config(), init_noh)
+                .context("While calling StaticRefinement::new")?;
+        assert!(refinement.patches[0].u.cent.c_sound.iter().all(|&c| c == 1.0));
+        refinement.run_corries()?;
+        check_noh_patch(&refinement.patches[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn noh_adaptive_keeps_the_speed_of_sound() -> Result<()> {
+        type N = Hll<E, S>;
+        type T = RungeKuttaFehlberg<P, E, S>;
+        type NF = Hll<E, SF>;
+        type TF = RungeKuttaFehlberg<PF, E, SF>;
+
+        let config = CorriesConfig::default_riemann_test::<N, E, S>(
+            0.2,
+            "results/integrationtests/noh_adaptive",
+            "noh_adaptive",
+        );
+        let adaptive_config = AdaptiveRefinementConfig {
+            ratio: 4,
+            indicator: RefinementIndicator::Lohner { filter: 0.01 },
+            threshold: 0.5,
+            buffer: 2,
+            regrid_interval: 2,
+            max_patches: 4,
+        };
+        let mut amr = AdaptiveRefinement::<P, N, T, PF, NF, TF, E, S, SF>::new(&config, &adaptive_config, init_noh)
+            .context("While calling AdaptiveRefinement::new")?;
+        // the density is uniform in the beginning, so all patches are built along the way, once the
+        // shocks run away from each other, and they need to pick up the speed of sound then
+        assert!(amr.levels.patches.is_empty());
+        amr.run_corries()?;
+
+        let patches = &amr.levels.patches;
+        assert!(patches.len() >= 2);
+        for patch in patches.iter() {
+            assert!(patch.u.cent.c_sound.iter().all(|&c| c == 1.0));
+            let rho_max = patch.u.cent.prim.row(P::JRHO).fold(0.0_f64, |acc, &rho| acc.max(rho));
+            assert_relative_eq!(rho_max, 0.5 * (3.0 + 5.0_f64.sqrt()), max_relative = 0.02);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2022-2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use color_eyre::{eyre::Context, Result};
+use corries::{
+    initfuncs::{init_noh, init_sod},
+    prelude::*,
+};
+
+/// Runs the isothermal Noh test on a mesh with `n_all` cells, and returns the primitive variables
+/// together with the L1 error of the density against the analytic solution.
+///
+/// With a speed of sound and an inflow speed of 1, the gas behind the two shocks that run away from
+/// the centre at `xi = 1.5` gets compressed to `rho = (3 + sqrt(5)) / 2`, and the shocks move with
+/// `1 / (rho - 1)`.
+fn run_noh<const S: usize>(n_all: usize, name: &str) -> Result<(ndarray::Array2<f64>, f64)> {
+    type P<const S: usize> = Euler1DIsot<S>;
+    const E: usize = 2;
+    let t_end = 0.5;
+    let mut config = CorriesConfig::default_riemann_test::<Hll<E, S>, E, S>(
+        t_end,
+        &format!("results/integrationtests/{}_{}", name, n_all),
+        name,
+    );
+    config.mesh_config.n_all = Some(n_all);
+    let mut components = config
+        .init_corries::<P<S>, Hll<E, S>, RungeKuttaFehlberg<P<S>, E, S>, E, S>(init_noh)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    let (u, mesh) = (&components.0, &components.2);
+    assert_eq!(u.cent.prim.ncols(), n_all);
+    assert_eq!(mesh.n_comp, n_all - 4);
+    let rho_shocked = 0.5 * (3.0 + 5.0_f64.sqrt());
+    let xi_shock = t_end / (rho_shocked - 1.0);
+    let error = (mesh.ixi_in..=mesh.ixi_out)
+        .map(|i| {
+            let rho = if (mesh.xi_cent[i] - 1.5).abs() < xi_shock {
+                rho_shocked
+            } else {
+                1.0
+            };
+            (u.cent.prim[[P::<S>::JRHO, i]] - rho).abs() * mesh.cell_width[i]
+        })
+        .sum();
+    Ok((u.cent.prim.clone(), error))
+}
+
+#[test]
+fn noh_resolution_sweep() -> Result<()> {
+    let mut errors = vec![];
+    for n_all in [54, 104, 204] {
+        let (prim, error) = dispatch_S!(n_all, [54, 104, 204], { run_noh::<S>(n_all, "noh_resolution") })?;
+
+        // the runtime-sized run does the same operations with different loop bounds
+        let (prim_runtime, error_runtime) = run_noh::<RUNTIME_SIZE>(n_all, "noh_runtime_resolution")?;
+        assert_eq!(prim_runtime, prim);
+        assert_eq!(error_runtime, error);
+        errors.push(error);
+    }
+
+    // the shocks smear out over a fixed number of cells, so the error shrinks with the cell width
+    for w in errors.windows(2) {
+        assert!(
+            w[1] < 0.7 * w[0],
+            "The error did not drop with the resolution! Got {:?}",
+            errors
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn mismatching_sizes_are_rejected() {
+    const S: usize = 54;
+    let mut meshconfig = MeshConfig::default_riemann_test();
+    meshconfig.n_all = Some(104);
+    assert!(Mesh::<S>::new(&meshconfig).is_err());
+    assert!(dispatch_S!(30, [54, 104], { Ok(S) }).is_err());
+}
+
+#[test]
+fn runtime_size_matches_compile_time_size() -> Result<()> {
+    const S: usize = 100;
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Kt<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    type PR = Euler1DAdiabatic<RUNTIME_SIZE>;
+    type NR = Kt<E, RUNTIME_SIZE>;
+    type TR = RungeKuttaFehlberg<PR, E, RUNTIME_SIZE>;
+
+    let mut config = CorriesConfig::default_riemann_test::<N, E, S>(
+        0.1,
+        "results/integrationtests/sod_const_size",
+        "sod_const_size",
+    );
+    config.physics_config.adiabatic_index = 1.4;
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    let mut config_runtime = CorriesConfig::default_riemann_test::<NR, E, RUNTIME_SIZE>(
+        0.1,
+        "results/integrationtests/sod_runtime_size",
+        "sod_runtime_size",
+    );
+    config_runtime.physics_config.adiabatic_index = 1.4;
+    config_runtime.mesh_config.n_all = Some(S);
+    let mut components_runtime = config_runtime
+        .init_corries::<PR, NR, TR, E, RUNTIME_SIZE>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_runtime.run_corries()?;
+
+    // both run the exact same operations, just with different loop bounds
+    assert_eq!(components_runtime.1.timestep.t, components.1.timestep.t);
+    assert_eq!(components_runtime.0.cent.prim, components.0.cent.prim);
+    Ok(())
+}
+
+#[test]
+fn runtime_size_needs_n_all() {
+    assert!(Mesh::<RUNTIME_SIZE>::new(&MeshConfig::default_riemann_test()).is_err());
+}
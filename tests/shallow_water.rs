@@ -0,0 +1,175 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::{
+    initfuncs::{init_dam_break_dry, init_dam_break_wet},
+    prelude::*,
+};
+const S: usize = 200;
+set_Physics_and_E!(ShallowWater);
+type T = RungeKuttaFehlberg<P, E, S>;
+
+const GRAVITY: f64 = 2.0;
+
+fn get_config<N: NumFlux<E, S> + 'static>(bathymetry: Bathymetry, t_end: f64, name: &str) -> CorriesConfig {
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(t_end, &format!("results/integrationtests/{}", name), name);
+    config.physics_config.systems.shallow_water.gravity = GRAVITY;
+    config.physics_config.systems.shallow_water.bathymetry = bathymetry;
+    config
+}
+
+/// Ritter's solution for the water depth of a dam break onto a dry bottom, with an initial depth
+/// of 1.0, `x` being the distance to the dam
+fn ritter_depth(x: f64, t: f64) -> f64 {
+    let c0 = GRAVITY.sqrt();
+    if x <= -c0 * t {
+        1.0
+    } else if x <= 2.0 * c0 * t {
+        (2.0 * c0 - x / t).powi(2) / (9.0 * GRAVITY)
+    } else {
+        0.0
+    }
+}
+
+fn run_dam_break_dry<N: NumFlux<E, S> + 'static>(name: &str) -> Result<()> {
+    let t_end = 0.1;
+    let mut components = get_config::<N>(Bathymetry::Flat, t_end, name)
+        .init_corries::<P, N, T, E, S>(init_dam_break_dry)
+        .context("While calling CorriesConfig::init_corries")?;
+    let (u, _, mesh, _) = &components;
+    let s = mesh.ixi_in..=mesh.ixi_out;
+    let xi_dam = mesh.xi_west[S / 2];
+    let depth = P::depth(&u.cent);
+    let water_initial: f64 = s.clone().map(|i| depth[i]).sum();
+    components.run_corries()?;
+
+    // the waves do not reach the edges of the mesh
+    let (u, _, mesh, _) = &components;
+    let depth = P::depth(&u.cent);
+    let water: f64 = s.clone().map(|i| depth[i]).sum();
+    assert_relative_eq!(water, water_initial, max_relative = 1.0e-12);
+
+    // the smeared out head of the rarefaction and the front running onto the dry bottom add most
+    // of the error
+    let error_l1 = s
+        .clone()
+        .map(|i| (u.cent.prim[[P::JRHO, i]] - ritter_depth(mesh.xi_cent[i] - xi_dam, t_end)).abs())
+        .sum::<f64>()
+        / mesh.n_comp as f64;
+    assert!(error_l1 < 1.0e-2, "error_l1 = {}", error_l1);
+    assert!(u.cent.prim.row(P::JRHO).iter().all(|&h| h >= 0.0));
+    Ok(())
+}
+
+#[test]
+fn dam_break_dry_hll() -> Result<()> {
+    run_dam_break_dry::<Hll<E, S>>("dam_break_dry_hll")
+}
+
+#[test]
+fn dam_break_dry_kt() -> Result<()> {
+    run_dam_break_dry::<Kt<E, S>>("dam_break_dry_kt")
+}
+
+#[test]
+fn dam_break_wet_launches_a_bore() -> Result<()> {
+    type N = Kt<E, S>;
+    let mut components = get_config::<N>(Bathymetry::Flat, 0.1, "dam_break_wet")
+        .init_corries::<P, N, T, E, S>(init_dam_break_wet)
+        .context("While calling CorriesConfig::init_corries")?;
+    components.run_corries()?;
+
+    // Stoker's solution has a plateau with h = 0.3962 and u = 0.7412 * sqrt(g) between the
+    // rarefaction and the bore, which spans 0.016 < x < 0.14 at t = 0.1
+    let u = &components.0;
+    let i_plateau = S / 2 + 15;
+    assert_relative_eq!(u.cent.prim[[P::JRHO, i_plateau]], 0.3962, max_relative = 1.0e-2);
+    assert_relative_eq!(
+        u.cent.prim[[P::JXI, i_plateau]],
+        0.7412 * GRAVITY.sqrt(),
+        max_relative = 1.0e-2
+    );
+    Ok(())
+}
+
+/// Smooth bump in the middle of the mesh, reaching up to 0.8
+fn bump(xi: f64) -> f64 {
+    let x = (xi - 1.5) / 0.2;
+    if x.abs() < 1.0 {
+        0.8 * (1.0 - x * x).powi(2)
+    } else {
+        0.0
+    }
+}
+
+/// A flat surface at an elevation of 1.0 without any motion
+fn init_lake_at_rest<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        u.cent.prim[[P::JRHO, i]] = 1.0 - u.cent.bottom[i];
+    }
+    Ok(())
+}
+
+fn run_lake_at_rest<N: NumFlux<E, S> + 'static>(name: &str) -> Result<()> {
+    let mut components = get_config::<N>(Bathymetry::Function(bump), 0.5, name)
+        .init_corries::<P, N, T, E, S>(init_lake_at_rest)
+        .context("While calling CorriesConfig::init_corries")?;
+    let depth_initial = components.0.cent.prim.row(P::JRHO).to_owned();
+    components.run_corries()?;
+
+    let u = &components.0;
+    assert_relative_eq!(u.cent.prim.row(P::JRHO), depth_initial, epsilon = 1.0e-12);
+    // the conservative row 0 carries the surface elevation, so it is 1.0 everywhere, while the
+    // depth is what is left above the bottom
+    assert!(u.cent.cons.row(P::JRHO).iter().all(|&x| (x - 1.0).abs() < 1.0e-12));
+    assert_relative_eq!(P::depth(&u.cent), depth_initial, epsilon = 1.0e-12);
+    assert!(u.cent.prim.row(P::JXI).iter().all(|&v| v.abs() < 1.0e-12));
+    Ok(())
+}
+
+#[test]
+fn lake_at_rest_hll() -> Result<()> {
+    run_lake_at_rest::<Hll<E, S>>("lake_at_rest_hll")
+}
+
+#[test]
+fn lake_at_rest_kt() -> Result<()> {
+    run_lake_at_rest::<Kt<E, S>>("lake_at_rest_kt")
+}
+
+#[test]
+fn bathymetry_is_rejected_for_other_systems() {
+    type PE = Euler1DIsot<S>;
+    type NE = Hll<2, S>;
+    type TE = RungeKuttaFehlberg<PE, 2, S>;
+    assert!(
+        get_config::<Hll<E, S>>(Bathymetry::Function(bump), 0.1, "bathymetry_euler")
+            .init_corries::<PE, NE, TE, 2, S>(corries::initfuncs::init_noh)
+            .is_err()
+    );
+}
+
+#[test]
+fn gravity_is_only_checked_for_shallow_water() {
+    type PE = Euler1DIsot<S>;
+    type NE = Hll<2, S>;
+    type TE = RungeKuttaFehlberg<PE, 2, S>;
+    let mut config = get_config::<Hll<E, S>>(Bathymetry::Flat, 0.1, "gravity_euler");
+    config.physics_config.systems.shallow_water.gravity = 0.0;
+    assert!(config
+        .clone()
+        .init_corries::<P, Hll<E, S>, T, E, S>(init_dam_break_wet)
+        .is_err());
+    assert!(config
+        .init_corries::<PE, NE, TE, 2, S>(corries::initfuncs::init_noh)
+        .is_ok());
+}
@@ -2,6 +2,7 @@
 // Author: Tommy Breslein (github.com/tbreslein)
 // License: MIT
 
+use approx::assert_relative_eq;
 use color_eyre::{eyre::Context, Result};
 use corries::{initfuncs::init_sod, prelude::*};
 const S: usize = 100;
@@ -13,6 +14,7 @@ fn get_config<N: NumFlux<E, S> + 'static, const E: usize>(folder_name: &str, fil
         physics_config: PhysicsConfig {
             units_mode: UnitsMode::SI,
             adiabatic_index: 1.4,
+            ..Default::default()
         },
         boundary_condition_west: BoundaryMode::NoGradients,
         boundary_condition_east: BoundaryMode::NoGradients,
@@ -48,3 +50,228 @@ fn sod_kt() -> Result<()> {
         .context("While calling CorriesConfig::init_corries")?
         .run_corries()
 }
+
+/// Sod test with a uniform shear flow along eta on top
+fn init_sod_shear<P, N, T, const E: usize>(
+    u: &mut State<P, E, S>,
+    solver: &mut Solver<P, N, T, E, S>,
+    mesh: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    init_sod(u, solver, mesh)?;
+    u.cent.prim.row_mut(P::JETA).fill(0.5);
+    Ok(())
+}
+
+#[test]
+fn sod_2d_matches_1d() -> Result<()> {
+    type P1 = Euler1DAdiabatic<S>;
+    type P2 = Euler2DAdiabatic<S>;
+    type N1 = Hll<3, S>;
+    type N2 = Hll<4, S>;
+    type T1 = RungeKuttaFehlberg<P1, 3, S>;
+    type T2 = RungeKuttaFehlberg<P2, 4, S>;
+
+    let mut components_1d = get_config::<N1, 3>("results/integrationtests/sod_1d", "sod_1d")
+        .init_corries::<P1, N1, T1, 3, S>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_1d.run_corries()?;
+    let mut components_2d = get_config::<N2, 4>("results/integrationtests/sod_2d", "sod_2d")
+        .init_corries::<P2, N2, T2, 4, S>(init_sod_shear)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_2d.run_corries()?;
+
+    // on a cartesian mesh, the eta velocity is just advected along, without affecting the rest
+    let (u_1d, u_2d) = (&components_1d.0, &components_2d.0);
+    assert_eq!(components_1d.1.timestep.t, components_2d.1.timestep.t);
+    assert_relative_eq!(
+        u_2d.cent.prim.row(P2::JRHO),
+        u_1d.cent.prim.row(P1::JRHO),
+        max_relative = 1.0e-12
+    );
+    assert_relative_eq!(
+        u_2d.cent.prim.row(P2::JXI),
+        u_1d.cent.prim.row(P1::JXI),
+        epsilon = 1.0e-12
+    );
+    assert_relative_eq!(
+        u_2d.cent.prim.row(P2::JPRESSURE),
+        u_1d.cent.prim.row(P1::JPRESSURE),
+        max_relative = 1.0e-12
+    );
+    assert_relative_eq!(
+        u_2d.cent.prim.row(P2::JETA),
+        u_1d.cent.prim.row(P1::JXI).mapv(|_| 0.5),
+        max_relative = 1.0e-12
+    );
+    Ok(())
+}
+
+/// Sod test, where the gas in the west is dyed with the first scalar, and the second scalar is
+/// uniform
+fn init_sod_dyed<P, N, T, const E: usize>(
+    u: &mut State<P, E, S>,
+    _: &mut Solver<P, N, T, E, S>,
+    _: &Mesh<S>,
+) -> Result<()>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        let west = i < breakpoint_index;
+        u.cent.prim[[P::JRHO, i]] = if west { 1.0 } else { 0.125 };
+        u.cent.prim[[P::JPRESSURE, i]] = if west { 1.0 } else { 0.1 };
+        u.cent.prim[[P::JSCALARS, i]] = if west { 1.0 } else { 0.0 };
+        u.cent.prim[[P::JSCALARS + 1, i]] = 0.5;
+    }
+    Ok(())
+}
+
+#[test]
+fn sod_scalars_are_advected_passively() -> Result<()> {
+    type P1 = Euler1DAdiabatic<S>;
+    type P2 = Euler1DAdiabaticScalars<5, S>;
+    type N1 = Kt<3, S>;
+    type N2 = Kt<5, S>;
+    type T1 = RungeKuttaFehlberg<P1, 3, S>;
+    type T2 = RungeKuttaFehlberg<P2, 5, S>;
+
+    let mut config_1d = get_config::<N1, 3>("results/integrationtests/sod_1d_ref", "sod_1d_ref");
+    config_1d.numerics_config.t_end = 0.1;
+    let mut components_1d = config_1d
+        .init_corries::<P1, N1, T1, 3, S>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    components_1d.run_corries()?;
+
+    let mut config = get_config::<N2, 5>("results/integrationtests/sod_scalars", "sod_scalars");
+    // short enough that the waves do not reach the edges of the mesh, where mass could leave it
+    config.numerics_config.t_end = 0.1;
+    config.writer_config[1]
+        .data_names
+        .extend([DataName::Scalar(0), DataName::Scalar(1)]);
+    let mut components = config
+        .init_corries::<P2, N2, T2, 5, S>(init_sod_dyed)
+        .context("While calling CorriesConfig::init_corries")?;
+    let totals_initial = totals(&components.0, &components.2);
+    components.run_corries()?;
+
+    // the scalars do not act back on the flow
+    let (u_1d, u) = (&components_1d.0, &components.0);
+    assert_eq!(components_1d.1.timestep.t, components.1.timestep.t);
+    for j in [P1::JRHO, P1::JXI, P1::JPRESSURE] {
+        assert_relative_eq!(u.cent.prim.row(j), u_1d.cent.prim.row(j), epsilon = 1.0e-12);
+    }
+
+    // the dye is conserved and stays bounded, and the uniform scalar stays uniform
+    let totals_final = totals(u, &components.2);
+    assert_relative_eq!(
+        totals_final[P2::JSCALARS],
+        totals_initial[P2::JSCALARS],
+        max_relative = 1.0e-12
+    );
+    let dye = P2::scalar(&u.cent, 0);
+    assert!(dye.iter().all(|&x| (-1.0e-12..=1.0 + 1.0e-12).contains(&x)));
+    assert_relative_eq!(P2::scalar(&u.cent, 1), dye.mapv(|_| 0.5), max_relative = 1.0e-12);
+
+    // the dye follows the contact, which has moved east, but not as far as the shock
+    let mesh = &components.2;
+    let xi_dyed = (mesh.ixi_in..=mesh.ixi_out).filter(|&i| dye[i] > 0.5).max().unwrap();
+    let xi_shocked = (mesh.ixi_in..=mesh.ixi_out)
+        .filter(|&i| u.cent.prim[[P2::JRHO, i]] > 0.2)
+        .max()
+        .unwrap();
+    assert!(mesh.xi_cent[xi_dyed] > 1.5);
+    assert!(xi_dyed < xi_shocked);
+    Ok(())
+}
+
+fn totals<P: Physics<E, S>, const E: usize>(u: &State<P, E, S>, mesh: &Mesh<S>) -> [f64; E] {
+    let mut totals = [0.0; E];
+    for (j, total) in totals.iter_mut().enumerate() {
+        *total = (mesh.ixi_in..=mesh.ixi_out)
+            .map(|i| u.cent.cons[[j, i]] * mesh.volume[i])
+            .sum();
+    }
+    totals
+}
+
+fn run_moving_mesh<P, N, const E: usize>(
+    mesh_motion: MeshMotion,
+    name: &str,
+) -> Result<CorriesComponents<P, N, RungeKuttaFehlberg<P, E, S>, E, S>>
+where
+    P: Physics<E, S> + 'static,
+    N: NumFlux<E, S> + 'static,
+{
+    let mut config = get_config::<N, E>(&format!("results/integrationtests/{}", name), name);
+    // short enough that the waves do not reach the edges of the mesh, where mass could leave it
+    config.numerics_config.t_end = 0.1;
+    config.numerics_config.mesh_motion = mesh_motion;
+    let mut components = config
+        .init_corries::<P, N, RungeKuttaFehlberg<P, E, S>, E, S>(init_sod)
+        .context("While calling CorriesConfig::init_corries")?;
+    let totals_initial = totals(&components.0, &components.2);
+
+    components.run_corries()?;
+
+    let (u, solver, mesh, _) = &components;
+    assert!(solver.timestep.t >= 0.1 - 1.0e-12);
+    assert_eq!(mesh.xi_west[mesh.ixi_in], mesh.xi_in);
+    assert_eq!(mesh.xi_east[mesh.ixi_out], mesh.xi_out);
+    let totals_final = totals(u, mesh);
+    assert_relative_eq!(totals_final[P::JRHO], totals_initial[P::JRHO], max_relative = 1.0e-12);
+    // the conservative energy shares its index with the primitive pressure
+    assert_relative_eq!(
+        totals_final[P::JPRESSURE],
+        totals_initial[P::JPRESSURE],
+        max_relative = 1.0e-12
+    );
+    Ok(components)
+}
+
+#[test]
+fn sod_lagrangian_hll() -> Result<()> {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Hll<E, S>;
+
+    let (_, _, mesh, _) =
+        run_moving_mesh::<P, N, E>(MeshMotion::Lagrangian { remap_interval: None }, "sod_lagrangian_hll")?;
+
+    // the cells in the rarefaction expand, and the cells behind the shock are compressed
+    let cell_width = &mesh.cell_width;
+    let comp = mesh.ixi_in..=mesh.ixi_out;
+    let i_min = comp
+        .clone()
+        .reduce(|k, i| if cell_width[i] < cell_width[k] { i } else { k })
+        .unwrap();
+    let i_max = comp
+        .reduce(|k, i| if cell_width[i] > cell_width[k] { i } else { k })
+        .unwrap();
+    let width_initial = (mesh.xi_out - mesh.xi_in) / mesh.n_comp as f64;
+    assert!(cell_width[i_min] < 0.9 * width_initial);
+    assert!(cell_width[i_max] > 1.1 * width_initial);
+    assert!(i_max < i_min);
+    Ok(())
+}
+
+#[test]
+fn sod_ale_kt() -> Result<()> {
+    set_Physics_and_E!(Euler1DAdiabatic);
+    type N = Kt<E, S>;
+
+    run_moving_mesh::<P, N, E>(
+        MeshMotion::Lagrangian {
+            remap_interval: Some(5),
+        },
+        "sod_ale_kt",
+    )?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2023
+// Author: Tommy Breslein (github.com/tbreslein)
+// License: MIT
+
+use approx::assert_relative_eq;
+use color_eyre::{eyre::Context, Result};
+use corries::prelude::*;
+const S: usize = 200;
+set_Physics_and_E!(Srhd);
+
+/// Blast wave with a relativistic shell, i.e. problem 1 from Marti & Müller (2003), who give the
+/// solution at t = 0.4
+fn init_blast_wave<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    let breakpoint_index = (S as f64 * 0.5) as usize;
+    u.cent.prim.fill(0.0);
+    for i in 0..S {
+        let west = i < breakpoint_index;
+        u.cent.prim[[P::JRHO, i]] = if west { 10.0 } else { 1.0 };
+        u.cent.prim[[P::JPRESSURE, i]] = if west { 13.33 } else { 1.0e-6 };
+    }
+    Ok(())
+}
+
+fn run_blast_wave<N: NumFlux<E, S> + 'static>(name: &str) -> Result<()> {
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let mut config =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.4, &format!("results/integrationtests/{}", name), name);
+    // reflecting walls, so that the faint numerical precursors of the waves cannot leave the mesh
+    let walls = BoundaryMode::Custom(vec![
+        (P::JRHO, CustomBoundaryMode::NoGradients),
+        (P::JXI, CustomBoundaryMode::Reflecting),
+        (P::JPRESSURE, CustomBoundaryMode::NoGradients),
+    ]);
+    config.boundary_condition_west = walls.clone();
+    config.boundary_condition_east = walls;
+    let mut components = config
+        .init_corries::<P, N, T, E, S>(init_blast_wave)
+        .context("While calling CorriesConfig::init_corries")?;
+    let mass = |u: &State<P, E, S>, mesh: &Mesh<S>| -> f64 {
+        (mesh.ixi_in..=mesh.ixi_out).map(|i| u.cent.cons[[P::JRHO, i]]).sum()
+    };
+    let mass_initial = mass(&components.0, &components.2);
+    components.run_corries()?;
+
+    // the waves stay away from the edges of the mesh, so the rest mass in the computational area is
+    // conserved
+    let u = &components.0;
+    assert_relative_eq!(mass(u, &components.2), mass_initial, max_relative = 1.0e-10);
+
+    // between the rarefaction and the shock, the gas moves with v = 0.714 at p = 1.448; take the
+    // fastest cell to find that region, since the shell behind the shock is smeared out
+    let i_max = (0..S)
+        .max_by(|&i, &k| u.cent.prim[[P::JXI, i]].total_cmp(&u.cent.prim[[P::JXI, k]]))
+        .unwrap();
+    assert_relative_eq!(u.cent.prim[[P::JXI, i_max]], 0.714, max_relative = 0.03);
+    assert_relative_eq!(u.cent.prim[[P::JPRESSURE, i_max]], 1.448, max_relative = 0.1);
+    assert!(u.cent.prim.row(P::JXI).iter().all(|&v| v.abs() < 1.0));
+    Ok(())
+}
+
+#[test]
+fn blast_wave_hll() -> Result<()> {
+    run_blast_wave::<Hll<E, S>>("srhd_blast_wave_hll")
+}
+
+#[test]
+fn blast_wave_kt() -> Result<()> {
+    run_blast_wave::<Kt<E, S>>("srhd_blast_wave_kt")
+}
+
+/// Sets up a state that moves at the speed of light, which cannot be converted back from the
+/// conservative variables
+fn init_luminal<N, T>(u: &mut State<P, E, S>, _: &mut Solver<P, N, T, E, S>, _: &Mesh<S>) -> Result<()>
+where
+    N: NumFlux<E, S>,
+    T: TimeSolver<P, E, S>,
+{
+    u.cent.prim.row_mut(P::JRHO).fill(1.0);
+    u.cent.prim.row_mut(P::JXI).fill(1.0);
+    u.cent.prim.row_mut(P::JPRESSURE).fill(1.0);
+    Ok(())
+}
+
+#[test]
+fn failed_recovery_is_reported() -> Result<()> {
+    type N = Hll<E, S>;
+    type T = RungeKuttaFehlberg<P, E, S>;
+    let name = "srhd_failed_recovery";
+    let mut components =
+        CorriesConfig::default_riemann_test::<N, E, S>(0.4, &format!("results/integrationtests/{}", name), name)
+            .init_corries::<P, N, T, E, S>(init_luminal)
+            .context("While calling CorriesConfig::init_corries")?;
+    let err = components.run_corries().unwrap_err();
+    assert!(format!("{:?}", err).contains("Recovering the primitive variables"));
+    Ok(())
+}